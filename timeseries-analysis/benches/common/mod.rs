@@ -0,0 +1,38 @@
+//! Utilitários de medição compartilhados pelos benchmarks (sem dependências externas)
+//!
+//! Cada arquivo em `benches/` declara `mod common;` e chama [`bench_fn`].
+
+use std::time::{Duration, Instant};
+
+/// Chamadas de aquecimento antes da medição, para encher caches e estabilizar a CPU
+const WARMUP_CALLS: u32 = 2;
+
+/// Mede `f` em `iterations` chamadas após o aquecimento e imprime o tempo por chamada
+/// e por elemento
+///
+/// `elements` é o tamanho da entrada processada em cada chamada. Retorna o tempo médio
+/// por chamada, para quem quiser comparar resultados no próprio benchmark.
+pub fn bench_fn<F: FnMut()>(name: &str, elements: usize, iterations: u32, mut f: F) -> Duration {
+    for _ in 0..WARMUP_CALLS {
+        f();
+    }
+    let iterations = iterations.max(1);
+    let start = Instant::now();
+    for _ in 0..iterations {
+        f();
+    }
+    let per_call = start.elapsed() / iterations;
+    println!(
+        "{:<28} n={:<9} {:>12.3?}/chamada  {:>8.2} ns/elemento",
+        name,
+        elements,
+        per_call,
+        per_call.as_nanos() as f64 / elements.max(1) as f64
+    );
+    per_call
+}
+
+/// Número de iterações para que cada medição processe cerca de `budget` elementos
+pub fn iterations_for(len: usize, budget: usize) -> u32 {
+    (budget / len.max(1)).clamp(3, 1_000) as u32
+}
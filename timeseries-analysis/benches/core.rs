@@ -0,0 +1,49 @@
+//! Tempo por elemento das funções centrais em séries sintéticas de 1 mil a 10 milhões de pontos
+//!
+//! Rode com `cargo bench --bench core`. Para medir uma função nova, acrescente uma chamada a
+//! `common::bench_fn` em `main`.
+
+mod common;
+
+use std::hint::black_box;
+
+use timeseries_analysis::generators::seasonal_series;
+use timeseries_analysis::{
+    autocorrelation, calculate_descriptive_stats, linear_regression, residual_diagnostics, rolling_max, rolling_mean,
+    rolling_std,
+};
+
+const WINDOW: usize = 24;
+
+fn main() {
+    for &len in &[1_000, 100_000, 10_000_000] {
+        let data = seasonal_series(len, 24, 10.0, 0.05, 1.0, 42).unwrap();
+        let iterations = common::iterations_for(len, 50_000_000);
+
+        common::bench_fn("linear_regression", len, iterations, || {
+            black_box(linear_regression(black_box(&data)).unwrap());
+        });
+        common::bench_fn("calculate_descriptive_stats", len, iterations, || {
+            black_box(calculate_descriptive_stats(black_box(&data)).unwrap());
+        });
+        common::bench_fn("rolling_mean", len, iterations, || {
+            black_box(rolling_mean(black_box(&data), WINDOW).unwrap());
+        });
+        common::bench_fn("rolling_std", len, iterations, || {
+            black_box(rolling_std(black_box(&data), WINDOW).unwrap());
+        });
+        common::bench_fn("rolling_max", len, iterations, || {
+            black_box(rolling_max(black_box(&data), WINDOW).unwrap());
+        });
+
+        common::bench_fn("autocorrelation (24 lags)", len, iterations, || {
+            black_box(autocorrelation(black_box(&data), WINDOW).unwrap());
+        });
+
+        // Autocorrelação de lag 1 (e demais diagnósticos) dos resíduos do ajuste
+        let result = linear_regression(&data).unwrap();
+        common::bench_fn("residual_diagnostics", len, iterations, || {
+            black_box(residual_diagnostics(black_box(&data), &result).unwrap());
+        });
+    }
+}
@@ -0,0 +1,48 @@
+//! Compara a regressão linear em passagem única com a versão antiga em várias passagens
+//!
+//! Rode com `cargo bench --bench regression`.
+
+mod common;
+
+use std::hint::black_box;
+
+use timeseries_analysis::{calculate_mse, calculate_r_squared, linear_regression};
+
+/// Implementação anterior: vetor de x, médias, somatórios centrados e mais duas
+/// passagens para MSE e R²
+fn multi_pass_regression(data: &[f64]) -> (f64, f64, f64, f64) {
+    let n = data.len() as f64;
+    let x: Vec<f64> = (0..data.len()).map(|x| x as f64).collect();
+    let x_mean = x.iter().sum::<f64>() / n;
+    let y_mean = data.iter().sum::<f64>() / n;
+
+    let mut numerator = 0.0;
+    let mut denominator = 0.0;
+    for i in 0..data.len() {
+        numerator += (x[i] - x_mean) * (data[i] - y_mean);
+        denominator += (x[i] - x_mean).powi(2);
+    }
+    let slope = numerator / denominator;
+    let intercept = y_mean - slope * x_mean;
+
+    let predictions: Vec<f64> = x.iter().map(|&xi| intercept + slope * xi).collect();
+    let mse = calculate_mse(data, &predictions).unwrap();
+    let r_squared = calculate_r_squared(data, &predictions).unwrap();
+    (slope, intercept, mse, r_squared)
+}
+
+fn main() {
+    for &len in &[1_000, 100_000, 5_000_000] {
+        let data: Vec<f64> = (0..len)
+            .map(|i| 1e6 + 0.25 * i as f64 + ((i * 7919) % 101) as f64)
+            .collect();
+        let iterations = common::iterations_for(len, 50_000_000);
+
+        common::bench_fn("passagem unica", len, iterations, || {
+            black_box(linear_regression(black_box(&data)).unwrap());
+        });
+        common::bench_fn("varias passagens (antiga)", len, iterations, || {
+            black_box(multi_pass_regression(black_box(&data)));
+        });
+    }
+}
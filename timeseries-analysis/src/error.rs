@@ -0,0 +1,328 @@
+//! Tipo de erro único da biblioteca
+//!
+//! Todas as funções falíveis devolvem [`TimeSeriesError`]; [`TimeSeriesError::kind`] permite
+//! tratar o erro por categoria sem depender da mensagem.
+//!
+//! ```
+//! use timeseries_analysis::error::{ErrorKind, TimeSeriesError};
+//! use timeseries_analysis::regression::linear_regression;
+//!
+//! let err: TimeSeriesError = linear_regression(&[1.0]).unwrap_err();
+//! assert_eq!(err.kind(), ErrorKind::InsufficientData);
+//! ```
+
+use crate::*;
+use core::sync::atomic::{AtomicU8, Ordering};
+
+/// Idioma das mensagens legíveis (erros e legendas dos gráficos)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Language {
+    /// Português, o idioma padrão do `Display` de [`TimeSeriesError`]
+    #[default]
+    Pt,
+    /// Inglês
+    En,
+}
+
+static MESSAGE_LANGUAGE: AtomicU8 = AtomicU8::new(Language::Pt as u8);
+
+/// Define o idioma do `Display` de [`TimeSeriesError`] para todo o processo
+///
+/// O padrão é [`Language::Pt`]. Para escolher o idioma de uma mensagem isolada, sem estado
+/// global, use [`TimeSeriesError::to_localized_string`].
+pub fn set_message_language(language: Language) {
+    MESSAGE_LANGUAGE.store(language as u8, Ordering::Relaxed);
+}
+
+/// Idioma atual do `Display` de [`TimeSeriesError`] (veja [`set_message_language`])
+pub fn message_language() -> Language {
+    match MESSAGE_LANGUAGE.load(Ordering::Relaxed) {
+        value if value == Language::En as u8 => Language::En,
+        _ => Language::Pt,
+    }
+}
+
+/// Texto livre de um erro nos dois idiomas
+///
+/// Criado a partir de um `&str` ou `String`, o mesmo texto vale para os dois idiomas.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalizedText {
+    pub pt: String,
+    pub en: String,
+}
+
+impl LocalizedText {
+    pub fn new(pt: impl Into<String>, en: impl Into<String>) -> Self {
+        LocalizedText {
+            pt: pt.into(),
+            en: en.into(),
+        }
+    }
+
+    /// Texto no idioma pedido
+    pub fn get(&self, language: Language) -> &str {
+        match language {
+            Language::Pt => &self.pt,
+            Language::En => &self.en,
+        }
+    }
+}
+
+impl From<&str> for LocalizedText {
+    fn from(text: &str) -> Self {
+        LocalizedText::new(text, text)
+    }
+}
+
+impl From<String> for LocalizedText {
+    fn from(text: String) -> Self {
+        LocalizedText::new(text.clone(), text)
+    }
+}
+
+/// Erros na análise de séries temporais
+///
+/// As variantes carregam os dados estruturados do erro para tratamento programático;
+/// a mensagem legível fica a cargo do `Display`, no idioma de [`set_message_language`], ou de
+/// [`to_localized_string`](Self::to_localized_string). O `reason` de
+/// [`InvalidParameter`](Self::InvalidParameter) e a mensagem de [`Other`](Self::Other) são
+/// [`LocalizedText`]: os erros da biblioteca trazem os dois idiomas.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TimeSeriesError {
+    /// Menos pontos do que o mínimo necessário
+    InsufficientData { required: usize, got: usize },
+    /// Séries que deveriam ter o mesmo tamanho não têm
+    LengthMismatch { left: usize, right: usize },
+    /// Parâmetro fora do domínio válido
+    InvalidParameter { name: &'static str, reason: LocalizedText },
+    /// NaN ou infinito na posição indicada
+    NonFiniteValue { index: usize },
+    /// Sistema de equações sem solução única (preditores colineares)
+    SingularMatrix,
+    /// Demais erros, apenas com mensagem
+    Other(LocalizedText),
+    /// Erro ocorrido numa etapa nomeada de um pipeline (ex.: [`TimeSeriesAnalysis`])
+    Stage {
+        stage: &'static str,
+        source: Box<TimeSeriesError>,
+    },
+}
+
+/// Categoria de um [`TimeSeriesError`], sem os dados associados
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    InsufficientData,
+    LengthMismatch,
+    InvalidParameter,
+    NonFiniteValue,
+    SingularMatrix,
+    Other,
+}
+
+impl TimeSeriesError {
+    /// Cria um erro genérico apenas com mensagem (um texto só ou [`LocalizedText`])
+    pub fn new(msg: impl Into<LocalizedText>) -> Self {
+        TimeSeriesError::Other(msg.into())
+    }
+
+    /// Atalho para [`TimeSeriesError::InsufficientData`]
+    pub fn insufficient_data(required: usize, got: usize) -> Self {
+        TimeSeriesError::InsufficientData { required, got }
+    }
+
+    /// Atalho para [`TimeSeriesError::InvalidParameter`]
+    pub fn invalid_parameter(name: &'static str, reason: impl Into<LocalizedText>) -> Self {
+        TimeSeriesError::InvalidParameter {
+            name,
+            reason: reason.into(),
+        }
+    }
+
+    /// Anexa o nome da etapa em que o erro ocorreu
+    pub fn in_stage(self, stage: &'static str) -> Self {
+        TimeSeriesError::Stage {
+            stage,
+            source: Box::new(self),
+        }
+    }
+
+    /// Categoria do erro (para [`TimeSeriesError::Stage`], a do erro original)
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            TimeSeriesError::InsufficientData { .. } => ErrorKind::InsufficientData,
+            TimeSeriesError::LengthMismatch { .. } => ErrorKind::LengthMismatch,
+            TimeSeriesError::InvalidParameter { .. } => ErrorKind::InvalidParameter,
+            TimeSeriesError::NonFiniteValue { .. } => ErrorKind::NonFiniteValue,
+            TimeSeriesError::SingularMatrix => ErrorKind::SingularMatrix,
+            TimeSeriesError::Other(_) => ErrorKind::Other,
+            TimeSeriesError::Stage { source, .. } => source.kind(),
+        }
+    }
+
+    /// Mensagem legível no idioma pedido, no mesmo formato do `Display`
+    ///
+    /// ```
+    /// use timeseries_analysis::{Language, TimeSeriesError};
+    ///
+    /// let err = TimeSeriesError::insufficient_data(3, 1);
+    /// assert_eq!(
+    ///     err.to_localized_string(Language::En),
+    ///     "TimeSeriesError: Insufficient data: 3 point(s) required, 1 received"
+    /// );
+    /// ```
+    pub fn to_localized_string(&self, language: Language) -> String {
+        let mut out = String::from("TimeSeriesError: ");
+        self.write_message(&mut out, language).expect("escrita em String não falha");
+        out
+    }
+
+    fn write_message<W: fmt::Write>(&self, f: &mut W, language: Language) -> fmt::Result {
+        match (self, language) {
+            (TimeSeriesError::InsufficientData { required, got }, Language::Pt) => write!(
+                f,
+                "Dados insuficientes: necessário(s) {} ponto(s), recebido(s) {}",
+                required, got
+            ),
+            (TimeSeriesError::InsufficientData { required, got }, Language::En) => write!(
+                f,
+                "Insufficient data: {} point(s) required, {} received",
+                required, got
+            ),
+            (TimeSeriesError::LengthMismatch { left, right }, Language::Pt) => write!(
+                f,
+                "Séries com tamanhos diferentes: {} e {}",
+                left, right
+            ),
+            (TimeSeriesError::LengthMismatch { left, right }, Language::En) => write!(
+                f,
+                "Series lengths differ: {} and {}",
+                left, right
+            ),
+            (TimeSeriesError::InvalidParameter { name, reason }, Language::Pt) => {
+                write!(f, "Parâmetro inválido '{}': {}", name, reason.pt)
+            }
+            (TimeSeriesError::InvalidParameter { name, reason }, Language::En) => {
+                write!(f, "Invalid parameter '{}': {}", name, reason.en)
+            }
+            (TimeSeriesError::NonFiniteValue { index }, Language::Pt) => {
+                write!(f, "Valor não finito no índice {}", index)
+            }
+            (TimeSeriesError::NonFiniteValue { index }, Language::En) => {
+                write!(f, "Non-finite value at index {}", index)
+            }
+            (TimeSeriesError::SingularMatrix, Language::Pt) => {
+                write!(f, "Sistema linear singular: preditores colineares")
+            }
+            (TimeSeriesError::SingularMatrix, Language::En) => {
+                write!(f, "Singular linear system: collinear predictors")
+            }
+            (TimeSeriesError::Other(message), _) => write!(f, "{}", message.get(language)),
+            (TimeSeriesError::Stage { stage, source }, _) => {
+                match language {
+                    Language::Pt => write!(f, "Etapa '{}' falhou: ", stage)?,
+                    Language::En => write!(f, "Stage '{}' failed: ", stage)?,
+                }
+                source.write_message(f, language)
+            }
+        }
+    }
+}
+
+impl fmt::Display for TimeSeriesError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TimeSeriesError: ")?;
+        self.write_message(f, message_language())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TimeSeriesError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TimeSeriesError::Stage { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod testes {
+    use super::*;
+
+    #[test]
+    fn test_erros_estruturados() {
+        assert_eq!(
+            linear_regression(&[1.0]).unwrap_err(),
+            TimeSeriesError::InsufficientData { required: 2, got: 1 }
+        );
+        assert_eq!(
+            evaluate(&[1.0, 2.0], &[1.0], ErrorMetric::Mae).unwrap_err(),
+            TimeSeriesError::LengthMismatch { left: 2, right: 1 }
+        );
+        assert_eq!(
+            calculate_descriptive_stats(&[1.0, f64::NAN]).unwrap_err(),
+            TimeSeriesError::NonFiniteValue { index: 1 }
+        );
+        match percentile(&[1.0], 101.0).unwrap_err() {
+            TimeSeriesError::InvalidParameter { name, .. } => assert_eq!(name, "p"),
+            other => panic!("erro inesperado: {:?}", other),
+        }
+
+        let too_short = polynomial_regression(&[1.0, 2.0, 3.0], 3).unwrap_err();
+        assert_eq!(too_short.kind(), ErrorKind::InsufficientData);
+        assert_eq!(skewness(&[2.0, 2.0, 2.0]).unwrap_err().kind(), ErrorKind::Other);
+    }
+
+    #[test]
+    fn test_erros_display() {
+        let err = TimeSeriesError::insufficient_data(2, 1);
+        assert_eq!(
+            err.to_string(),
+            "TimeSeriesError: Dados insuficientes: necessário(s) 2 ponto(s), recebido(s) 1"
+        );
+        assert_eq!(TimeSeriesError::new("falha").to_string(), "TimeSeriesError: falha");
+        assert_eq!(TimeSeriesError::SingularMatrix.kind(), ErrorKind::SingularMatrix);
+    }
+
+    #[test]
+    fn test_mensagens_de_erro_nos_dois_idiomas() {
+        let insufficient = linear_regression(&[1.0]).unwrap_err();
+        assert_eq!(insufficient, TimeSeriesError::InsufficientData { required: 2, got: 1 });
+        assert_eq!(insufficient.to_localized_string(Language::Pt), insufficient.to_string());
+        assert_eq!(
+            insufficient.to_string(),
+            "TimeSeriesError: Dados insuficientes: necessário(s) 2 ponto(s), recebido(s) 1"
+        );
+        assert_eq!(
+            insufficient.to_localized_string(Language::En),
+            "TimeSeriesError: Insufficient data: 2 point(s) required, 1 received"
+        );
+
+        let mismatch = calculate_r_squared(&[1.0, 2.0], &[1.0]).unwrap_err();
+        assert_eq!(mismatch.kind(), ErrorKind::LengthMismatch);
+        assert_eq!(mismatch.to_string(), "TimeSeriesError: Séries com tamanhos diferentes: 2 e 1");
+        assert_eq!(mismatch.to_localized_string(Language::En), "TimeSeriesError: Series lengths differ: 2 and 1");
+
+        let invalid = percentile(&[1.0, 2.0], 101.0).unwrap_err();
+        assert_eq!(
+            invalid.to_localized_string(Language::En),
+            "TimeSeriesError: Invalid parameter 'p': must be between 0 and 100"
+        );
+        assert_eq!(
+            invalid.to_string(),
+            "TimeSeriesError: Parâmetro inválido 'p': deve estar entre 0 e 100"
+        );
+        let other = skewness(&[2.0, 2.0, 2.0]).unwrap_err();
+        assert_eq!(
+            other.to_localized_string(Language::En),
+            "TimeSeriesError: Skewness undefined for a series with zero variance"
+        );
+        assert_eq!(TimeSeriesError::new("falha").to_localized_string(Language::En), "TimeSeriesError: falha");
+
+        let staged = mismatch.in_stage("fit");
+        assert_eq!(
+            staged.to_localized_string(Language::En),
+            "TimeSeriesError: Stage 'fit' failed: Series lengths differ: 2 and 1"
+        );
+    }
+}
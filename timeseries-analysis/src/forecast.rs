@@ -0,0 +1,1557 @@
+//! Previsão, validação e seleção de modelos
+//!
+//! Extrapolação da reta ajustada, previsões por quantis, backtest, validação cruzada com
+//! origem móvel, ensembles e o pipeline [`TimeSeriesAnalysis`].
+//!
+//! ```
+//! use timeseries_analysis::forecast::predict_future;
+//! use timeseries_analysis::regression::linear_regression;
+//!
+//! let result = linear_regression(&[1.0, 2.0, 3.0]).unwrap();
+//! assert_eq!(predict_future(&result, 2), vec![4.0, 5.0]);
+//! ```
+
+use crate::*;
+
+/// Ponto previsto além do fim da série
+///
+/// `period` é o índice absoluto a partir de 0, como em `predictions`: a primeira
+/// previsão de uma série com `n` observações tem `period == n`. Sem intervalo de
+/// previsão, `lower` e `upper` são iguais a `value`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ForecastPoint<T: Float = f64> {
+    pub period: usize,
+    pub x: T,
+    pub value: T,
+    /// Limite inferior do intervalo de previsão
+    pub lower: T,
+    /// Limite superior do intervalo de previsão
+    pub upper: T,
+}
+
+impl<T: Float> ForecastPoint<T> {
+    /// Número do período a partir de 1, como exibido nos relatórios
+    pub fn period_number(&self) -> usize {
+        self.period + 1
+    }
+}
+
+/// Realiza previsões futuras usando os coeficientes da regressão linear
+///
+/// Equivale aos valores de [`LinearRegressionResult::forecast`].
+pub fn predict_future<T: Float>(result: &LinearRegressionResult<T>, future_periods: usize) -> Vec<T> {
+    result.forecast(future_periods).into_iter().map(|point| point.value).collect()
+}
+
+/// Valor da reta ajustada no período `period` (0 é a primeira observação; aceita frações)
+pub fn value_at(result: &LinearRegressionResult, period: f64) -> f64 {
+    result.intercept + result.slope * period
+}
+
+/// Quantos períodos após a última observação a reta ajustada atinge `target`
+///
+/// Resolve `intercept + slope·x = target` e retorna `x - (n - 1)`. Um valor negativo
+/// indica que, pela tendência, o alvo já foi cruzado esse número de períodos antes da
+/// última observação — o que inclui alvos na direção oposta à da tendência. Erro se a
+/// inclinação for praticamente nula.
+pub fn periods_until(result: &LinearRegressionResult, target: f64) -> Result<f64, TimeSeriesError> {
+    if !target.is_finite() {
+        return Err(TimeSeriesError::invalid_parameter(
+            "target",
+            LocalizedText::new("deve ser finito", "must be finite"),
+        ));
+    }
+    if result.count == 0 {
+        return Err(TimeSeriesError::insufficient_data(1, 0));
+    }
+    let last = (result.count - 1) as f64;
+    // A variação da reta ao longo dos dados é comparada ao nível dos valores ajustados
+    let scale = result.intercept.abs().max(value_at(result, last).abs());
+    if approx_zero(result.slope * last.max(1.0), scale) {
+        return Err(TimeSeriesError::new(
+            LocalizedText::new(
+                "Tendência nula: o alvo nunca é atingido",
+                "Flat trend: the target is never reached",
+            ),
+        ));
+    }
+    Ok((target - result.intercept) / result.slope - last)
+}
+
+/// Previsões de [`predict_future`] rotuladas com datas; `start` é a data do primeiro
+/// período previsto
+pub fn forecast_with_dates(
+    result: &LinearRegressionResult,
+    periods: usize,
+    start: Date,
+    freq: Frequency,
+) -> Vec<(String, f64)> {
+    label_periods(start, freq, periods)
+        .into_iter()
+        .zip(predict_future(result, periods))
+        .collect()
+}
+
+/// Faixas de previsão pelos quantis empíricos dos resíduos dentro da amostra
+///
+/// Cada caminho é a previsão pontual de [`predict_future`] deslocada pelo quantil `q` dos
+/// resíduos `actual - previsto`, sem supor normalidade. Os caminhos saem na ordem de
+/// `quantiles` e nunca se cruzam: os deslocamentos são ordenados junto com os quantis.
+/// Cada quantil deve estar em (0, 1).
+pub fn quantile_forecast(
+    actual: &[f64],
+    result: &LinearRegressionResult,
+    periods: usize,
+    quantiles: &[f64],
+) -> Result<Vec<Vec<f64>>, TimeSeriesError> {
+    if let Some(q) = quantiles.iter().find(|q| !(**q > 0.0 && **q < 1.0)) {
+        return Err(TimeSeriesError::invalid_parameter(
+            "quantiles",
+            LocalizedText::new(
+                format!("{} fora do intervalo (0, 1)", q),
+                format!("{} outside the interval (0, 1)", q),
+            ),
+        ));
+    }
+    let residuals = sorted_copy(&regression_residuals(actual, result, 2)?);
+
+    let mut offsets: Vec<f64> = quantiles.iter().map(|q| percentile_sorted(&residuals, q * 100.0)).collect();
+    let mut order: Vec<usize> = (0..quantiles.len()).collect();
+    order.sort_by(|&i, &j| quantiles[i].total_cmp(&quantiles[j]));
+    let mut sorted_offsets: Vec<f64> = order.iter().map(|&i| offsets[i]).collect();
+    sorted_offsets.sort_by(f64::total_cmp);
+    for (&i, offset) in order.iter().zip(sorted_offsets) {
+        offsets[i] = offset;
+    }
+
+    let point = predict_future(result, periods);
+    Ok(offsets
+        .iter()
+        .map(|offset| point.iter().map(|p| p + offset).collect())
+        .collect())
+}
+
+/// Divide a série em treino (início) e teste (últimos `test_size` pontos)
+///
+/// A ordem temporal é preservada; exige `test_size >= 1` e ao menos 2 pontos de treino.
+pub fn train_test_split(data: &[f64], test_size: usize) -> Result<(&[f64], &[f64]), TimeSeriesError> {
+    if test_size == 0 {
+        return Err(TimeSeriesError::invalid_parameter(
+            "test_size",
+            LocalizedText::new("deve ser maior que zero", "must be greater than zero"),
+        ));
+    }
+    if data.len() < test_size + 2 {
+        return Err(TimeSeriesError::insufficient_data(test_size + 2, data.len()));
+    }
+    validate_finite(data)?;
+    Ok(data.split_at(data.len() - test_size))
+}
+
+/// Resultado de um backtest com conjunto de validação (holdout)
+#[derive(Debug, Clone, PartialEq)]
+pub struct BacktestReport {
+    /// Previsões para o período de teste
+    pub forecasts: Vec<f64>,
+    /// Valores reais do período de teste
+    pub actual: Vec<f64>,
+    pub mse: f64,
+    pub mae: f64,
+    /// `None` quando algum valor real do teste é zero
+    pub mape: Option<f64>,
+}
+
+/// Ajusta a regressão linear no treino e mede o erro das previsões no teste
+pub fn backtest(data: &[f64], test_size: usize) -> Result<BacktestReport, TimeSeriesError> {
+    let (train, test) = train_test_split(data, test_size)?;
+    let model = linear_regression(train)?;
+    let forecasts = predict_future(&model, test_size);
+
+    Ok(BacktestReport {
+        mse: calculate_mse(test, &forecasts)?,
+        mae: calculate_mae(test, &forecasts)?,
+        mape: calculate_mape(test, &forecasts)?,
+        actual: test.to_vec(),
+        forecasts,
+    })
+}
+
+/// Resultado de [`intervention_analysis`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct InterventionResult {
+    pub event_index: usize,
+    /// Ajuste linear dos dados anteriores ao evento
+    pub pre_fit: LinearRegressionResult,
+    /// Projeção da tendência anterior sobre a janela pós-evento (o contrafactual)
+    pub counterfactual: Vec<f64>,
+    /// `real - contrafactual` em cada período da janela
+    pub differences: Vec<f64>,
+    /// Média de `differences`: o efeito estimado por período
+    pub average_effect: f64,
+    /// Soma de `differences`: o efeito acumulado na janela
+    pub cumulative_effect: f64,
+    /// Erro padrão aproximado de `average_effect`
+    pub standard_error: f64,
+    /// `average_effect / standard_error`
+    pub z_statistic: f64,
+    /// p-valor bilateral pela aproximação normal
+    pub p_value: f64,
+    /// `p_value < 0.05`
+    pub significant: bool,
+}
+
+/// Estima o efeito de uma intervenção no período `event_index` comparando os `window`
+/// períodos seguintes com a tendência anterior projetada
+///
+/// A reta é ajustada com [`linear_regression`] em `data[..event_index]` e projetada com
+/// [`predict_future`] sobre `data[event_index..event_index + window]`. A avaliação de
+/// significância é aproximada: supõe resíduos independentes com a variância residual
+/// `s² = RSS/(m - 2)` do trecho anterior (`m` pontos), de modo que
+/// `erro padrão² = s²·(1/window + 1/m + (x̄_pós - x̄_pré)²/Sxx)`. Exige ao menos 3 pontos
+/// antes do evento e `window` pontos a partir dele.
+pub fn intervention_analysis(
+    data: &[f64],
+    event_index: usize,
+    window: usize,
+) -> Result<InterventionResult, TimeSeriesError> {
+    if window == 0 {
+        return Err(TimeSeriesError::invalid_parameter(
+            "window",
+            LocalizedText::new("deve ser maior que zero", "must be greater than zero"),
+        ));
+    }
+    if event_index < 3 {
+        return Err(TimeSeriesError::invalid_parameter(
+            "event_index",
+            LocalizedText::new(
+                format!("exige 3 ponto(s) antes do evento, há {}", event_index),
+                format!("requires 3 point(s) before the event, got {}", event_index),
+            ),
+        ));
+    }
+    if event_index > data.len() || data.len() - event_index < window {
+        return Err(TimeSeriesError::invalid_parameter(
+            "event_index",
+            LocalizedText::new(
+                format!("exige {} ponto(s) a partir do evento, há {}", window, data.len().saturating_sub(event_index) ),
+                format!("requires {} point(s) from the event on, got {}", window, data.len().saturating_sub(event_index) ),
+            ),
+        ));
+    }
+    validate_finite(data)?;
+
+    let pre = &data[..event_index];
+    let post = &data[event_index..event_index + window];
+    let pre_fit = linear_regression(pre)?;
+    let counterfactual = predict_future(&pre_fit, window);
+    let differences: Vec<f64> = post.iter().zip(&counterfactual).map(|(a, c)| a - c).collect();
+    let cumulative_effect = kahan_sum(differences.iter().copied());
+    let average_effect = cumulative_effect / window as f64;
+
+    let m = event_index as f64;
+    let w = window as f64;
+    let variance = residual_sum_of_squares(pre, &pre_fit.predictions)? / (m - 2.0);
+    let pre_mean_x = (m - 1.0) / 2.0;
+    let post_mean_x = m + (w - 1.0) / 2.0;
+    let sxx = m * (m * m - 1.0) / 12.0;
+    let standard_error = (variance * (1.0 / w + 1.0 / m + (post_mean_x - pre_mean_x).powi(2) / sxx)).sqrt();
+    let z_statistic = if standard_error > 0.0 {
+        average_effect / standard_error
+    } else if average_effect == 0.0 {
+        0.0
+    } else {
+        average_effect.signum() * f64::INFINITY
+    };
+    let p_value = 2.0 * (1.0 - normal_cdf(z_statistic.abs()));
+
+    Ok(InterventionResult {
+        event_index,
+        pre_fit,
+        counterfactual,
+        differences,
+        average_effect,
+        cumulative_effect,
+        standard_error,
+        z_statistic,
+        p_value,
+        significant: p_value < 0.05,
+    })
+}
+
+/// `Σ|φ|` a partir do qual [`ArModel::near_unit_root`] é sinalizado
+pub const AR_UNIT_ROOT_THRESHOLD: f64 = 0.95;
+
+/// Modelo autorregressivo `x[t] - μ = φ1·(x[t-1] - μ) + … + φp·(x[t-p] - μ) + ε[t]`
+/// ajustado por [`fit_ar`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArModel {
+    /// Média `μ` da série de ajuste
+    pub mean: f64,
+    /// `φ1..φp`, do lag 1 ao lag `p`
+    pub coefficients: Vec<f64>,
+    /// Variância estimada do ruído `ε`
+    pub noise_variance: f64,
+    /// Previsões um passo à frente: `fitted[k]` prevê `data[k + p]` a partir dos `p`
+    /// valores anteriores
+    pub fitted: Vec<f64>,
+    /// `Σ|φ| >= AR_UNIT_ROOT_THRESHOLD`: o modelo está perto da não estacionariedade e as
+    /// previsões voltam à média muito devagar. Indicador conservador — `Σ|φ| < 1` garante
+    /// estacionariedade, mas modelos estacionários também podem ser sinalizados
+    pub near_unit_root: bool,
+}
+
+impl ArModel {
+    /// Ordem `p` do modelo
+    pub fn order(&self) -> usize {
+        self.coefficients.len()
+    }
+
+    /// Previsão de um passo a partir dos `p` valores mais recentes, do mais antigo ao mais novo
+    fn step(&self, recent: &[f64]) -> f64 {
+        self.mean
+            + self
+                .coefficients
+                .iter()
+                .zip(recent.iter().rev())
+                .map(|(phi, x)| phi * (x - self.mean))
+                .sum::<f64>()
+    }
+
+    /// Previsões para os `periods` períodos após `data_tail`, iterando a recursão com as
+    /// próprias previsões
+    ///
+    /// Só os últimos `p` valores de `data_tail` são usados; se houver menos, os que faltam
+    /// são tomados como a média. Com o modelo estacionário, as previsões convergem para
+    /// `mean`.
+    pub fn forecast(&self, data_tail: &[f64], periods: usize) -> Vec<f64> {
+        let p = self.order();
+        let mut history: Vec<f64> = vec![self.mean; p.saturating_sub(data_tail.len())];
+        history.extend_from_slice(&data_tail[data_tail.len().saturating_sub(p)..]);
+        let mut out = Vec::with_capacity(periods);
+        for _ in 0..periods {
+            let next = self.step(&history[history.len() - p..]);
+            history.push(next);
+            out.push(next);
+        }
+        out
+    }
+}
+
+/// Ajusta um modelo AR(`order`) pelas equações de Yule–Walker
+///
+/// As equações são resolvidas pela recursão de Levinson–Durbin sobre a [`autocorrelation`]
+/// amostral, o que sempre produz um modelo estacionário (as autocorrelações parciais
+/// ficam em (-1, 1)); a variância do ruído é `γ0·Π(1 - κk²)`, com `γ0` a variância
+/// populacional e `κk` as autocorrelações parciais. Exige `0 < order < n` e série não
+/// constante.
+pub fn fit_ar(data: &[f64], order: usize) -> Result<ArModel, TimeSeriesError> {
+    if order == 0 {
+        return Err(TimeSeriesError::invalid_parameter(
+            "order",
+            LocalizedText::new("deve ser maior que zero", "must be greater than zero"),
+        ));
+    }
+    let acf = autocorrelation(data, order)?;
+    let n = data.len() as f64;
+    let mean = kahan_sum(data.iter().copied()) / n;
+    let variance = kahan_sum(data.iter().map(|v| (v - mean) * (v - mean))) / n;
+
+    let mut coefficients: Vec<f64> = Vec::with_capacity(order);
+    let mut error = 1.0;
+    for k in 1..=order {
+        let projection: f64 = coefficients.iter().enumerate().map(|(j, phi)| phi * acf[k - 1 - j]).sum();
+        let kappa = (acf[k] - projection) / error;
+        let previous = coefficients.clone();
+        for (j, phi) in coefficients.iter_mut().enumerate() {
+            *phi -= kappa * previous[k - 2 - j];
+        }
+        coefficients.push(kappa);
+        error *= 1.0 - kappa * kappa;
+    }
+
+    let near_unit_root = coefficients.iter().map(|phi| phi.abs()).sum::<f64>() >= AR_UNIT_ROOT_THRESHOLD;
+    let mut model = ArModel {
+        mean,
+        coefficients,
+        noise_variance: variance * error,
+        fitted: Vec::new(),
+        near_unit_root,
+    };
+    model.fitted = data.windows(order).take(data.len() - order).map(|recent| model.step(recent)).collect();
+    Ok(model)
+}
+
+/// Modelos de previsão disponíveis para validação e comparação
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ForecastModel {
+    /// Tendência linear ajustada por `linear_regression`
+    Linear,
+    /// Repete o último valor observado
+    Naive,
+    /// Extrapola a reta entre a primeira e a última observação
+    Drift,
+    /// Repete o valor observado no mesmo ponto do último ciclo sazonal
+    SeasonalNaive(usize),
+    /// Repete o último nível de [`exponential_moving_average`] com o `alpha` dado
+    ExponentialSmoothing(f64),
+    /// Prolonga o nível e a inclinação finais de [`kalman_trend_filter`]
+    Kalman { process_noise: f64, observation_noise: f64 },
+}
+
+impl ForecastModel {
+    /// Nome curto do modelo
+    pub fn name(&self) -> &'static str {
+        match self {
+            ForecastModel::Linear => "Linear",
+            ForecastModel::Naive => "Naive",
+            ForecastModel::Drift => "Drift",
+            ForecastModel::SeasonalNaive(_) => "SeasonalNaive",
+            ForecastModel::ExponentialSmoothing(_) => "ExponentialSmoothing",
+            ForecastModel::Kalman { .. } => "Kalman",
+        }
+    }
+
+    /// Ajusta o modelo aos dados e prevê os próximos `periods` valores
+    pub fn forecast(&self, data: &[f64], periods: usize) -> Result<Vec<f64>, TimeSeriesError> {
+        validate_finite(data)?;
+        match self {
+            ForecastModel::Linear => {
+                let result = linear_regression(data)?;
+                Ok(predict_future(&result, periods))
+            }
+            ForecastModel::Naive => {
+                let last = *data.last()
+                    .ok_or_else(|| TimeSeriesError::insufficient_data(1, 0))?;
+                Ok(vec![last; periods])
+            }
+            ForecastModel::Drift => {
+                if data.len() < 2 {
+                    return Err(TimeSeriesError::insufficient_data(2, data.len()));
+                }
+                let first = data[0];
+                let last = data[data.len() - 1];
+                let drift = (last - first) / (data.len() - 1) as f64;
+                Ok((1..=periods).map(|h| last + drift * h as f64).collect())
+            }
+            ForecastModel::SeasonalNaive(period) => {
+                let period = *period;
+                if period == 0 {
+                    return Err(TimeSeriesError::invalid_parameter(
+                        "period",
+                        LocalizedText::new("deve ser maior que zero", "must be greater than zero"),
+                    ));
+                }
+                if data.len() < period {
+                    return Err(TimeSeriesError::insufficient_data(period, data.len()));
+                }
+                let last_cycle = &data[data.len() - period..];
+                Ok((0..periods).map(|h| last_cycle[h % period]).collect())
+            }
+            ForecastModel::ExponentialSmoothing(alpha) => {
+                let smoothed = exponential_moving_average(data, *alpha)?;
+                Ok(vec![smoothed[smoothed.len() - 1]; periods])
+            }
+            ForecastModel::Kalman { process_noise, observation_noise } => {
+                Ok(kalman_trend_filter(data, *process_noise, *observation_noise)?.forecast(periods))
+            }
+        }
+    }
+}
+
+impl fmt::Display for ForecastModel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Resultado detalhado de [`ensemble_forecast_detailed`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnsembleForecast {
+    /// Média ponderada dos caminhos
+    pub forecast: Vec<f64>,
+    /// Caminho de cada modelo usado, com o peso normalizado aplicado
+    pub paths: Vec<(ForecastModel, f64, Vec<f64>)>,
+    /// Modelos que falharam nos dados e foram ignorados (apenas fora do modo estrito)
+    pub skipped: Vec<(ForecastModel, TimeSeriesError)>,
+}
+
+/// Combina as previsões de vários modelos pela média ponderada dos caminhos
+///
+/// Sem `weights`, todos os modelos pesam igual. Modelos que falham são ignorados e os
+/// pesos restantes renormalizados; veja [`ensemble_forecast_detailed`] para o modo estrito
+/// e para os caminhos individuais.
+pub fn ensemble_forecast(
+    data: &[f64],
+    periods: usize,
+    models: &[ForecastModel],
+    weights: Option<&[f64]>,
+) -> Result<Vec<f64>, TimeSeriesError> {
+    Ok(ensemble_forecast_detailed(data, periods, models, weights, false)?.forecast)
+}
+
+/// Como [`ensemble_forecast`], devolvendo também o caminho de cada modelo
+///
+/// Com `strict`, a falha de qualquer modelo interrompe a chamada. Os pesos devem ser
+/// finitos, não negativos, um por modelo e com soma positiva.
+pub fn ensemble_forecast_detailed(
+    data: &[f64],
+    periods: usize,
+    models: &[ForecastModel],
+    weights: Option<&[f64]>,
+    strict: bool,
+) -> Result<EnsembleForecast, TimeSeriesError> {
+    if models.is_empty() {
+        return Err(TimeSeriesError::invalid_parameter(
+            "models",
+            LocalizedText::new("a lista de modelos está vazia", "the model list is empty"),
+        ));
+    }
+    let weights = match weights {
+        Some(weights) => {
+            if weights.len() != models.len() {
+                return Err(TimeSeriesError::LengthMismatch {
+                    left: models.len(),
+                    right: weights.len(),
+                });
+            }
+            if weights.iter().any(|w| !(w.is_finite() && *w >= 0.0)) {
+                return Err(TimeSeriesError::invalid_parameter(
+                    "weights",
+                    LocalizedText::new(
+                        "devem ser finitos e não negativos",
+                        "must be finite and non-negative",
+                    ),
+                ));
+            }
+            weights.to_vec()
+        }
+        None => vec![1.0; models.len()],
+    };
+
+    let mut used = Vec::with_capacity(models.len());
+    let mut skipped = Vec::new();
+    for (&model, &weight) in models.iter().zip(&weights) {
+        match model.forecast(data, periods) {
+            Ok(path) => used.push((model, weight, path)),
+            Err(err) if strict => return Err(err.in_stage(model.name())),
+            Err(err) => skipped.push((model, err)),
+        }
+    }
+
+    let total: f64 = used.iter().map(|(_, weight, _)| weight).sum();
+    if used.is_empty() {
+        return Err(TimeSeriesError::new(
+            LocalizedText::new(
+                "Nenhum modelo do ensemble pôde ser ajustado",
+                "No ensemble model could be fitted",
+            ),
+        ));
+    }
+    if total <= 0.0 {
+        return Err(TimeSeriesError::invalid_parameter(
+            "weights",
+            LocalizedText::new(
+                "a soma dos pesos dos modelos usados é zero",
+                "the weights of the models used sum to zero",
+            ),
+        ));
+    }
+
+    let mut forecast = vec![0.0; periods];
+    for (_, weight, path) in used.iter_mut() {
+        *weight /= total;
+        for (value, p) in forecast.iter_mut().zip(path.iter()) {
+            *value += *weight * p;
+        }
+    }
+
+    Ok(EnsembleForecast {
+        forecast,
+        paths: used,
+        skipped,
+    })
+}
+
+/// Forma de reconciliar previsões de partes com a previsão do total em [`reconcile_forecasts`]
+///
+/// Em todos os métodos o total é mantido e só as partes são ajustadas.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReconciliationMethod {
+    /// Top-down: divide o total pelas proporções das próprias previsões das partes em cada passo
+    ///
+    /// Quando as partes somam zero no passo (proporções indefinidas), a discrepância é
+    /// dividida igualmente, como em [`EqualShare`](Self::EqualShare).
+    ForecastProportions,
+    /// Top-down: divide o total por proporções fixas, uma por parte (por exemplo, as de
+    /// [`historical_proportions`]); são normalizadas para somar 1
+    HistoricalProportions(Vec<f64>),
+    /// Projeção: soma a cada parte a mesma fração da discrepância `total - Σ partes`
+    EqualShare,
+    /// Projeção: divide a discrepância proporcionalmente à variância (dos erros) de cada parte,
+    /// de modo que as partes mais incertas absorvem mais do ajuste
+    VarianceWeighted(Vec<f64>),
+}
+
+/// Participação média de cada parte no histórico: `Σₜ parte[t] / Σₜ Σⱼ parteⱼ[t]`
+///
+/// Serve de entrada para [`ReconciliationMethod::HistoricalProportions`]. Erro se as partes
+/// tiverem tamanhos diferentes ou somarem zero.
+pub fn historical_proportions(history: &[Vec<f64>]) -> Result<Vec<f64>, TimeSeriesError> {
+    let series: Vec<&[f64]> = history.iter().map(Vec::as_slice).collect();
+    validate_series_set(&series)?;
+    validate_equal_lengths(&series)?;
+    let totals: Vec<f64> = history.iter().map(|part| kahan_sum(part.iter().copied())).collect();
+    let grand_total = kahan_sum(totals.iter().copied());
+    if approx_zero(grand_total, totals.iter().map(|t| t.abs()).sum()) {
+        return Err(TimeSeriesError::invalid_parameter(
+            "history",
+            LocalizedText::new("as partes somam zero", "the parts sum to zero"),
+        ));
+    }
+    Ok(totals.iter().map(|t| t / grand_total).collect())
+}
+
+/// Pesos de [`ReconciliationMethod`] normalizados para somar 1
+fn reconciliation_weights(weights: &[f64], parts: usize) -> Result<Vec<f64>, TimeSeriesError> {
+    if weights.len() != parts {
+        return Err(TimeSeriesError::LengthMismatch {
+            left: parts,
+            right: weights.len(),
+        });
+    }
+    validate_finite(weights)?;
+    if weights.iter().any(|&w| w < 0.0) {
+        return Err(TimeSeriesError::invalid_parameter(
+            "method",
+            LocalizedText::new("os pesos não podem ser negativos", "weights cannot be negative"),
+        ));
+    }
+    let sum = kahan_sum(weights.iter().copied());
+    if sum <= 0.0 {
+        return Err(TimeSeriesError::invalid_parameter(
+            "method",
+            LocalizedText::new("ao menos um peso deve ser positivo", "at least one weight must be positive"),
+        ));
+    }
+    Ok(weights.iter().map(|w| w / sum).collect())
+}
+
+/// Ajusta as previsões das partes para que somem o total em cada passo do horizonte
+///
+/// `parts[j][h]` é a previsão da parte `j` no passo `h`; todas as partes precisam ter o
+/// tamanho de `total`. O total não é alterado. Previsões já consistentes não mudam com
+/// [`EqualShare`](ReconciliationMethod::EqualShare) e
+/// [`VarianceWeighted`](ReconciliationMethod::VarianceWeighted) (discrepância nula) nem com
+/// [`ForecastProportions`](ReconciliationMethod::ForecastProportions) (fator 1).
+pub fn reconcile_forecasts(
+    total: &[f64],
+    parts: &[Vec<f64>],
+    method: &ReconciliationMethod,
+) -> Result<Vec<Vec<f64>>, TimeSeriesError> {
+    if parts.is_empty() {
+        return Err(TimeSeriesError::insufficient_data(1, 0));
+    }
+    validate_finite(total)?;
+    for part in parts {
+        if part.len() != total.len() {
+            return Err(TimeSeriesError::LengthMismatch {
+                left: total.len(),
+                right: part.len(),
+            });
+        }
+        validate_finite(part)?;
+    }
+    let weights = match method {
+        ReconciliationMethod::HistoricalProportions(w) | ReconciliationMethod::VarianceWeighted(w) => {
+            reconciliation_weights(w, parts.len())?
+        }
+        _ => Vec::new(),
+    };
+
+    let k = parts.len() as f64;
+    let mut reconciled = parts.to_vec();
+    for (h, &target) in total.iter().enumerate() {
+        let sum = kahan_sum(parts.iter().map(|part| part[h]));
+        let discrepancy = target - sum;
+        for (j, part) in reconciled.iter_mut().enumerate() {
+            part[h] = match method {
+                ReconciliationMethod::ForecastProportions => {
+                    let scale = parts.iter().map(|p| p[h].abs()).sum::<f64>();
+                    if approx_zero(sum, scale) {
+                        part[h] + discrepancy / k
+                    } else {
+                        part[h] * (target / sum)
+                    }
+                }
+                ReconciliationMethod::HistoricalProportions(_) => weights[j] * target,
+                ReconciliationMethod::EqualShare => part[h] + discrepancy / k,
+                ReconciliationMethod::VarianceWeighted(_) => part[h] + weights[j] * discrepancy,
+            };
+        }
+    }
+    Ok(reconciled)
+}
+
+/// Resultado de uma dobra da validação cruzada com origem móvel
+#[derive(Debug, Clone, PartialEq)]
+pub struct CvFold {
+    /// Quantidade de pontos usados no treino desta dobra
+    pub train_size: usize,
+    pub forecasts: Vec<f64>,
+    pub actual: Vec<f64>,
+}
+
+/// Relatório da validação cruzada com origem móvel
+#[derive(Debug, Clone, PartialEq)]
+pub struct CvReport {
+    pub model: ForecastModel,
+    pub folds: Vec<CvFold>,
+    /// Erro absoluto médio para cada passo do horizonte (índice 0 = um passo à frente)
+    pub mae_by_horizon: Vec<f64>,
+    /// Erro quadrático médio para cada passo do horizonte
+    pub mse_by_horizon: Vec<f64>,
+}
+
+impl CvReport {
+    /// MAE médio considerando todos os passos do horizonte
+    pub fn overall_mae(&self) -> f64 {
+        self.mae_by_horizon.iter().sum::<f64>() / self.mae_by_horizon.len() as f64
+    }
+}
+
+/// Validação cruzada com origem móvel (rolling origin)
+///
+/// Começa com `initial_train` pontos de treino, prevê `horizon` passos e avança a
+/// origem em `step` pontos até que não haja mais dados para o horizonte completo.
+pub fn rolling_origin_cv(
+    data: &[f64],
+    initial_train: usize,
+    horizon: usize,
+    step: usize,
+    model: ForecastModel,
+) -> Result<CvReport, TimeSeriesError> {
+    if horizon == 0 {
+        return Err(TimeSeriesError::invalid_parameter(
+            "horizon",
+            LocalizedText::new("deve ser maior que zero", "must be greater than zero"),
+        ));
+    }
+    if step == 0 {
+        return Err(TimeSeriesError::invalid_parameter(
+            "step",
+            LocalizedText::new("deve ser maior que zero", "must be greater than zero"),
+        ));
+    }
+    if initial_train == 0 {
+        return Err(TimeSeriesError::invalid_parameter(
+            "initial_train",
+            LocalizedText::new("deve ser maior que zero", "must be greater than zero"),
+        ));
+    }
+    if initial_train + horizon > data.len() {
+        return Err(TimeSeriesError::insufficient_data(initial_train + horizon, data.len()));
+    }
+
+    let mut folds = Vec::new();
+    let mut abs_sums = vec![0.0; horizon];
+    let mut sq_sums = vec![0.0; horizon];
+
+    let mut origin = initial_train;
+    while origin + horizon <= data.len() {
+        let forecasts = model.forecast(&data[..origin], horizon)?;
+        let actual = data[origin..origin + horizon].to_vec();
+
+        for (h, (&a, &p)) in actual.iter().zip(forecasts.iter()).enumerate() {
+            abs_sums[h] += (a - p).abs();
+            sq_sums[h] += (a - p).powi(2);
+        }
+
+        folds.push(CvFold { train_size: origin, forecasts, actual });
+        origin += step;
+    }
+
+    let count = folds.len() as f64;
+    Ok(CvReport {
+        model,
+        folds,
+        mae_by_horizon: abs_sums.iter().map(|s| s / count).collect(),
+        mse_by_horizon: sq_sums.iter().map(|s| s / count).collect(),
+    })
+}
+
+/// Resultado da seleção automática de modelos
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelSelection {
+    /// Modelo com menor erro no conjunto de teste
+    pub best: ForecastModel,
+    /// Métrica usada na classificação
+    pub metric: ErrorMetric,
+    /// Erro do melhor modelo no conjunto de teste
+    pub holdout_error: f64,
+    /// Previsão de `test_size` períodos do melhor modelo, reajustado com todos os dados
+    pub forecast: Vec<f64>,
+    /// Candidatos avaliados com sucesso, do melhor para o pior
+    pub ranking: Vec<(ForecastModel, f64)>,
+    /// Candidatos que falharam no treino ou na avaliação, com o erro, na ordem de `candidates`
+    pub skipped: Vec<(ForecastModel, TimeSeriesError)>,
+}
+
+impl ModelSelection {
+    /// Nome do modelo vencedor
+    pub fn name(&self) -> &'static str {
+        self.best.name()
+    }
+}
+
+/// Avalia todos os candidatos no mesmo holdout e escolhe o de menor erro
+///
+/// Candidatos que falham nos dados de treino (ex.: período sazonal maior que o treino)
+/// ficam fora do ranking e são listados em `skipped` com o motivo. Empates são resolvidos
+/// pela ordem em `candidates`.
+pub fn select_best_model(
+    data: &[f64],
+    test_size: usize,
+    candidates: &[ForecastModel],
+    metric: ErrorMetric,
+) -> Result<ModelSelection, TimeSeriesError> {
+    if candidates.is_empty() {
+        return Err(TimeSeriesError::invalid_parameter(
+            "candidates",
+            LocalizedText::new("nenhum modelo candidato informado", "no candidate model given"),
+        ));
+    }
+
+    let (train, test) = train_test_split(data, test_size)?;
+
+    let mut ranking: Vec<(ForecastModel, f64)> = Vec::new();
+    let mut skipped = Vec::new();
+    for &model in candidates {
+        let error = model.forecast(train, test_size).and_then(|forecasts| evaluate(test, &forecasts, metric));
+        match error {
+            Ok(error) => ranking.push((model, error)),
+            Err(err) => skipped.push((model, err)),
+        }
+    }
+
+    if ranking.is_empty() {
+        return Err(TimeSeriesError::new(
+            LocalizedText::new(
+                "Nenhum modelo candidato pôde ser avaliado",
+                "No candidate model could be evaluated",
+            ),
+        ));
+    }
+
+    // sort_by é estável: empates mantêm a ordem dos candidatos
+    ranking.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+    let (best, holdout_error) = ranking[0];
+    let forecast = best.forecast(data, test_size)?;
+
+    Ok(ModelSelection {
+        best,
+        metric,
+        holdout_error,
+        forecast,
+        ranking,
+        skipped,
+    })
+}
+
+/// Resultado de [`grid_search`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct GridSearchResult<P> {
+    /// Parâmetro com a menor pontuação
+    pub best: P,
+    /// Pontuação do melhor parâmetro
+    pub best_score: f64,
+    /// Pontuação de cada parâmetro avaliado com sucesso, na ordem da grade
+    pub scores: Vec<(P, f64)>,
+    /// Parâmetros cuja avaliação falhou (ou deu pontuação não finita), com o erro
+    pub failures: Vec<(P, TimeSeriesError)>,
+}
+
+/// Avalia `score` em cada ponto da grade e escolhe o de menor pontuação
+///
+/// Pontos que retornam erro ou pontuação não finita são registrados em `failures` e
+/// ignorados. Empates são resolvidos pelo primeiro ponto da grade. Erro se nenhum
+/// ponto puder ser avaliado.
+pub fn grid_search<P, F>(grid: &[P], score: F) -> Result<GridSearchResult<P>, TimeSeriesError>
+where
+    P: Clone,
+    F: Fn(&P) -> Result<f64, TimeSeriesError>,
+{
+    if grid.is_empty() {
+        return Err(TimeSeriesError::invalid_parameter(
+            "grid",
+            LocalizedText::new("a grade de parâmetros está vazia", "the parameter grid is empty"),
+        ));
+    }
+
+    let mut scores = Vec::with_capacity(grid.len());
+    let mut failures = Vec::new();
+    for param in grid {
+        match score(param) {
+            Ok(value) if value.is_finite() => scores.push((param.clone(), value)),
+            Ok(_) => failures.push((param.clone(), TimeSeriesError::new(
+                LocalizedText::new("Pontuação não finita", "Non-finite score"),
+            ))),
+            Err(err) => failures.push((param.clone(), err)),
+        }
+    }
+
+    let (best, best_score) = scores
+        .iter()
+        .fold(None::<&(P, f64)>, |best, candidate| match best {
+            Some(current) if current.1 <= candidate.1 => Some(current),
+            _ => Some(candidate),
+        })
+        .cloned()
+        .ok_or_else(|| TimeSeriesError::new(
+            LocalizedText::new("Nenhum ponto da grade pôde ser avaliado", "No grid point could be evaluated"),
+        ))?;
+
+    Ok(GridSearchResult {
+        best,
+        best_score,
+        scores,
+        failures,
+    })
+}
+
+/// Escolhe o `alpha` da [`exponential_moving_average`] com menor erro quadrático médio
+/// de previsão um passo à frente (a média até `t-1` prevê `y[t]`)
+pub fn tune_ema_alpha(data: &[f64], alphas: &[f64]) -> Result<GridSearchResult<f64>, TimeSeriesError> {
+    if data.len() < 2 {
+        return Err(TimeSeriesError::insufficient_data(2, data.len()));
+    }
+    grid_search(alphas, |&alpha| {
+        let smoothed = exponential_moving_average(data, alpha)?;
+        calculate_mse(&data[1..], &smoothed[..data.len() - 1])
+    })
+}
+
+/// Registro de uma etapa executada por [`TimeSeriesAnalysis`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnalysisStep {
+    /// Nome da etapa (o mesmo do método do builder)
+    pub name: &'static str,
+    /// Parâmetros usados e efeito da etapa, em texto
+    pub description: String,
+    /// Tamanho da série antes da etapa
+    pub len_before: usize,
+    /// Tamanho da série depois da etapa
+    pub len_after: usize,
+}
+
+impl fmt::Display for AnalysisStep {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} ({} -> {} pontos)",
+            self.name, self.description, self.len_before, self.len_after
+        )
+    }
+}
+
+/// Pipeline de pré-processamento e ajuste: preencher → limpar → suavizar → ajustar → prever
+///
+/// Cada etapa transforma a série e fica registrada em [`steps`](Self::steps). As etapas
+/// preservam o eixo do tempo da série original, de modo que as previsões começam no período
+/// seguinte à última observação recebida. O primeiro
+/// erro interrompe o pipeline: as etapas seguintes são ignoradas e [`fit_linear`](Self::fit_linear)
+/// devolve o erro envolvido em [`TimeSeriesError::Stage`] com o nome da etapa.
+///
+/// ```
+/// use timeseries_analysis::{FillMethod, TimeSeriesAnalysis};
+///
+/// let data = [1.0, f64::NAN, 3.0, 4.0, 5.0, 6.0];
+/// let fitted = TimeSeriesAnalysis::new(&data)
+///     .fill_missing(FillMethod::LinearInterpolate)
+///     .smooth_sma(2)
+///     .fit_linear()
+///     .unwrap();
+/// assert_eq!(fitted.forecast(1), vec![7.0]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TimeSeriesAnalysis {
+    data: Vec<f64>,
+    steps: Vec<AnalysisStep>,
+    error: Option<TimeSeriesError>,
+    /// Período da série original correspondente a `data[0]` (fracionário após médias
+    /// móveis de janela par)
+    offset: f64,
+    /// Tamanho da série original
+    periods: usize,
+}
+
+impl TimeSeriesAnalysis {
+    /// Inicia o pipeline com uma cópia de `data`
+    pub fn new(data: &[f64]) -> Self {
+        TimeSeriesAnalysis {
+            data: data.to_vec(),
+            steps: Vec::new(),
+            error: None,
+            offset: 0.0,
+            periods: data.len(),
+        }
+    }
+
+    /// Série após as etapas executadas até aqui
+    pub fn data(&self) -> &[f64] {
+        &self.data
+    }
+
+    /// Etapas executadas até aqui, em ordem
+    pub fn steps(&self) -> &[AnalysisStep] {
+        &self.steps
+    }
+
+    /// Primeiro erro do pipeline, se houver
+    pub fn error(&self) -> Option<&TimeSeriesError> {
+        self.error.as_ref()
+    }
+
+    fn apply<F>(mut self, name: &'static str, step: F) -> Self
+    where
+        F: FnOnce(&[f64]) -> Result<(Vec<f64>, String), TimeSeriesError>,
+    {
+        if self.error.is_some() {
+            return self;
+        }
+        match step(&self.data) {
+            Ok((data, description)) => {
+                self.steps.push(AnalysisStep {
+                    name,
+                    description,
+                    len_before: self.data.len(),
+                    len_after: data.len(),
+                });
+                self.data = data;
+            }
+            Err(err) => self.error = Some(err.in_stage(name)),
+        }
+        self
+    }
+
+    /// Preenche valores ausentes com [`fill_missing`]
+    pub fn fill_missing(self, method: FillMethod) -> Self {
+        self.apply("fill_missing", |data| {
+            let missing = data.iter().filter(|v| v.is_nan()).count();
+            let filled = fill_missing(data, method)?;
+            Ok((filled, format!("{}, {} valor(es) preenchido(s)", method, missing)))
+        })
+    }
+
+    /// Substitui os outliers de [`remove_outliers_zscore`] por interpolação linear entre os
+    /// vizinhos, sem encurtar a série
+    pub fn remove_outliers_zscore(self, threshold: f64) -> Self {
+        self.apply("remove_outliers_zscore", |data| {
+            let (_, removed) = remove_outliers_zscore(data, threshold)?;
+            let mut marked = data.to_vec();
+            removed.iter().for_each(|&i| marked[i] = f64::NAN);
+            let filled = fill_missing(&marked, FillMethod::LinearInterpolate)?;
+            Ok((filled, format!("limiar {}, {} ponto(s) substituído(s)", threshold, removed.len())))
+        })
+    }
+
+    /// Suaviza com a média móvel simples ([`rolling_mean`]), centrada: cada média fica no
+    /// período do meio da sua janela, e a série perde `(window - 1) / 2` pontos em cada ponta
+    pub fn smooth_sma(self, window: usize) -> Self {
+        let mut analysis = self.apply("smooth_sma", |data| {
+            Ok((rolling_mean(data, window)?, format!("janela {}", window)))
+        });
+        if analysis.error.is_none() {
+            analysis.offset += (window - 1) as f64 / 2.0;
+        }
+        analysis
+    }
+
+    /// Suaviza com a [`exponential_moving_average`]
+    pub fn smooth_ema(self, alpha: f64) -> Self {
+        self.apply("smooth_ema", |data| {
+            Ok((exponential_moving_average(data, alpha)?, format!("alpha {}", alpha)))
+        })
+    }
+
+    /// Ajusta a regressão linear à série resultante
+    pub fn fit_linear(self) -> Result<FittedAnalysis, TimeSeriesError> {
+        if let Some(err) = self.error {
+            return Err(err);
+        }
+        let mut result = linear_regression(&self.data).map_err(|err| err.in_stage("fit_linear"))?;
+        let metrics = evaluate_all(&self.data, &result.predictions).map_err(|err| err.in_stage("fit_linear"))?;
+        // Reta no eixo da série original: `data[j]` está no período `offset + j`
+        result.intercept -= result.slope * self.offset;
+        result.count = self.periods;
+        Ok(FittedAnalysis {
+            data: self.data,
+            steps: self.steps,
+            result,
+            metrics,
+        })
+    }
+}
+
+/// Pipeline de [`TimeSeriesAnalysis`] já ajustado
+#[derive(Debug, Clone)]
+pub struct FittedAnalysis {
+    data: Vec<f64>,
+    steps: Vec<AnalysisStep>,
+    result: LinearRegressionResult,
+    metrics: MetricsReport,
+}
+
+impl FittedAnalysis {
+    /// Série usada no ajuste (após todas as etapas)
+    pub fn data(&self) -> &[f64] {
+        &self.data
+    }
+
+    /// Etapas executadas antes do ajuste
+    pub fn steps(&self) -> &[AnalysisStep] {
+        &self.steps
+    }
+
+    /// Resultado da regressão linear
+    pub fn result(&self) -> &LinearRegressionResult {
+        &self.result
+    }
+
+    /// Métricas de erro do ajuste dentro da amostra
+    pub fn metrics(&self) -> &MetricsReport {
+        &self.metrics
+    }
+
+    /// Previsões para os próximos `periods` períodos (ver [`predict_future`])
+    pub fn forecast(&self, periods: usize) -> Vec<f64> {
+        predict_future(&self.result, periods)
+    }
+
+    /// Resumo das etapas, dos coeficientes e das métricas finais
+    pub fn report(&self) -> String {
+        let mut out = String::from("Pipeline de Analise:\n");
+        for (i, step) in self.steps.iter().enumerate() {
+            out.push_str(&format!("   {}. {}\n", i + 1, step));
+        }
+        out.push_str(&format!(
+            "   {}. fit_linear: slope {:.4}, intercept {:.4}, R² {:.4} ({} pontos)\n",
+            self.steps.len() + 1,
+            self.result.slope,
+            self.result.intercept,
+            self.result.r_squared,
+            self.data.len()
+        ));
+        out.push_str("\nMetricas Finais:\n");
+        out.push_str(&self.metrics.to_string());
+        out
+    }
+}
+
+#[cfg(test)]
+mod testes {
+    use super::*;
+    use crate::testes::{assert_approx_eq, pseudo_random};
+
+    #[test]
+    fn test_previsao_futura() {
+        let data = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+        let result = linear_regression(&data).unwrap();
+        let predictions = predict_future(&result, 3);
+
+        assert_eq!(predictions.len(), 3);
+        assert_approx_eq(predictions[0], 6.0, 1e-10);
+        assert_approx_eq(predictions[1], 7.0, 1e-10);
+        assert_approx_eq(predictions[2], 8.0, 1e-10);
+    }
+
+    #[test]
+    fn test_train_test_split() {
+        let data = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+        let (train, test) = train_test_split(&data, 2).unwrap();
+        assert_eq!(train, &[1.0, 2.0, 3.0]);
+        assert_eq!(test, &[4.0, 5.0]);
+
+        assert!(train_test_split(&data, 0).is_err());
+        assert!(train_test_split(&data, 4).is_err());
+        assert!(train_test_split(&data, 3).is_ok());
+    }
+
+    #[test]
+    fn test_backtest_holdout() {
+        let data = vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 8.0];
+        let report = backtest(&data, 2).unwrap();
+
+        assert_eq!(report.actual, vec![6.0, 8.0]);
+        assert_approx_eq(report.forecasts[0], 6.0, 1e-10);
+        assert_approx_eq(report.forecasts[1], 7.0, 1e-10);
+        assert_approx_eq(report.mse, 0.5, 1e-10);
+        assert_approx_eq(report.mae, 0.5, 1e-10);
+        assert_approx_eq(report.mape.unwrap(), 6.25, 1e-10);
+
+        assert!(backtest(&data, 6).is_err());
+    }
+
+    #[test]
+    fn test_modelos_de_previsao_simples() {
+        let data = vec![2.0, 4.0, 5.0, 8.0];
+        assert_eq!(ForecastModel::Naive.forecast(&data, 2).unwrap(), vec![8.0, 8.0]);
+        assert_eq!(ForecastModel::Drift.forecast(&data, 2).unwrap(), vec![10.0, 12.0]);
+        assert!(ForecastModel::Naive.forecast(&[], 1).is_err());
+        assert!(ForecastModel::Drift.forecast(&[1.0], 1).is_err());
+
+        // Níveis suavizados: 2, 3, 4, 6
+        assert_eq!(ForecastModel::ExponentialSmoothing(0.5).forecast(&data, 2).unwrap(), vec![6.0, 6.0]);
+        let kalman = ForecastModel::Kalman { process_noise: 0.5, observation_noise: 1.0 };
+        assert_eq!(kalman.forecast(&data, 3).unwrap(), kalman_trend_filter(&data, 0.5, 1.0).unwrap().forecast(3));
+        assert_eq!(kalman.name(), "Kalman");
+        assert!(ForecastModel::ExponentialSmoothing(0.0).forecast(&data, 1).is_err());
+    }
+
+    #[test]
+    fn test_validacao_cruzada_origem_movel() {
+        let data = vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0];
+        let report = rolling_origin_cv(&data, 3, 2, 1, ForecastModel::Naive).unwrap();
+
+        assert_eq!(report.folds.len(), 3);
+        assert_eq!(report.folds[0].train_size, 3);
+        assert_eq!(report.folds[2].actual, vec![6.0, 7.0]);
+        assert_eq!(report.mae_by_horizon, vec![1.0, 2.0]);
+        assert_eq!(report.mse_by_horizon, vec![1.0, 4.0]);
+        assert_approx_eq(report.overall_mae(), 1.5, 1e-12);
+
+        let linear = rolling_origin_cv(&data, 3, 2, 2, ForecastModel::Linear).unwrap();
+        assert_eq!(linear.folds.len(), 2);
+        assert!(linear.mae_by_horizon.iter().all(|&e| e < 1e-10));
+    }
+
+    #[test]
+    fn test_validacao_cruzada_sem_dobras() {
+        let data = vec![1.0, 2.0, 3.0, 4.0];
+        assert!(rolling_origin_cv(&data, 3, 2, 1, ForecastModel::Naive).is_err());
+        assert!(rolling_origin_cv(&data, 2, 0, 1, ForecastModel::Naive).is_err());
+        assert!(rolling_origin_cv(&data, 2, 1, 0, ForecastModel::Naive).is_err());
+        assert!(rolling_origin_cv(&data, 1, 1, 1, ForecastModel::Linear).is_err());
+    }
+
+    #[test]
+    fn test_previsao_sazonal_ingenua() {
+        let data = vec![1.0, 5.0, 3.0, 2.0, 6.0, 4.0];
+        let forecast = ForecastModel::SeasonalNaive(3).forecast(&data, 4).unwrap();
+        assert_eq!(forecast, vec![2.0, 6.0, 4.0, 2.0]);
+        assert!(ForecastModel::SeasonalNaive(0).forecast(&data, 1).is_err());
+        assert!(ForecastModel::SeasonalNaive(7).forecast(&data, 1).is_err());
+    }
+
+    #[test]
+    fn test_selecao_automatica_de_modelo() {
+        let seasonal = vec![10.0, 20.0, 30.0, 10.0, 20.0, 30.0, 10.0, 20.0, 30.0, 10.0, 20.0, 30.0];
+        let candidates = [
+            ForecastModel::Linear,
+            ForecastModel::Naive,
+            ForecastModel::Drift,
+            ForecastModel::SeasonalNaive(3),
+            ForecastModel::SeasonalNaive(20),
+            ForecastModel::ExponentialSmoothing(0.5),
+            ForecastModel::Kalman { process_noise: 1.0, observation_noise: 10.0 },
+            ForecastModel::ExponentialSmoothing(1.5),
+        ];
+
+        let selection = select_best_model(&seasonal, 3, &candidates, ErrorMetric::Mae).unwrap();
+        assert_eq!(selection.best, ForecastModel::SeasonalNaive(3));
+        assert_eq!(selection.name(), "SeasonalNaive");
+        assert_approx_eq(selection.holdout_error, 0.0, 1e-12);
+        assert_eq!(selection.forecast, vec![10.0, 20.0, 30.0]);
+        assert_eq!(selection.ranking.len(), 6);
+        // Período maior que o treino e alpha fora de (0, 1] são relatados, não descartados em silêncio
+        let skipped: Vec<ForecastModel> = selection.skipped.iter().map(|(model, _)| *model).collect();
+        assert_eq!(skipped, vec![ForecastModel::SeasonalNaive(20), ForecastModel::ExponentialSmoothing(1.5)]);
+        assert_eq!(selection.skipped[0].1, TimeSeriesError::insufficient_data(20, 9));
+        assert!(matches!(selection.skipped[1].1, TimeSeriesError::InvalidParameter { name: "alpha", .. }));
+
+        assert!(select_best_model(&seasonal, 3, &[], ErrorMetric::Mae).is_err());
+    }
+
+    #[test]
+    fn test_selecao_empate_deterministico() {
+        let constant = vec![4.0; 8];
+        let candidates = [ForecastModel::Drift, ForecastModel::Naive, ForecastModel::Linear];
+        let selection = select_best_model(&constant, 2, &candidates, ErrorMetric::Mse).unwrap();
+        assert_eq!(selection.best, ForecastModel::Drift);
+
+        let reversed = [ForecastModel::Linear, ForecastModel::Naive, ForecastModel::Drift];
+        let selection = select_best_model(&constant, 2, &reversed, ErrorMetric::Mse).unwrap();
+        assert_eq!(selection.best, ForecastModel::Linear);
+    }
+
+    #[test]
+    fn test_periods_until_e_value_at() {
+        // y = 10 + 2x, última observação em x = 4 (valor 18)
+        let result = linear_regression(&[10.0, 12.0, 14.0, 16.0, 18.0]).unwrap();
+        assert_approx_eq(value_at(&result, 2.5), 15.0, 1e-9);
+        assert_approx_eq(periods_until(&result, 30.0).unwrap(), 6.0, 1e-9);
+        assert_approx_eq(periods_until(&result, 18.0).unwrap(), 0.0, 1e-9);
+        // Alvo já ultrapassado / na direção oposta: negativo
+        assert_approx_eq(periods_until(&result, 12.0).unwrap(), -3.0, 1e-9);
+        assert_approx_eq(periods_until(&result, 0.0).unwrap(), -9.0, 1e-9);
+
+        let flat = linear_regression(&[5.0, 5.0, 5.0]).unwrap();
+        assert!(periods_until(&flat, 10.0).is_err());
+
+        // A inclinação é nula ou não conforme a escala da série
+        for scale in [1e-20, 1e20] {
+            let data: Vec<f64> = [10.0, 12.0, 14.0, 16.0, 18.0].iter().map(|v| v * scale).collect();
+            let result = linear_regression(&data).unwrap();
+            assert_approx_eq(periods_until(&result, 30.0 * scale).unwrap(), 6.0, 1e-6);
+            let flat = LinearRegressionResult { slope: 1e-16 * scale, ..result };
+            assert!(periods_until(&flat, 30.0 * scale).is_err());
+        }
+    }
+
+    #[test]
+    fn test_grid_search_registra_falhas_e_empates() {
+        let grid = [3, 0, 1, -1, 5];
+        let result = grid_search(&grid, |&p| {
+            if p < 0 {
+                Err(TimeSeriesError::invalid_parameter("p", "negativo"))
+            } else {
+                Ok(((p - 2) * (p - 2)) as f64)
+            }
+        })
+        .unwrap();
+        // 3 e 1 empatam com pontuação 1: vence o primeiro da grade
+        assert_eq!((result.best, result.best_score), (3, 1.0));
+        assert_eq!(result.scores.len(), 4);
+        assert_eq!(result.failures.len(), 1);
+        assert_eq!(result.failures[0].0, -1);
+
+        assert!(grid_search(&[-1], |_| Err::<f64, _>(TimeSeriesError::new("falha"))).is_err());
+        assert!(grid_search::<i32, _>(&[], |_| Ok(0.0)).is_err());
+    }
+
+    #[test]
+    fn test_tune_ema_alpha() {
+        let ema = exponential_moving_average(&[1.0, 3.0, 3.0], 0.5).unwrap();
+        assert_eq!(ema, vec![1.0, 2.0, 2.5]);
+
+        // Passeio sem ruído: a melhor previsão é o último valor (alpha = 1)
+        let trend: Vec<f64> = (0..30).map(|i| i as f64).collect();
+        let tuned = tune_ema_alpha(&trend, &[0.1, 0.5, 1.0, 1.5]).unwrap();
+        assert_eq!(tuned.best, 1.0);
+        assert_eq!(tuned.failures.len(), 1);
+    }
+
+    #[test]
+    fn test_pipeline_analise() {
+        let mut data: Vec<f64> = (0..20).map(|i| 2.0 * i as f64 + 1.0).collect();
+        data[4] = f64::NAN;
+        data[10] = 500.0;
+        let fitted = TimeSeriesAnalysis::new(&data)
+            .fill_missing(FillMethod::LinearInterpolate)
+            .remove_outliers_zscore(3.0)
+            .smooth_sma(3)
+            .fit_linear()
+            .unwrap();
+        let steps = fitted.steps();
+        assert_eq!(steps.len(), 3);
+        assert_eq!((steps[1].len_before, steps[1].len_after), (20, 20));
+        assert_eq!(fitted.data().len(), 18);
+        // Outlier interpolado e média centrada: a reta segue 2x + 1 no eixo original
+        let forecast = fitted.forecast(2);
+        assert_approx_eq(forecast[0], 41.0, 1e-9);
+        assert_approx_eq(forecast[1], 43.0, 1e-9);
+        let report = fitted.report();
+        assert!(report.contains("1. fill_missing: interpolação linear, 1 valor(es) preenchido(s) (20 -> 20 pontos)"));
+        assert!(report.contains("limiar 3, 1 ponto(s) substituído(s)"));
+        assert!(report.contains("4. fit_linear: slope"));
+
+        let err = TimeSeriesAnalysis::new(&[1.0, 2.0]).smooth_sma(5).fit_linear().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InsufficientData);
+        assert!(err.to_string().contains("Etapa 'smooth_sma' falhou: Dados insuficientes"));
+    }
+
+    #[test]
+    fn test_previsao_com_datas() {
+        let result = linear_regression(&[10.0, 20.0, 30.0]).unwrap();
+        let start = Date::try_from((2024, 12, 1)).unwrap();
+        let forecasts = forecast_with_dates(&result, 2, start, Frequency::Monthly);
+        assert_eq!(forecasts, vec![("2024-12-01".to_string(), 40.0), ("2025-01-01".to_string(), 50.0)]);
+    }
+
+    #[test]
+    fn test_previsao_por_quantis() {
+        // resíduos simétricos: -0.6, 1.2, 0, -1.2, 0.6
+        let data = [9.0, 13.0, 14.0, 15.0, 19.0];
+        let result = linear_regression(&data).unwrap();
+        let point = predict_future(&result, 3);
+        let paths = quantile_forecast(&data, &result, 3, &[0.9, 0.5, 0.1]).unwrap();
+        assert_eq!(paths.len(), 3);
+        for (path, p) in paths[1].iter().zip(&point) {
+            assert_approx_eq(*path, *p, 1e-12);
+        }
+        for ((low, mid), high) in paths[2].iter().zip(&paths[1]).zip(&paths[0]) {
+            assert!(low <= mid && mid <= high);
+        }
+        assert_approx_eq(paths[0][0] - point[0], 0.96, 1e-12);
+        assert!(quantile_forecast(&data, &result, 3, &[0.0]).is_err());
+        assert!(quantile_forecast(&data, &result, 3, &[f64::NAN]).is_err());
+    }
+
+    #[test]
+    fn test_ensemble_forecast() {
+        let data = [10.0, 12.0, 14.0, 20.0];
+        // Naive: 20, 20; Drift (inclinação 10/3): 23.33, 26.67
+        let equal = ensemble_forecast(&data, 2, &[ForecastModel::Naive, ForecastModel::Drift], None).unwrap();
+        assert_approx_eq(equal[0], (20.0 + 70.0 / 3.0) / 2.0, 1e-12);
+        let weighted =
+            ensemble_forecast(&data, 2, &[ForecastModel::Naive, ForecastModel::Drift], Some(&[3.0, 1.0])).unwrap();
+        assert_approx_eq(weighted[1], 0.75 * 20.0 + 0.25 * (20.0 + 20.0 / 3.0), 1e-12);
+
+        let models = [ForecastModel::Naive, ForecastModel::SeasonalNaive(12)];
+        let lenient = ensemble_forecast_detailed(&data, 2, &models, Some(&[1.0, 4.0]), false).unwrap();
+        assert_eq!(lenient.forecast, vec![20.0, 20.0]);
+        assert_eq!(lenient.paths[0].1, 1.0);
+        assert_eq!(lenient.skipped.len(), 1);
+        let err = ensemble_forecast_detailed(&data, 2, &models, None, true).unwrap_err();
+        assert!(err.to_string().contains("Etapa 'SeasonalNaive' falhou"));
+
+        assert!(ensemble_forecast(&data, 2, &models, Some(&[1.0, -1.0])).is_err());
+        assert!(ensemble_forecast(&data, 2, &models, Some(&[1.0])).is_err());
+    }
+
+    #[test]
+    fn test_reconciliacao_de_previsoes_soma_o_total() {
+        let total = [100.0, 110.0, 90.0];
+        let parts = vec![vec![30.0, 40.0, 20.0], vec![50.0, 50.0, 50.0], vec![10.0, 10.0, 0.0]];
+        let methods = [
+            ReconciliationMethod::ForecastProportions,
+            ReconciliationMethod::HistoricalProportions(vec![2.0, 5.0, 1.0]),
+            ReconciliationMethod::EqualShare,
+            ReconciliationMethod::VarianceWeighted(vec![4.0, 1.0, 0.0]),
+        ];
+        for method in &methods {
+            let reconciled = reconcile_forecasts(&total, &parts, method).unwrap();
+            for (h, &target) in total.iter().enumerate() {
+                let sum: f64 = reconciled.iter().map(|part| part[h]).sum();
+                assert_approx_eq(sum, target, 1e-9);
+            }
+        }
+
+        // Passo 0: discrepância 10; proporções 30:50:10 → fator 100/90
+        let proportional = reconcile_forecasts(&total, &parts, &methods[0]).unwrap();
+        assert_approx_eq(proportional[0][0], 30.0 * 100.0 / 90.0, 1e-12);
+        let equal = reconcile_forecasts(&total, &parts, &methods[2]).unwrap();
+        assert_approx_eq(equal[2][0], 10.0 + 10.0 / 3.0, 1e-12);
+        // Variância zero: a terceira parte não é ajustada; as demais dividem 80% / 20%
+        let weighted = reconcile_forecasts(&total, &parts, &methods[3]).unwrap();
+        assert_eq!(weighted[2], parts[2]);
+        assert_approx_eq(weighted[0][0], 38.0, 1e-12);
+        let historical = reconcile_forecasts(&total, &parts, &methods[1]).unwrap();
+        assert_approx_eq(historical[1][1], 110.0 * 5.0 / 8.0, 1e-12);
+
+        // Partes que somam zero: a discrepância é dividida igualmente
+        let zero_sum = reconcile_forecasts(&[6.0], &[vec![5.0], vec![-5.0]], &methods[0]).unwrap();
+        assert_eq!(zero_sum, vec![vec![8.0], vec![-2.0]]);
+    }
+
+    #[test]
+    fn test_reconciliacao_mantem_previsoes_consistentes() {
+        let total = [10.0, 12.5, 7.25];
+        let parts = vec![vec![4.0, 5.0, 3.0], vec![6.0, 7.5, 4.25]];
+        for method in [
+            ReconciliationMethod::ForecastProportions,
+            ReconciliationMethod::EqualShare,
+            ReconciliationMethod::VarianceWeighted(vec![1.0, 3.0]),
+        ] {
+            assert_eq!(reconcile_forecasts(&total, &parts, &method).unwrap(), parts);
+        }
+
+        let history = vec![vec![1.0, 3.0], vec![2.0, 2.0]];
+        assert_eq!(historical_proportions(&history).unwrap(), vec![0.5, 0.5]);
+
+        assert!(matches!(
+            reconcile_forecasts(&total, &[vec![1.0, 2.0]], &ReconciliationMethod::EqualShare),
+            Err(TimeSeriesError::LengthMismatch { left: 3, right: 2 })
+        ));
+        assert!(matches!(
+            reconcile_forecasts(&total, &parts, &ReconciliationMethod::HistoricalProportions(vec![1.0])),
+            Err(TimeSeriesError::LengthMismatch { left: 2, right: 1 })
+        ));
+        assert!(reconcile_forecasts(&total, &parts, &ReconciliationMethod::VarianceWeighted(vec![0.0, 0.0])).is_err());
+        assert!(historical_proportions(&[vec![1.0], vec![-1.0]]).is_err());
+    }
+
+    #[test]
+    fn test_intervencao_recupera_o_salto() {
+        // Tendência 2 + 0.5·t com ruído; a partir do período 40 a série sobe 5 unidades
+        let noise = pseudo_random(12, 60);
+        let data: Vec<f64> = (0..60)
+            .map(|t| 2.0 + 0.5 * t as f64 + (noise[t] - 0.5) + if t >= 40 { 5.0 } else { 0.0 })
+            .collect();
+        let result = intervention_analysis(&data, 40, 15).unwrap();
+        assert_eq!(result.counterfactual.len(), 15);
+        assert_approx_eq(result.average_effect, 5.0, 0.3);
+        assert_approx_eq(result.cumulative_effect, result.average_effect * 15.0, 1e-9);
+        assert!(result.significant && result.p_value < 1e-6);
+
+        // Sem salto, o efeito estimado fica perto de zero e não é significativo
+        let flat: Vec<f64> = (0..60).map(|t| 2.0 + 0.5 * t as f64 + (noise[t] - 0.5)).collect();
+        let none = intervention_analysis(&flat, 40, 15).unwrap();
+        assert!(none.average_effect.abs() < 0.3);
+        assert!(!none.significant);
+
+        // Reta exata: erro padrão nulo e diferenças exatas
+        let line: Vec<f64> = (0..10).map(|t| if t >= 6 { t as f64 + 3.0 } else { t as f64 }).collect();
+        let exact = intervention_analysis(&line, 6, 4).unwrap();
+        assert_approx_eq(exact.average_effect, 3.0, 1e-9);
+        assert_eq!(exact.p_value, 0.0);
+    }
+
+    #[test]
+    fn test_intervencao_exige_dados_dos_dois_lados() {
+        let data = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+        assert!(matches!(
+            intervention_analysis(&data, 2, 2),
+            Err(TimeSeriesError::InvalidParameter { name: "event_index", .. })
+        ));
+        assert!(matches!(
+            intervention_analysis(&data, 4, 3),
+            Err(TimeSeriesError::InvalidParameter { name: "event_index", .. })
+        ));
+        assert!(intervention_analysis(&data, 9, 1).is_err());
+        assert!(intervention_analysis(&data, 3, 0).is_err());
+        assert!(intervention_analysis(&data, 3, 3).is_ok());
+    }
+
+    #[test]
+    fn test_fit_ar1_recupera_phi() {
+        let data = generators::ar1_series(3000, 0.7, 1.0, 21).unwrap();
+        let model = fit_ar(&data, 1).unwrap();
+        assert_eq!(model.order(), 1);
+        assert_approx_eq(model.coefficients[0], 0.7, 0.05);
+        assert_approx_eq(model.noise_variance, 1.0, 0.1);
+        assert!(!model.near_unit_root);
+        assert_eq!(model.fitted.len(), data.len() - 1);
+        assert_approx_eq(model.fitted[0], model.mean + model.coefficients[0] * (data[0] - model.mean), 1e-12);
+
+        // previsões decaem geometricamente para a média
+        let forecast = model.forecast(&[model.mean + 10.0], 30);
+        let gaps: Vec<f64> = forecast.iter().map(|f| (f - model.mean).abs()).collect();
+        assert!(gaps.windows(2).all(|w| w[1] < w[0]));
+        assert!(gaps[29] < 0.01);
+        assert_approx_eq(gaps[0], 10.0 * model.coefficients[0], 1e-9);
+
+        let ar2 = fit_ar(&data, 2).unwrap();
+        assert_approx_eq(ar2.coefficients[1], 0.0, 0.05);
+        assert_eq!(ar2.forecast(&[], 3), vec![ar2.mean; 3]);
+    }
+
+    #[test]
+    fn test_fit_ar_erros_e_raiz_unitaria() {
+        let walk = generators::random_walk(500, 1.0, 4).unwrap();
+        assert!(fit_ar(&walk, 1).unwrap().near_unit_root);
+        assert!(matches!(fit_ar(&walk, 0), Err(TimeSeriesError::InvalidParameter { .. })));
+        assert!(fit_ar(&[1.0, 2.0, 3.0], 3).is_err());
+        assert!(fit_ar(&[2.0; 10], 1).is_err());
+    }
+}
@@ -0,0 +1,169 @@
+//! Formatação numérica configurável para relatórios, CSV, JSON e `Display`
+//!
+//! O `Display` de cada resultado usa a precisão histórica do tipo (por exemplo, 2 casas
+//! nas estatísticas e 4 na regressão) ou a precisão do formatador (`{:.6}`); para
+//! separador de milhar e notação científica, use [`FormatWith::format_with`].
+//!
+//! ```
+//! use timeseries_analysis::formatting::FormatOptions;
+//!
+//! let options = FormatOptions {
+//!     decimals: 1,
+//!     thousands_separator: Some('_'),
+//!     scientific_threshold: Some(1e9),
+//! };
+//! assert_eq!(options.format(1234567.25), "1_234_567.2");
+//! assert_eq!(options.format(-2.5e12), "-2.5e12");
+//! ```
+
+use crate::*;
+
+/// Como os números são escritos na saída legível
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FormatOptions {
+    /// Casas decimais (também da mantissa na notação científica)
+    pub decimals: usize,
+    /// Separador entre grupos de três dígitos da parte inteira; o separador decimal é
+    /// sempre `.`
+    pub thousands_separator: Option<char>,
+    /// Valores com `|v| >= limite` ou `0 < |v| < 1/limite` são escritos em notação
+    /// científica (`1.23e9`)
+    pub scientific_threshold: Option<f64>,
+}
+
+/// 2 casas, sem separador de milhar e sem notação científica
+impl Default for FormatOptions {
+    fn default() -> Self {
+        FormatOptions {
+            decimals: 2,
+            thousands_separator: None,
+            scientific_threshold: None,
+        }
+    }
+}
+
+impl FormatOptions {
+    /// Opções padrão com `decimals` casas decimais
+    pub fn with_decimals(decimals: usize) -> Self {
+        FormatOptions {
+            decimals,
+            ..FormatOptions::default()
+        }
+    }
+
+    /// Opções do `Display` de um tipo: a precisão do formatador, se houver, ou `decimals`
+    pub(crate) fn for_formatter(f: &fmt::Formatter, decimals: usize) -> Self {
+        FormatOptions::with_decimals(f.precision().unwrap_or(decimals))
+    }
+
+    /// O valor cruza o limite da notação científica
+    pub fn is_scientific(&self, value: f64) -> bool {
+        match self.scientific_threshold {
+            Some(threshold) if value.is_finite() && value != 0.0 => {
+                let magnitude = value.abs();
+                magnitude >= threshold || magnitude < 1.0 / threshold
+            }
+            _ => false,
+        }
+    }
+
+    /// Escreve `value` conforme as opções; NaN e infinito ficam como no `Display` de `f64`
+    pub fn format(&self, value: f64) -> String {
+        if self.is_scientific(value) {
+            return format!("{:.*e}", self.decimals, value);
+        }
+        let fixed = format!("{:.*}", self.decimals, value);
+        match self.thousands_separator {
+            Some(separator) if value.is_finite() => group_thousands(&fixed, separator),
+            _ => fixed,
+        }
+    }
+
+    /// Número JSON: como [`format`](Self::format), mas sem separador de milhar e com `null`
+    /// para valores não finitos
+    pub fn format_json(&self, value: f64) -> String {
+        if !value.is_finite() {
+            return "null".to_string();
+        }
+        FormatOptions {
+            thousands_separator: None,
+            ..*self
+        }
+        .format(value)
+    }
+}
+
+/// Insere `separator` a cada três dígitos da parte inteira de um número já formatado
+fn group_thousands(fixed: &str, separator: char) -> String {
+    let (sign, unsigned) = match fixed.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", fixed),
+    };
+    let (integer, fraction) = match unsigned.find('.') {
+        Some(dot) => unsigned.split_at(dot),
+        None => (unsigned, ""),
+    };
+    let mut out = String::with_capacity(fixed.len() + integer.len() / 3);
+    out.push_str(sign);
+    for (i, digit) in integer.chars().enumerate() {
+        if i > 0 && (integer.len() - i) % 3 == 0 {
+            out.push(separator);
+        }
+        out.push(digit);
+    }
+    out.push_str(fraction);
+    out
+}
+
+/// Texto legível de um resultado com [`FormatOptions`] explícitas
+///
+/// Produz o mesmo layout do `Display` do tipo, trocando apenas a escrita dos números.
+pub trait FormatWith {
+    fn format_with(&self, options: &FormatOptions) -> String;
+}
+
+impl FormatWith for f64 {
+    fn format_with(&self, options: &FormatOptions) -> String {
+        options.format(*self)
+    }
+}
+
+/// `Display` do valor sem opções, [`FormatWith`] com elas
+pub(crate) fn display_or_format<V: fmt::Display + FormatWith>(value: &V, options: Option<&FormatOptions>) -> String {
+    match options {
+        Some(options) => value.format_with(options),
+        None => value.to_string(),
+    }
+}
+
+/// Número avulso de relatório: 2 casas decimais sem opções
+pub(crate) fn format_number(value: f64, options: Option<&FormatOptions>) -> String {
+    match options {
+        Some(options) => options.format(value),
+        None => format!("{:.2}", value),
+    }
+}
+
+#[cfg(test)]
+mod testes {
+    use super::*;
+
+    #[test]
+    fn test_format_options_snapshots() {
+        let stats = calculate_descriptive_stats(&[1000.0, 2500.5, 4000.25]).unwrap();
+        assert_eq!(stats.format_with(&FormatOptions::default()), stats.to_string());
+        assert!(stats.format_with(&FormatOptions::with_decimals(6)).contains("   Variancia: 1500250.041667\n"));
+        let separated = FormatOptions {
+            thousands_separator: Some(','),
+            ..FormatOptions::default()
+        };
+        assert!(stats.format_with(&separated).contains("   Variancia: 1,500,250.04\n"));
+        assert_eq!(format!("{:.6}", stats), stats.format_with(&FormatOptions::with_decimals(6)));
+
+        let fit = linear_regression(&[100.0, 120.0, 130.0, 145.0, 160.0]).unwrap();
+        assert_eq!(fit.format_with(&FormatOptions::with_decimals(4)), fit.to_string());
+        assert!(fit.format_with(&FormatOptions::with_decimals(2)).starts_with("Regressao Linear: y = 102.00 + 14.50·x\n"));
+        assert_eq!(fit.to_json_with(&FormatOptions::with_decimals(1)), "{\"slope\":14.5,\"intercept\":102.0,\"r_squared\":1.0,\"mse\":3.5,\"predictions\":[102.0,116.5,131.0,145.5,160.0]}");
+        assert_eq!(fit.summary(&[100.0, 120.0, 130.0, 145.0, 160.0]), fit.summary_with(&[100.0, 120.0, 130.0, 145.0, 160.0], &FormatOptions::with_decimals(4)));
+    }
+}
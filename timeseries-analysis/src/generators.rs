@@ -0,0 +1,224 @@
+//! Geradores de séries sintéticas reprodutíveis, para testes e demonstrações
+//!
+//! Usam um gerador pseudoaleatório próprio (xorshift64* com semente espalhada por
+//! splitmix64), sem depender de crates externas: a mesma semente gera sempre a mesma série.
+//!
+//! ```
+//! use timeseries_analysis::generators;
+//!
+//! let serie = generators::linear_series(3, 2.0, 1.0, 0.0, 42).unwrap();
+//! assert_eq!(serie, vec![1.0, 3.0, 5.0]);
+//! assert_eq!(generators::random_walk(50, 1.0, 7), generators::random_walk(50, 1.0, 7));
+//! ```
+
+use crate::*;
+
+/// Gerador pseudoaleatório xorshift64*
+#[derive(Debug, Clone)]
+pub(crate) struct SeededRng {
+    state: u64,
+}
+
+impl SeededRng {
+    pub(crate) fn new(seed: u64) -> Self {
+        // splitmix64: sementes próximas geram estados bem diferentes e nunca zero
+        let mut z = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+        SeededRng { state: z.max(1) }
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    /// Uniforme em [0, 1)
+    pub(crate) fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Normal padrão (Box–Muller)
+    pub(crate) fn next_normal(&mut self) -> f64 {
+        let u1 = 1.0 - self.next_f64();
+        let u2 = self.next_f64();
+        (-2.0 * u1.ln()).sqrt() * (2.0 * core::f64::consts::PI * u2).cos()
+    }
+}
+
+fn validate_len(n: usize) -> Result<(), TimeSeriesError> {
+    if n == 0 {
+        return Err(TimeSeriesError::invalid_parameter(
+            "n",
+            LocalizedText::new("deve ser ao menos 1", "must be at least 1"),
+        ));
+    }
+    Ok(())
+}
+
+fn validate_std(name: &'static str, value: f64) -> Result<(), TimeSeriesError> {
+    if !(value.is_finite() && value >= 0.0) {
+        return Err(TimeSeriesError::invalid_parameter(
+            name,
+            LocalizedText::new("deve ser finito e não negativo", "must be finite and non-negative"),
+        ));
+    }
+    Ok(())
+}
+
+fn validate_coefficient(name: &'static str, value: f64) -> Result<(), TimeSeriesError> {
+    if !value.is_finite() {
+        return Err(TimeSeriesError::invalid_parameter(
+            name,
+            LocalizedText::new("deve ser finito", "must be finite"),
+        ));
+    }
+    Ok(())
+}
+
+/// `intercept + slope·t + ruído`, com ruído normal de desvio `noise_std`
+pub fn linear_series(
+    n: usize,
+    slope: f64,
+    intercept: f64,
+    noise_std: f64,
+    seed: u64,
+) -> Result<Vec<f64>, TimeSeriesError> {
+    validate_len(n)?;
+    validate_coefficient("slope", slope)?;
+    validate_coefficient("intercept", intercept)?;
+    validate_std("noise_std", noise_std)?;
+
+    let mut rng = SeededRng::new(seed);
+    Ok((0..n)
+        .map(|t| intercept + slope * t as f64 + noise_std * rng.next_normal())
+        .collect())
+}
+
+/// `trend·t + amplitude·sen(2πt/period) + ruído`
+pub fn seasonal_series(
+    n: usize,
+    period: usize,
+    amplitude: f64,
+    trend: f64,
+    noise_std: f64,
+    seed: u64,
+) -> Result<Vec<f64>, TimeSeriesError> {
+    validate_len(n)?;
+    if period < 2 {
+        return Err(TimeSeriesError::invalid_parameter(
+            "period",
+            LocalizedText::new("deve ser ao menos 2", "must be at least 2"),
+        ));
+    }
+    validate_coefficient("amplitude", amplitude)?;
+    validate_coefficient("trend", trend)?;
+    validate_std("noise_std", noise_std)?;
+
+    let mut rng = SeededRng::new(seed);
+    let angular = 2.0 * core::f64::consts::PI / period as f64;
+    Ok((0..n)
+        .map(|t| {
+            let t = t as f64;
+            trend * t + amplitude * (angular * t).sin() + noise_std * rng.next_normal()
+        })
+        .collect())
+}
+
+/// Passeio aleatório partindo de zero, com passos normais de desvio `step_std`
+pub fn random_walk(n: usize, step_std: f64, seed: u64) -> Result<Vec<f64>, TimeSeriesError> {
+    validate_len(n)?;
+    validate_std("step_std", step_std)?;
+
+    let mut rng = SeededRng::new(seed);
+    let mut level = 0.0;
+    Ok((0..n)
+        .map(|t| {
+            if t > 0 {
+                level += step_std * rng.next_normal();
+            }
+            level
+        })
+        .collect())
+}
+
+/// Processo AR(1) de média zero `y[t] = phi·y[t-1] + ruído`
+///
+/// Exige |phi| < 1 (processo estacionário); o primeiro valor é sorteado da
+/// distribuição estacionária, com variância `noise_std² / (1 - phi²)`.
+pub fn ar1_series(n: usize, phi: f64, noise_std: f64, seed: u64) -> Result<Vec<f64>, TimeSeriesError> {
+    validate_len(n)?;
+    if !(phi.is_finite() && phi.abs() < 1.0) {
+        return Err(TimeSeriesError::invalid_parameter(
+            "phi",
+            LocalizedText::new(
+                "deve ter valor absoluto menor que 1 (use random_walk para phi = 1)",
+                "must have absolute value below 1 (use random_walk for phi = 1)",
+            ),
+        ));
+    }
+    validate_std("noise_std", noise_std)?;
+
+    let mut rng = SeededRng::new(seed);
+    let mut value = noise_std / (1.0 - phi * phi).sqrt() * rng.next_normal();
+    let mut series = Vec::with_capacity(n);
+    series.push(value);
+    for _ in 1..n {
+        value = phi * value + noise_std * rng.next_normal();
+        series.push(value);
+    }
+    Ok(series)
+}
+
+#[cfg(test)]
+mod testes {
+    use super::*;
+    use crate::testes::assert_approx_eq;
+
+    #[test]
+    fn test_gerador_linear() {
+        let a = generators::linear_series(200, 0.5, 10.0, 1.0, 42).unwrap();
+        assert_eq!(a, generators::linear_series(200, 0.5, 10.0, 1.0, 42).unwrap());
+        assert_ne!(a, generators::linear_series(200, 0.5, 10.0, 1.0, 43).unwrap());
+        let fit = linear_regression(&a).unwrap();
+        assert_approx_eq(fit.slope, 0.5, 0.01);
+        assert_approx_eq(fit.mse.sqrt(), 1.0, 0.15);
+        assert_eq!(generators::linear_series(3, 2.0, 1.0, 0.0, 0).unwrap(), vec![1.0, 3.0, 5.0]);
+        assert!(generators::linear_series(0, 1.0, 0.0, 1.0, 1).is_err());
+        assert!(generators::linear_series(5, 1.0, 0.0, -1.0, 1).is_err());
+    }
+
+    #[test]
+    fn test_gerador_sazonal() {
+        let a = generators::seasonal_series(48, 12, 3.0, 0.1, 0.5, 7).unwrap();
+        assert_eq!(a, generators::seasonal_series(48, 12, 3.0, 0.1, 0.5, 7).unwrap());
+        let clean = generators::seasonal_series(13, 4, 2.0, 0.0, 0.0, 7).unwrap();
+        assert_approx_eq(clean[1], 2.0, 1e-12);
+        assert_approx_eq(clean[3], -2.0, 1e-12);
+        assert_approx_eq(clean[12], 0.0, 1e-12);
+        assert!(generators::seasonal_series(10, 1, 1.0, 0.0, 0.0, 7).is_err());
+    }
+
+    #[test]
+    fn test_gerador_passeio_aleatorio() {
+        let a = generators::random_walk(100, 2.0, 5).unwrap();
+        assert_eq!(a, generators::random_walk(100, 2.0, 5).unwrap());
+        assert_eq!(a[0], 0.0);
+        let steps: Vec<f64> = a.windows(2).map(|w| w[1] - w[0]).collect();
+        assert_approx_eq(calculate_descriptive_stats(&steps).unwrap().std_dev, 2.0, 0.4);
+        assert!(generators::random_walk(10, f64::NAN, 5).is_err());
+    }
+
+    #[test]
+    fn test_gerador_ar1() {
+        let a = generators::ar1_series(2000, 0.8, 1.0, 9).unwrap();
+        assert_eq!(a, generators::ar1_series(2000, 0.8, 1.0, 9).unwrap());
+        let (x, y) = lagged_pairs(&a, 1).unwrap();
+        assert_approx_eq(linear_regression_xy(&x, &y).unwrap().slope, 0.8, 0.05);
+        assert!(generators::ar1_series(10, 1.0, 1.0, 9).is_err());
+        assert!(generators::ar1_series(10, 0.5, -0.1, 9).is_err());
+    }
+}
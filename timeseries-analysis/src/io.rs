@@ -0,0 +1,276 @@
+//! Leitura de séries em texto/CSV e serialização em JSON
+//!
+//! ```
+//! use timeseries_analysis::io::{parse_series, ToJson};
+//!
+//! let data = parse_series("dia,valor\n1,10.5\n2,11\n", 1).unwrap();
+//! assert_eq!(data, vec![10.5, 11.0]);
+//! assert_eq!(data.to_json(), "[10.5,11]");
+//! ```
+
+use crate::*;
+
+/// Lê uma série numérica de texto: um valor por linha ou CSV
+///
+/// Campos podem ser separados por `,`, `;` ou tabulação; `column` (a partir de 0)
+/// escolhe o campo usado. Linhas vazias e iniciadas por `#` são ignoradas, assim como
+/// uma primeira linha não numérica (cabeçalho). Qualquer outro valor inválido gera
+/// erro com o número da linha.
+pub fn parse_series(text: &str, column: usize) -> Result<Vec<f64>, TimeSeriesError> {
+    let mut values = Vec::new();
+    let mut header_allowed = true;
+    for (number, line) in text.lines().enumerate().map(|(i, l)| (i + 1, l.trim())) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let field = line
+            .split([',', ';', '\t'])
+            .nth(column)
+            .map(str::trim)
+            .ok_or_else(|| TimeSeriesError::new(
+                LocalizedText::new(
+                    format!("Linha {}: coluna {} inexistente", number, column),
+                    format!("Line {}: column {} does not exist", number, column),
+                ),
+            ))?;
+        match field.parse::<f64>() {
+            Ok(value) => values.push(value),
+            Err(_) if header_allowed => {}
+            Err(_) => {
+                return Err(TimeSeriesError::new(
+                    LocalizedText::new(
+                        format!("Linha {}: valor inválido '{}'", number, field),
+                        format!("Line {}: invalid value '{}'", number, field),
+                    ),
+                ));
+            }
+        }
+        header_allowed = false;
+    }
+
+    if values.is_empty() {
+        return Err(TimeSeriesError::insufficient_data(1, 0));
+    }
+    validate_finite(&values)?;
+    Ok(values)
+}
+
+/// Lê uma série de um arquivo com [`parse_series`]
+#[cfg(feature = "std")]
+pub fn load_series<P: AsRef<std::path::Path>>(path: P, column: usize) -> Result<Vec<f64>, TimeSeriesError> {
+    let path = path.as_ref();
+    let text = std::fs::read_to_string(path)
+        .map_err(|err| TimeSeriesError::new(
+            LocalizedText::new(
+                format!("Erro ao ler '{}': {}", path.display(), err),
+                format!("Error reading '{}': {}", path.display(), err),
+            ),
+        ))?;
+    parse_series(&text, column)
+}
+/// Serialização em JSON, escrita à mão para não depender de crates externas
+///
+/// Valores não finitos viram `null`, já que JSON não representa NaN nem infinito.
+pub trait ToJson {
+    /// Documento JSON compacto (sem espaços ou quebras de linha)
+    fn to_json(&self) -> String;
+
+    /// Como [`to_json`](Self::to_json), com os números arredondados conforme `options`
+    /// ([`FormatOptions::format_json`])
+    fn to_json_with(&self, options: &FormatOptions) -> String;
+}
+
+/// JSON dos tipos da crate; `numbers` `None` escreve a representação mais curta de cada
+/// número
+trait WriteJson {
+    fn write_json(&self, numbers: Option<&FormatOptions>) -> String;
+}
+
+fn json_number(value: f64, numbers: Option<&FormatOptions>) -> String {
+    match numbers {
+        Some(options) => options.format_json(value),
+        None if value.is_finite() => format!("{}", value),
+        None => "null".to_string(),
+    }
+}
+
+fn json_array<T: Float>(values: &[T], numbers: Option<&FormatOptions>) -> String {
+    let items: Vec<String> = values.iter().map(|v| json_number(v.to_f64(), numbers)).collect();
+    format!("[{}]", items.join(","))
+}
+
+/// Escapa `text` como string JSON, incluindo as aspas
+pub fn json_string(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+impl WriteJson for f64 {
+    fn write_json(&self, numbers: Option<&FormatOptions>) -> String {
+        json_number(*self, numbers)
+    }
+}
+
+impl<T: Float> WriteJson for [T] {
+    fn write_json(&self, numbers: Option<&FormatOptions>) -> String {
+        json_array(self, numbers)
+    }
+}
+
+impl<T: Float> WriteJson for Vec<T> {
+    fn write_json(&self, numbers: Option<&FormatOptions>) -> String {
+        json_array(self, numbers)
+    }
+}
+
+impl WriteJson for DescriptiveStats {
+    fn write_json(&self, numbers: Option<&FormatOptions>) -> String {
+        format!(
+            "{{\"count\":{},\"mean\":{},\"std_dev\":{},\"variance\":{},\"min\":{},\"max\":{},\"median\":{},\"q1\":{},\"q3\":{}}}",
+            self.count,
+            json_number(self.mean, numbers),
+            json_number(self.std_dev, numbers),
+            json_number(self.variance, numbers),
+            json_number(self.min, numbers),
+            json_number(self.max, numbers),
+            json_number(self.median, numbers),
+            json_number(self.q1, numbers),
+            json_number(self.q3, numbers)
+        )
+    }
+}
+
+impl<T: Float> WriteJson for LinearRegressionResult<T> {
+    fn write_json(&self, numbers: Option<&FormatOptions>) -> String {
+        format!(
+            "{{\"slope\":{},\"intercept\":{},\"r_squared\":{},\"mse\":{},\"predictions\":{}}}",
+            json_number(self.slope.to_f64(), numbers),
+            json_number(self.intercept.to_f64(), numbers),
+            json_number(self.r_squared.to_f64(), numbers),
+            json_number(self.mse.to_f64(), numbers),
+            json_array(&self.predictions, numbers)
+        )
+    }
+}
+
+impl<T: Float> WriteJson for MetricsReport<T> {
+    fn write_json(&self, numbers: Option<&FormatOptions>) -> String {
+        format!(
+            "{{\"mse\":{},\"rmse\":{},\"mae\":{},\"mape\":{},\"smape\":{}}}",
+            json_number(self.mse.to_f64(), numbers),
+            json_number(self.rmse.to_f64(), numbers),
+            json_number(self.mae.to_f64(), numbers),
+            self.mape.map_or_else(|| "null".to_string(), |m| json_number(m.to_f64(), numbers)),
+            json_number(self.smape.to_f64(), numbers)
+        )
+    }
+}
+
+fn json_option<T: WriteJson>(numbers: Option<&FormatOptions>, value: &Option<T>) -> String {
+    value.as_ref().map_or_else(|| "null".to_string(), |v| v.write_json(numbers))
+}
+
+/// Identificador estável (em inglês) da direção, independente do `Display`
+fn trend_id(trend: TrendDirection) -> &'static str {
+    match trend {
+        TrendDirection::Increasing => "increasing",
+        TrendDirection::Decreasing => "decreasing",
+        TrendDirection::Flat => "flat",
+        TrendDirection::Inconclusive => "inconclusive",
+    }
+}
+
+impl WriteJson for AnalysisResult {
+    fn write_json(&self, numbers: Option<&FormatOptions>) -> String {
+        let trend = self.trend.map_or_else(|| "null".to_string(), |t| json_string(trend_id(t)));
+        let anomalies = match &self.anomalies {
+            Some(indices) => {
+                let items: Vec<String> = indices.iter().map(|i| i.to_string()).collect();
+                format!("[{}]", items.join(","))
+            }
+            None => "null".to_string(),
+        };
+        let forecast = match &self.forecast {
+            Some(points) => {
+                let items: Vec<String> = points
+                    .iter()
+                    .map(|p| {
+                        format!(
+                            "{{\"period\":{},\"value\":{},\"lower\":{},\"upper\":{}}}",
+                            p.period_number(),
+                            json_number(p.value, numbers),
+                            json_number(p.lower, numbers),
+                            json_number(p.upper, numbers)
+                        )
+                    })
+                    .collect();
+                format!("[{}]", items.join(","))
+            }
+            None => "null".to_string(),
+        };
+        format!(
+            "{{\"observations\":{},\"stats\":{},\"regression\":{},\"trend\":{},\"metrics\":{},\"anomalies\":{},\"forecast\":{}}}",
+            self.observations,
+            json_option(numbers, &self.stats),
+            json_option(numbers, &self.regression),
+            trend,
+            json_option(numbers, &self.metrics),
+            anomalies,
+            forecast
+        )
+    }
+}
+
+/// Números, séries e resultados da crate: todos escrevem o JSON pelo mesmo `write_json`
+impl<T: WriteJson + ?Sized> ToJson for T {
+    fn to_json(&self) -> String {
+        self.write_json(None)
+    }
+
+    fn to_json_with(&self, options: &FormatOptions) -> String {
+        self.write_json(Some(options))
+    }
+}
+
+#[cfg(test)]
+mod testes {
+    use super::*;
+
+    #[test]
+    fn test_parse_series() {
+        let text = "mes;vendas\n# comentario\njan;100\n\nfev; 120.5\nmar;130\n";
+        assert_eq!(parse_series(text, 1).unwrap(), vec![100.0, 120.5, 130.0]);
+        assert_eq!(parse_series("1\n2\n3", 0).unwrap(), vec![1.0, 2.0, 3.0]);
+
+        let err = parse_series("1\n2\nabc\n", 0).unwrap_err();
+        assert!(err.to_string().contains("Linha 3"), "{}", err);
+        assert!(parse_series("a,1\nb", 1).unwrap_err().to_string().contains("coluna 1"));
+        assert!(parse_series("cabecalho\n", 0).is_err());
+    }
+
+    #[test]
+    fn test_to_json() {
+        let result = linear_regression(&[1.0, 3.0, 5.0]).unwrap();
+        assert_eq!(
+            result.to_json(),
+            r#"{"slope":2,"intercept":1,"r_squared":1,"mse":0,"predictions":[1,3,5]}"#
+        );
+        let report = evaluate_all(&[0.0, 2.0], &[1.0, 2.0]).unwrap();
+        assert!(report.to_json().contains(r#""mape":null"#));
+        assert_eq!(vec![1.5, f64::NAN].to_json(), "[1.5,null]");
+        assert_eq!(json_string("a\"b\\c\n"), r#""a\"b\\c\n""#);
+    }
+}
@@ -1,345 +1,277 @@
 //! Módulo de análise de séries temporais com regressão linear
 //! Implementação pura sem dependências externas
+//!
+//! Sem a feature `std` (ativa por padrão) a biblioteca compila em `no_std` + `alloc`;
+//! apenas as funções que imprimem no terminal e o `impl Error` ficam indisponíveis.
+//!
+//! O código está dividido por área em [`regression`], [`metrics`], [`forecast`], [`stats`],
+//! [`transform`], [`smoothing`], [`series`], [`seasonal`], [`plot`], [`report`], [`io`] e
+//! [`error`]. Todos os itens públicos também são reexportados na raiz da crate; para importar
+//! só os mais usados, sem nomes genéricos como `median`, use o [`prelude`].
 
-use std::fmt;
-use std::error::Error;
+#![cfg_attr(all(not(feature = "std"), not(test)), no_std)]
 
-/// Estrutura para armazenar os resultados da regressão linear
-#[derive(Debug, Clone, PartialEq)]
-pub struct LinearRegressionResult {
-    pub slope: f64,
-    pub intercept: f64,
-    pub r_squared: f64,
-    pub mse: f64,
-    pub predictions: Vec<f64>,
-}
+extern crate alloc;
 
-/// Estrutura para representar erros na análise de séries temporais
-#[derive(Debug, Clone)]
-pub struct TimeSeriesError {
-    message: String,
-}
+use alloc::boxed::Box;
+use alloc::collections::VecDeque;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::iter::Sum;
+use core::ops::{Add, AddAssign, Div, Mul, Neg, Range, Sub};
 
-impl TimeSeriesError {
-    pub fn new(msg: &str) -> Self {
-        TimeSeriesError {
-            message: msg.to_string(),
-        }
-    }
-}
+#[cfg(all(not(feature = "std"), not(test)))]
+use math::FloatMath;
 
-impl fmt::Display for TimeSeriesError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "TimeSeriesError: {}", self.message)
-    }
-}
+pub mod error;
+pub mod io;
+pub mod regression;
+pub mod metrics;
+pub mod forecast;
+pub mod stats;
+pub mod transform;
+pub mod smoothing;
+pub mod series;
+pub mod seasonal;
+pub mod plot;
+pub mod report;
+pub mod formatting;
+pub mod render;
+pub mod generators;
+pub mod prelude;
 
-impl Error for TimeSeriesError {}
+mod math;
 
-/// Realiza regressão linear em uma série temporal
-pub fn linear_regression(data: &[f64]) -> Result<LinearRegressionResult, TimeSeriesError> {
-    if data.len() < 2 {
-        return Err(TimeSeriesError::new("Dados insuficientes para regressão linear"));
-    }
+pub use error::*;
+pub use io::*;
+pub use regression::*;
+pub use metrics::*;
+pub use forecast::*;
+pub use stats::*;
+pub use transform::*;
+pub use smoothing::*;
+pub use series::*;
+pub use seasonal::*;
+pub use plot::*;
+pub use report::*;
+pub use formatting::*;
+pub use render::*;
 
-    let n = data.len() as f64;
-    let x: Vec<f64> = (0..data.len()).map(|x| x as f64).collect();
-    
-    let x_mean = x.iter().sum::<f64>() / n;
-    let y_mean = data.iter().sum::<f64>() / n;
-    
-    let mut numerator = 0.0;
-    let mut denominator = 0.0;
-    
-    for i in 0..data.len() {
-        numerator += (x[i] - x_mean) * (data[i] - y_mean);
-        denominator += (x[i] - x_mean).powi(2);
-    }
-    
-    let slope = if denominator.abs() < f64::EPSILON {
-        0.0
-    } else {
-        numerator / denominator
-    };
-    
-    let intercept = y_mean - slope * x_mean;
-    
-    let predictions: Vec<f64> = x.iter().map(|&xi| intercept + slope * xi).collect();
-    let mse = calculate_mse(data, &predictions);
-    let r_squared = calculate_r_squared(data, &predictions, y_mean);
-    
-    Ok(LinearRegressionResult {
-        slope,
-        intercept,
-        r_squared,
-        mse,
-        predictions,
-    })
-}
+/// Tipo de ponto flutuante aceito pelas funções principais (`f32` ou `f64`)
+///
+/// A regressão linear e as métricas de erro são genéricas sobre este trait; o restante
+/// da biblioteca trabalha com `f64`.
+pub trait Float:
+    Copy
+    + Send
+    + Sync
+    + PartialOrd
+    + Default
+    + fmt::Debug
+    + fmt::Display
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + AddAssign
+    + Sum
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const EPSILON: Self;
 
-/// Calcula o Erro Quadrático Médio (MSE)
-pub fn calculate_mse(actual: &[f64], predicted: &[f64]) -> f64 {
-    if actual.len() != predicted.len() || actual.is_empty() {
-        return 0.0;
-    }
-    
-    let n = actual.len() as f64;
-    let sum_squared_errors: f64 = actual.iter()
-        .zip(predicted.iter())
-        .map(|(&a, &p)| (a - p).powi(2))
-        .sum();
-    
-    sum_squared_errors / n
+    fn from_usize(n: usize) -> Self;
+    fn from_f64(value: f64) -> Self;
+    fn to_f64(self) -> f64;
+    fn abs(self) -> Self;
+    fn sqrt(self) -> Self;
+    fn is_finite(self) -> bool;
 }
 
-/// Calcula o Coeficiente de Determinação (R²)
-pub fn calculate_r_squared(actual: &[f64], predicted: &[f64], y_mean: f64) -> f64 {
-    if actual.len() != predicted.len() || actual.is_empty() {
-        return 0.0;
-    }
-    
-    let total_sum_squares: f64 = actual.iter()
-        .map(|&y| (y - y_mean).powi(2))
-        .sum();
-    
-    let residual_sum_squares: f64 = actual.iter()
-        .zip(predicted.iter())
-        .map(|(&a, &p)| (a - p).powi(2))
-        .sum();
-    
-    if total_sum_squares.abs() < f64::EPSILON {
-        1.0
-    } else {
-        1.0 - (residual_sum_squares / total_sum_squares)
-    }
-}
+macro_rules! impl_float {
+    ($t:ty) => {
+        impl Float for $t {
+            const ZERO: Self = 0.0;
+            const ONE: Self = 1.0;
+            const EPSILON: Self = <$t>::EPSILON;
 
-/// Realiza previsões futuras usando os coeficientes da regressão linear
-pub fn predict_future(result: &LinearRegressionResult, future_periods: usize) -> Vec<f64> {
-    let n = result.predictions.len();
-    (0..future_periods)
-        .map(|i| result.intercept + result.slope * (n + i) as f64)
-        .collect()
+            fn from_usize(n: usize) -> Self {
+                n as $t
+            }
+            fn from_f64(value: f64) -> Self {
+                value as $t
+            }
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+            fn abs(self) -> Self {
+                <$t>::abs(self)
+            }
+            fn sqrt(self) -> Self {
+                #[cfg(feature = "std")]
+                return <$t>::sqrt(self);
+                #[cfg(not(feature = "std"))]
+                return math::sqrt(self as f64) as $t;
+            }
+            fn is_finite(self) -> bool {
+                <$t>::is_finite(self)
+            }
+        }
+    };
 }
 
-/// Calcula estatísticas descritivas básicas para uma série temporal
-pub fn calculate_descriptive_stats(data: &[f64]) -> Result<(f64, f64, f64, f64), TimeSeriesError> {
-    if data.is_empty() {
-        return Err(TimeSeriesError::new("Dados vazios para cálculo de estatísticas"));
-    }
-    
-    let n = data.len() as f64;
-    let mean = data.iter().sum::<f64>() / n;
-    
-    let variance: f64 = data.iter()
-        .map(|&x| (x - mean).powi(2))
-        .sum::<f64>() / n;
-    
-    let std_dev = variance.sqrt();
-    let min = data.iter().fold(f64::INFINITY, |a, &b| a.min(b));
-    let max = data.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
-    
-    Ok((mean, std_dev, min, max))
-}
+impl_float!(f32);
+impl_float!(f64);
 
-/// Gera uma visualização ASCII art da série temporal e previsões
-pub fn ascii_plot(actual: &[f64], predicted: &[f64], title: &str) {
-    if actual.is_empty() || actual.len() != predicted.len() {
-        println!("Dados inválidos para plotagem");
-        return;
-    }
+/// Soma compensada de Kahan–Babuška (Neumaier)
+///
+/// Mantém um termo de correção com os bits perdidos a cada adição, de modo que somas
+/// longas de valores grandes e parecidos não acumulem erro de arredondamento.
+#[derive(Debug, Clone, Copy, Default)]
+struct KahanSum<T: Float = f64> {
+    sum: T,
+    compensation: T,
+}
 
-    let height = 10;
-    let width = actual.len() * 2;
-    
-    let all_values: Vec<f64> = actual.iter().chain(predicted.iter()).cloned().collect();
-    let min_val = all_values.iter().fold(f64::INFINITY, |a, &b| a.min(b));
-    let max_val = all_values.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
-    let range = max_val - min_val;
-    
-    if range.abs() < f64::EPSILON {
-        println!("Intervalo de dados muito pequeno para plotagem");
-        return;
+impl<T: Float> KahanSum<T> {
+    fn new() -> Self {
+        KahanSum::default()
     }
 
-    println!("\n{}", title);
-    println!("{}", "-".repeat(width.min(60) + 12));
-    
-    for row in (0..height).rev() {
-        let threshold = min_val + (range * (row as f64) / (height as f64));
-        
-        print!("{:8.1} | ", threshold);
-        
-        for i in 0..actual.len() {
-            let is_actual = actual[i] >= threshold;
-            let is_predicted = predicted[i] >= threshold;
-            
-            if is_actual && is_predicted {
-                print!("●");
-            } else if is_actual {
-                print!("o");
-            } else if is_predicted {
-                print!("x");
-            } else {
-                print!(" ");
-            }
-            
-            if i < actual.len() - 1 {
-                print!(" ");
-            }
+    fn add(&mut self, value: T) {
+        let t = self.sum + value;
+        if self.sum.abs() >= value.abs() {
+            self.compensation += (self.sum - t) + value;
+        } else {
+            self.compensation += (value - t) + self.sum;
         }
-        println!();
+        self.sum = t;
     }
-    
-    println!("         |{}", "-".repeat(width.min(60) + 2));
-    print!("          ");
-    for i in 0..actual.len() {
-        print!("{} ", i + 1);
-        if i < actual.len() - 1 {
-            print!(" ");
-        }
-    }
-    println!("\n          Periodo");
-    
-    println!("\nLegenda:");
-    println!("  o = Valor Real");
-    println!("  x = Valor Previsto");
-    println!("  ● = Real e Previsto (sobrepostos)");
-}
-
-#[cfg(test)]
-mod testes {
-    use super::*;
 
-    fn assert_approx_eq(a: f64, b: f64, epsilon: f64) {
-        assert!((a - b).abs() < epsilon, "{} != {} within {}", a, b, epsilon);
+    fn total(&self) -> T {
+        self.sum + self.compensation
     }
 
-    #[test]
-    fn test_regressao_linear_ajuste_perfeito() {
-        let data = vec![1.0, 3.0, 5.0, 7.0, 9.0];
-        let result = linear_regression(&data).unwrap();
-        
-        assert_approx_eq(result.slope, 2.0, 1e-10);
-        assert_approx_eq(result.intercept, 1.0, 1e-10);
-        assert_approx_eq(result.r_squared, 1.0, 1e-10);
-        assert_approx_eq(result.mse, 0.0, 1e-10);
+    fn merge(mut self, other: Self) -> Self {
+        self.add(other.sum);
+        self.add(other.compensation);
+        self
     }
+}
 
-    #[test]
-    fn test_regressao_linear_dados_constantes() {
-        let data = vec![5.0, 5.0, 5.0, 5.0, 5.0];
-        let result = linear_regression(&data).unwrap();
-        
-        assert_approx_eq(result.slope, 0.0, 1e-10);
-        assert_approx_eq(result.intercept, 5.0, 1e-10);
-        assert_approx_eq(result.r_squared, 1.0, 1e-10);
-    }
+/// Menor tamanho de entrada processado em paralelo com a feature `parallel`
+#[cfg(feature = "parallel")]
+const PARALLEL_MIN_LEN: usize = 1 << 15;
 
-    #[test]
-    fn test_regressao_linear_slope_negativo() {
-        let data = vec![5.0, 4.0, 3.0, 2.0, 1.0];
-        let result = linear_regression(&data).unwrap();
-        
-        assert_approx_eq(result.slope, -1.0, 1e-10);
-        assert_approx_eq(result.intercept, 5.0, 1e-10);
-        assert_approx_eq(result.r_squared, 1.0, 1e-10);
+/// Aplica `f` a intervalos de índices de `0..len` e combina os resultados com `merge`
+///
+/// Com a feature `parallel` e entradas a partir de `PARALLEL_MIN_LEN`, os intervalos
+/// são processados em threads (uma por núcleo); caso contrário `f` recebe `0..len`.
+/// Como a soma em ponto flutuante não é associativa, o caminho paralelo pode diferir
+/// do sequencial no último dígito (diferença relativa da ordem de 1e-15, bem abaixo de 1e-9).
+#[cfg_attr(not(feature = "parallel"), allow(unused_variables))]
+fn fold_ranges<R, F, M>(len: usize, f: F, merge: M) -> R
+where
+    R: Send,
+    F: Fn(Range<usize>) -> R + Sync,
+    M: Fn(R, R) -> R,
+{
+    #[cfg(feature = "parallel")]
+    if len >= PARALLEL_MIN_LEN {
+        let threads = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+        return fold_ranges_threaded(len, len.div_ceil(threads), f, merge);
     }
+    f(0..len)
+}
 
-    #[test]
-    fn test_calcular_mse() {
-        let actual = vec![1.0, 2.0, 3.0];
-        let predicted = vec![1.0, 2.0, 3.0];
-        assert_approx_eq(calculate_mse(&actual, &predicted), 0.0, 1e-10);
-        
-        let predicted2 = vec![2.0, 3.0, 4.0];
-        assert_approx_eq(calculate_mse(&actual, &predicted2), 1.0, 1e-10);
-    }
+#[cfg(feature = "parallel")]
+fn fold_ranges_threaded<R, F, M>(len: usize, chunk_len: usize, f: F, merge: M) -> R
+where
+    R: Send,
+    F: Fn(Range<usize>) -> R + Sync,
+    M: Fn(R, R) -> R,
+{
+    let chunk_len = chunk_len.max(1);
+    let f = &f;
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..len)
+            .step_by(chunk_len)
+            .map(|start| scope.spawn(move || f(start..(start + chunk_len).min(len))))
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("thread de cálculo paralelo falhou"))
+            .reduce(merge)
+            .unwrap_or_else(|| f(0..0))
+    })
+}
 
-    #[test]
-    fn test_calcular_r_quadrado() {
-        let actual = vec![1.0, 2.0, 3.0, 4.0, 5.0];
-        let predicted = vec![1.0, 2.0, 3.0, 4.0, 5.0];
-        let y_mean = 3.0;
-        assert_approx_eq(calculate_r_squared(&actual, &predicted, y_mean), 1.0, 1e-10);
-        
-        let predicted2 = vec![3.0, 3.0, 3.0, 3.0, 3.0];
-        assert_approx_eq(calculate_r_squared(&actual, &predicted2, y_mean), 0.0, 1e-10);
+fn kahan_sum<T: Float, I: IntoIterator<Item = T>>(values: I) -> T {
+    let mut acc = KahanSum::new();
+    for value in values {
+        acc.add(value);
     }
+    acc.total()
+}
 
-    #[test]
-    fn test_previsao_futura() {
-        let data = vec![1.0, 2.0, 3.0, 4.0, 5.0];
-        let result = linear_regression(&data).unwrap();
-        let predictions = predict_future(&result, 3);
-        
-        assert_eq!(predictions.len(), 3);
-        assert_approx_eq(predictions[0], 6.0, 1e-10);
-        assert_approx_eq(predictions[1], 7.0, 1e-10);
-        assert_approx_eq(predictions[2], 8.0, 1e-10);
-    }
+/// Múltiplo de `EPSILON` tolerado por [`approx_zero`], para absorver o arredondamento acumulado
+const APPROX_ZERO_ULPS: f64 = 16.0;
 
-    #[test]
-    fn test_estatisticas_descritivas() {
-        let data = vec![1.0, 2.0, 3.0, 4.0, 5.0];
-        let (mean, std_dev, min, max) = calculate_descriptive_stats(&data).unwrap();
-        
-        assert_approx_eq(mean, 3.0, 1e-10);
-        assert_approx_eq(std_dev, (2.0_f64).sqrt(), 1e-10);
-        assert_approx_eq(min, 1.0, 1e-10);
-        assert_approx_eq(max, 5.0, 1e-10);
-    }
+/// Indica se `value` é desprezível frente a `scale`: `|value| ≤ 16·ε·|scale|`
+///
+/// `scale` deve estar na mesma unidade de `value` — por exemplo, a maior magnitude dos
+/// dados ao testar uma amplitude ou um desvio padrão. Assim a mesma série é tratada igual
+/// em torno de 1e-12 ou de 1e12. Com `scale` nulo, só o zero é desprezível.
+pub fn approx_zero<T: Float>(value: T, scale: T) -> bool {
+    value.abs() <= T::EPSILON * T::from_f64(APPROX_ZERO_ULPS) * scale.abs()
+}
 
-    #[test]
-    fn test_dados_vazios() {
-        let data: Vec<f64> = vec![];
-        let result = linear_regression(&data);
-        assert!(result.is_err());
-        
-        let stats_result = calculate_descriptive_stats(&data);
-        assert!(stats_result.is_err());
-    }
+/// Maior valor absoluto de `values` (zero se vazio)
+fn max_abs<T: Float>(values: &[T]) -> T {
+    values
+        .iter()
+        .map(|v| v.abs())
+        .fold(T::ZERO, |a, b| if b > a { b } else { a })
+}
 
-    #[test]
-    fn test_unico_ponto_dado() {
-        let data = vec![5.0];
-        let result = linear_regression(&data);
-        assert!(result.is_err());
+/// Verifica se todos os valores são finitos (sem NaN ou infinito)
+///
+/// O erro indica o índice do primeiro valor não finito.
+pub fn validate_finite<T: Float>(data: &[T]) -> Result<(), TimeSeriesError> {
+    match data.iter().position(|v| !v.is_finite()) {
+        Some(index) => Err(TimeSeriesError::NonFiniteValue { index }),
+        None => Ok(()),
     }
+}
 
-    #[test]
-    fn test_caso_limite_denominador_zero() {
-        let data = vec![1.0, 1.0, 1.0, 1.0];
-        let result = linear_regression(&data).unwrap();
-        
-        assert_approx_eq(result.slope, 0.0, 1e-10);
-        assert_approx_eq(result.intercept, 1.0, 1e-10);
-        assert_approx_eq(result.r_squared, 1.0, 1e-10);
-    }
+#[cfg(test)]
+mod testes {
+    use super::*;
 
-    #[test]
-    fn test_documentacao_exemplos() {
-        let dados = vec![1.0, 2.0, 3.0, 4.0, 5.0];
-        let resultado = linear_regression(&dados).unwrap();
-        
-        assert!(resultado.slope.is_finite());
-        assert!(resultado.intercept.is_finite());
-        
-        let previsoes = predict_future(&resultado, 3);
-        assert_eq!(previsoes.len(), 3);
-        
-        ascii_plot(&dados, &resultado.predictions, "Teste");
+    pub(crate) fn assert_approx_eq(a: f64, b: f64, epsilon: f64) {
+        assert!((a - b).abs() < epsilon, "{} != {} within {}", a, b, epsilon);
     }
 
-    #[test]
-    fn test_ascii_plot_dados_invalidos() {
-        ascii_plot(&[], &[], "Vazio");
-        ascii_plot(&[1.0], &[1.0, 2.0], "Tamanhos diferentes");
+    /// Valores pseudoaleatórios em [0, 1) (xorshift64), reproduzíveis pela semente
+    pub(crate) fn pseudo_random(seed: u64, n: usize) -> Vec<f64> {
+        let mut state = seed.max(1);
+        (0..n)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                (state >> 11) as f64 / (1u64 << 53) as f64
+            })
+            .collect()
     }
 
     #[test]
-    fn test_ascii_plot_dados_constantes() {
-        let data = vec![5.0, 5.0, 5.0];
-        ascii_plot(&data, &data, "Constantes");
+    fn test_soma_kahan() {
+        let values = std::iter::once(1e16).chain(std::iter::repeat_n(1.0, 1000)).chain(std::iter::once(-1e16));
+        assert_eq!(kahan_sum(values), 1000.0);
     }
 }
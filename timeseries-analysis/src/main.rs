@@ -1,57 +1,205 @@
+use std::io::Read;
+use std::process::ExitCode;
+
 use timeseries_analysis::*;
 
-fn main() {
-    // Exemplo de uso da biblioteca
-    let sales_data = vec![100.0, 120.0, 130.0, 145.0, 160.0];
-    
-    println!("=== ANALISE DE SERIES TEMPORAIS ===");
-    println!("Dados: {:?}", sales_data);
-    
-    // Calcular estatisticas descritivas
-    if let Ok((mean, std_dev, min, max)) = calculate_descriptive_stats(&sales_data) {
-        println!("\nEstatisticas Descritivas:");
-        println!("   Media: {:.2}", mean);
-        println!("   Desvio Padrao: {:.2}", std_dev);
-        println!("   Minimo: {:.2}", min);
-        println!("   Maximo: {:.2}", max);
-    }
-    
-    // Realizar regressao linear
-    match linear_regression(&sales_data) {
-        Ok(result) => {
-            println!("\nResultado da Regressao Linear:");
-            println!("   Slope (β1): {:.4}", result.slope);
-            println!("   Intercept (β0): {:.4}", result.intercept);
-            println!("   R²: {:.4}", result.r_squared);
-            println!("   MSE: {:.4}", result.mse);
-            
-            println!("\nValores Previstos:");
-            for (i, (actual, predicted)) in sales_data.iter().zip(result.predictions.iter()).enumerate() {
-                println!("   Periodo {}: Real = {:.1}, Previsto = {:.1}", i + 1, actual, predicted);
+const USAGE: &str = "Uso: timeseries-analysis <arquivo|-> [--forecast N] [--column K] [--output FORMATO] [--decimals N] [--plot|--no-plot]
+
+  <arquivo|->     arquivo com um valor por linha ou CSV; '-' lê da entrada padrão
+  --forecast N    número de períodos a prever (padrão: 3)
+  --column K      coluna do CSV a analisar, a partir de 0 (padrão: 0)
+  --output F      formato da saída: table, json ou csv (padrão: table)
+  --decimals N    casas decimais de todos os números (padrão: precisão de cada seção)
+  --plot          exibe os gráficos ASCII mesmo com --output json/csv
+  --no-plot       não exibe os gráficos ASCII";
+
+/// Formato da saída gerada pelo binário
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum OutputFormat {
+    Table,
+    Json,
+    Csv,
+}
+
+impl OutputFormat {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "table" => Some(OutputFormat::Table),
+            "json" => Some(OutputFormat::Json),
+            "csv" => Some(OutputFormat::Csv),
+            _ => None,
+        }
+    }
+}
+
+/// Opções da linha de comando
+struct Options {
+    path: String,
+    forecast: usize,
+    column: usize,
+    output: OutputFormat,
+    /// `None` mantém a formatação padrão de cada saída
+    decimals: Option<usize>,
+    /// `None` quando nem `--plot` nem `--no-plot` foram informados
+    plot: Option<bool>,
+}
+
+impl Options {
+    /// Os gráficos só aparecem por padrão na saída em tabela
+    fn show_plot(&self) -> bool {
+        self.plot.unwrap_or(self.output == OutputFormat::Table)
+    }
+
+    fn format(&self) -> Option<FormatOptions> {
+        self.decimals.map(FormatOptions::with_decimals)
+    }
+}
+
+fn parse_args<I: Iterator<Item = String>>(mut args: I) -> Result<Options, String> {
+    let mut path = None;
+    let mut options = Options {
+        path: String::new(),
+        forecast: 3,
+        column: 0,
+        output: OutputFormat::Table,
+        decimals: None,
+        plot: None,
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--forecast" | "--column" | "--decimals" => {
+                let value = args.next().ok_or_else(|| format!("{} exige um valor", arg))?;
+                let value: usize = value
+                    .parse()
+                    .map_err(|_| format!("valor inválido para {}: '{}'", arg, value))?;
+                match arg.as_str() {
+                    "--forecast" => options.forecast = value,
+                    "--column" => options.column = value,
+                    _ => options.decimals = Some(value),
+                }
             }
-            
-            // Gerar gráfico ASCII
-            ascii_plot(&sales_data, &result.predictions, "Vendas - Real vs Previsto");
-            
-            // Fazer previsoes futuras
-            let forecasts = predict_future(&result, 3);
-            println!("\nPrevisoes para os proximos 3 periodos:");
-            for (i, forecast) in forecasts.iter().enumerate() {
-                println!("   Periodo {}: {:.2}", i + 6, forecast); // Começa do período 6
+            "--output" => {
+                let value = args.next().ok_or_else(|| format!("{} exige um valor", arg))?;
+                options.output = OutputFormat::parse(&value)
+                    .ok_or_else(|| format!("formato de saída inválido: '{}'", value))?;
             }
+            "--plot" => options.plot = Some(true),
+            "--no-plot" => options.plot = Some(false),
+            "-h" | "--help" => return Err(String::new()),
+            _ if arg.starts_with("--") => return Err(format!("opção desconhecida: {}", arg)),
+            _ if path.is_none() => path = Some(arg),
+            _ => return Err(format!("argumento inesperado: {}", arg)),
+        }
+    }
+
+    options.path = path.ok_or_else(|| "informe o arquivo de dados ou '-'".to_string())?;
+    Ok(options)
+}
 
-            // Gerar gráfico com previsões futuras
-            let all_data: Vec<f64> = sales_data.iter().chain(forecasts.iter()).cloned().collect();
-            let all_predictions: Vec<f64> = result.predictions.iter().chain(forecasts.iter()).cloned().collect();
-            ascii_plot(&all_data, &all_predictions, "Vendas - Serie Completa com Previsoes");
+fn read_data(options: &Options) -> Result<Vec<f64>, TimeSeriesError> {
+    if options.path == "-" {
+        let mut text = String::new();
+        std::io::stdin()
+            .read_to_string(&mut text)
+            .map_err(|err| TimeSeriesError::new(format!("Erro ao ler a entrada padrão: {}", err)))?;
+        parse_series(&text, options.column)
+    } else {
+        load_series(&options.path, options.column)
+    }
+}
+
+fn run(options: &Options) -> Result<(), TimeSeriesError> {
+    let data = read_data(options)?;
+    let stats = calculate_descriptive_stats(&data)?;
+    let result = linear_regression(&data)?;
+    let report = evaluate_all(&data, &result.predictions)?;
+    let points = result.forecast(options.forecast);
+    let forecasts: Vec<f64> = points.iter().map(|point| point.value).collect();
+
+    let format = options.format();
+    match options.output {
+        OutputFormat::Table => print_table(&data, options.forecast, format)?,
+        OutputFormat::Json => print_json(&stats, &result, &report, &forecasts, format.as_ref()),
+        OutputFormat::Csv => print_csv(&data, &result, &points, format.as_ref()),
+    }
+
+    if options.show_plot() {
+        ascii_plot_colored(&data, &result.predictions, "Real vs Previsto");
+        if !forecasts.is_empty() {
+            ascii_plot_forecast(&data, &result, &forecasts, "Serie Completa com Previsoes");
         }
-        Err(e) => println!("\nErro na regressao: {}", e),
     }
+    Ok(())
+}
+
+/// Relatório completo; os gráficos ficam a cargo de `run`, que usa cores no terminal
+fn print_table(data: &[f64], forecast_periods: usize, format: Option<FormatOptions>) -> Result<(), TimeSeriesError> {
+    let options = ReportOptions {
+        plot: false,
+        forecast_periods,
+        format,
+        ..ReportOptions::default()
+    };
+    print!("{}", generate_report(data, &options)?);
+    Ok(())
+}
 
-    // Exemplo adicional com dados perfeitos
-    println!("\n\n=== EXEMPLO ADICIONAL: DADOS PERFEITOS ===");
-    let perfect_data = vec![1.0, 2.0, 3.0, 4.0, 5.0];
-    if let Ok(perfect_result) = linear_regression(&perfect_data) {
-        ascii_plot(&perfect_data, &perfect_result.predictions, "Dados Perfeitos - y = x + 0");
+fn print_json(
+    stats: &DescriptiveStats,
+    result: &LinearRegressionResult,
+    report: &MetricsReport,
+    forecasts: &[f64],
+    format: Option<&FormatOptions>,
+) {
+    fn json<J: ToJson + ?Sized>(value: &J, format: Option<&FormatOptions>) -> String {
+        match format {
+            Some(format) => value.to_json_with(format),
+            None => value.to_json(),
+        }
+    }
+    println!(
+        "{{\"stats\":{},\"regression\":{},\"metrics\":{},\"forecasts\":{}}}",
+        json(stats, format),
+        json(result, format),
+        json(report, format),
+        json(forecasts, format)
+    );
+}
+
+/// Uma linha por período; colunas sem valor ficam vazias. Com `format`, os números não
+/// levam separador de milhar, que se confundiria com o separador de colunas
+fn print_csv(data: &[f64], result: &LinearRegressionResult, points: &[ForecastPoint], format: Option<&FormatOptions>) {
+    let num = |value: f64| match format {
+        Some(format) => FormatOptions { thousands_separator: None, ..*format }.format(value),
+        None => value.to_string(),
+    };
+    println!("period,actual,fitted,forecast");
+    for (i, (actual, fitted)) in data.iter().zip(&result.predictions).enumerate() {
+        println!("{},{},{},", i + 1, num(*actual), num(*fitted));
+    }
+    for point in points {
+        println!("{},,,{}", point.period_number(), num(point.value));
+    }
+}
+
+fn main() -> ExitCode {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(message) => {
+            if !message.is_empty() {
+                eprintln!("Erro: {}\n", message);
+            }
+            eprintln!("{}", USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    match run(&options) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{}", err);
+            ExitCode::FAILURE
+        }
     }
 }
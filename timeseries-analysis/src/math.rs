@@ -0,0 +1,278 @@
+//! Funções matemáticas de ponto flutuante para `no_std`
+//!
+//! `core` não oferece `sqrt`, `powi`, `ln`, `floor` etc. para `f64`; estas versões
+//! em software substituem os métodos da `std` quando a feature `std` está desativada.
+#![allow(dead_code)]
+
+// ln 2 dividido em parte alta (bits baixos zerados) e correção, como no fdlibm
+const LN_2_HI: f64 = f64::from_bits(0x3fe6_2e42_fee0_0000);
+const LN_2_LO: f64 = f64::from_bits(0x3dea_39ef_3579_3c76);
+const TWO_POW_52: f64 = 4_503_599_627_370_496.0;
+// π/2 em duas partes para a redução de argumento de sin/cos (Cody–Waite, como no fdlibm)
+const PIO2_HI: f64 = f64::from_bits(0x3ff9_21fb_5440_0000);
+const PIO2_LO: f64 = f64::from_bits(0x3dd0_b461_1a62_6331);
+
+pub(crate) trait FloatMath {
+    fn powi(self, n: i32) -> Self;
+    fn powf(self, exponent: Self) -> Self;
+    fn exp(self) -> Self;
+    fn ln(self) -> Self;
+    fn floor(self) -> Self;
+    fn ceil(self) -> Self;
+    fn round(self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+}
+
+impl FloatMath for f64 {
+    fn powi(self, n: i32) -> f64 {
+        powi(self, n)
+    }
+    fn powf(self, exponent: f64) -> f64 {
+        powf(self, exponent)
+    }
+    fn exp(self) -> f64 {
+        exp(self)
+    }
+    fn ln(self) -> f64 {
+        ln(self)
+    }
+    fn floor(self) -> f64 {
+        floor(self)
+    }
+    fn ceil(self) -> f64 {
+        -floor(-self)
+    }
+    fn round(self) -> f64 {
+        if self < 0.0 {
+            -floor(-self + 0.5)
+        } else {
+            floor(self + 0.5)
+        }
+    }
+    fn sin(self) -> f64 {
+        sin(self)
+    }
+    fn cos(self) -> f64 {
+        cos(self)
+    }
+}
+
+/// Raiz quadrada pelo método de Newton, partindo de uma estimativa pelos bits
+pub(crate) fn sqrt(x: f64) -> f64 {
+    if x.is_nan() || x < 0.0 {
+        return f64::NAN;
+    }
+    if x == 0.0 || x.is_infinite() {
+        return x;
+    }
+    let mut y = f64::from_bits((x.to_bits() >> 1) + (1023_u64 << 51));
+    for _ in 0..64 {
+        let next = 0.5 * (y + x / y);
+        if next == y {
+            break;
+        }
+        y = next;
+    }
+    y
+}
+
+pub(crate) fn powi(x: f64, n: i32) -> f64 {
+    let mut exponent = (n as i64).unsigned_abs();
+    let mut base = x;
+    let mut result = 1.0;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result *= base;
+        }
+        base *= base;
+        exponent >>= 1;
+    }
+    if n < 0 {
+        1.0 / result
+    } else {
+        result
+    }
+}
+
+pub(crate) fn floor(x: f64) -> f64 {
+    if !x.is_finite() || x.abs() >= TWO_POW_52 {
+        return x;
+    }
+    let truncated = x as i64 as f64;
+    if truncated > x {
+        truncated - 1.0
+    } else {
+        truncated
+    }
+}
+
+/// Multiplica por 2^k montando a potência diretamente nos bits do expoente
+fn scale_by_power_of_two(mut value: f64, mut k: i32) -> f64 {
+    while k > 1023 {
+        value *= f64::from_bits(2046_u64 << 52);
+        k -= 1023;
+    }
+    while k < -1022 {
+        value *= f64::from_bits(1_u64 << 52);
+        k += 1022;
+    }
+    value * f64::from_bits(((k + 1023) as u64) << 52)
+}
+
+/// Exponencial: redução `x = k·ln2 + r` seguida da série de Taylor de `e^r`
+pub(crate) fn exp(x: f64) -> f64 {
+    if x.is_nan() {
+        return x;
+    }
+    if x > 709.782_712_893_384 {
+        return f64::INFINITY;
+    }
+    if x < -745.133_219_101_941_1 {
+        return 0.0;
+    }
+    let k = floor(x / core::f64::consts::LN_2 + 0.5);
+    let r = (x - k * LN_2_HI) - k * LN_2_LO;
+
+    let mut term = 1.0;
+    let mut sum = 1.0;
+    for i in 1..24 {
+        term *= r / i as f64;
+        sum += term;
+    }
+    scale_by_power_of_two(sum, k as i32)
+}
+
+/// Logaritmo natural: `x = m·2^e` com `ln m = 2·atanh((m-1)/(m+1))`
+pub(crate) fn ln(x: f64) -> f64 {
+    if x.is_nan() || x < 0.0 {
+        return f64::NAN;
+    }
+    if x == 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if x.is_infinite() {
+        return x;
+    }
+
+    let (x, mut exponent) = if x < f64::MIN_POSITIVE {
+        (x * TWO_POW_52, -52)
+    } else {
+        (x, 0)
+    };
+    let bits = x.to_bits();
+    exponent += ((bits >> 52) & 0x7ff) as i32 - 1023;
+    let mut mantissa = f64::from_bits((bits & ((1_u64 << 52) - 1)) | (1023_u64 << 52));
+    if mantissa > core::f64::consts::SQRT_2 {
+        mantissa /= 2.0;
+        exponent += 1;
+    }
+
+    let s = (mantissa - 1.0) / (mantissa + 1.0);
+    let s2 = s * s;
+    let mut power = s;
+    let mut series = 0.0;
+    for i in 0..16 {
+        series += power / (2 * i + 1) as f64;
+        power *= s2;
+    }
+    let e = exponent as f64;
+    e * LN_2_HI + (2.0 * series + e * LN_2_LO)
+}
+
+pub(crate) fn powf(base: f64, exponent: f64) -> f64 {
+    if exponent == 0.0 {
+        return 1.0;
+    }
+    if base.is_nan() || exponent.is_nan() {
+        return f64::NAN;
+    }
+    if base == 0.0 {
+        return if exponent > 0.0 { 0.0 } else { f64::INFINITY };
+    }
+    if floor(exponent) == exponent && exponent.abs() <= i32::MAX as f64 {
+        return powi(base, exponent as i32);
+    }
+    if base < 0.0 {
+        return f64::NAN;
+    }
+    exp(exponent * ln(base))
+}
+
+/// Séries de Taylor de seno e cosseno, precisas para |r| ≤ π/4
+fn sin_cos_kernel(r: f64) -> (f64, f64) {
+    let r2 = r * r;
+    let (mut sin, mut cos) = (0.0, 0.0);
+    let (mut sin_term, mut cos_term) = (r, 1.0);
+    for k in 1..=10 {
+        sin += sin_term;
+        cos += cos_term;
+        let k = k as f64;
+        sin_term *= -r2 / ((2.0 * k) * (2.0 * k + 1.0));
+        cos_term *= -r2 / ((2.0 * k - 1.0) * (2.0 * k));
+    }
+    (sin, cos)
+}
+
+/// Reduz `x` a `r + k·π/2` com |r| ≤ π/4 e devolve `(sin x, cos x)`
+fn sin_cos(x: f64) -> (f64, f64) {
+    if !x.is_finite() {
+        return (f64::NAN, f64::NAN);
+    }
+    let k = floor(x / (PIO2_HI + PIO2_LO) + 0.5);
+    let r = (x - k * PIO2_HI) - k * PIO2_LO;
+    let (s, c) = sin_cos_kernel(r);
+    match (k as i64).rem_euclid(4) {
+        0 => (s, c),
+        1 => (c, -s),
+        2 => (-s, -c),
+        _ => (-c, s),
+    }
+}
+
+pub(crate) fn sin(x: f64) -> f64 {
+    sin_cos(x).0
+}
+
+pub(crate) fn cos(x: f64) -> f64 {
+    sin_cos(x).1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_rel(a: f64, b: f64, rel: f64) {
+        if a == b {
+            return;
+        }
+        let scale = b.abs().max(1e-300);
+        assert!(((a - b) / scale).abs() <= rel, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn test_funcoes_equivalentes_a_std() {
+        let values = [1e-310, 1e-20, 0.001, 0.3, 0.5, 1.0, 1.5, 2.0, 2.5, 10.0, 123.456, 1e15, 1e300];
+        for &x in &values {
+            assert_rel(sqrt(x), x.sqrt(), 1e-15);
+            assert_rel(ln(x), x.ln(), 1e-14);
+            assert_rel(powf(x, 1.5), x.powf(1.5), 1e-12);
+        }
+        for &x in &[-30.5, -2.0, -0.5, 0.0, 0.25, 1.0, 3.3, 50.0, 700.0] {
+            assert_rel(exp(x), x.exp(), 1e-14);
+        }
+        for &x in &[-2.5, -2.0, -0.1, 0.0, 0.4, 0.5, 3.999, 1e17] {
+            assert_eq!(floor(x), x.floor());
+            assert_eq!(FloatMath::ceil(x), x.ceil());
+            assert_eq!(FloatMath::round(x), x.round());
+        }
+        for &x in &[-100.0, -3.0, -1.0, -0.1, 0.0, 0.5, 1.0, 2.0, 3.5, 10.0, 1000.0] {
+            assert!((sin(x) - x.sin()).abs() <= 1e-14, "sin({})", x);
+            assert!((cos(x) - x.cos()).abs() <= 1e-14, "cos({})", x);
+        }
+        assert_eq!(powi(3.0, 4), 81.0);
+        assert_eq!(powi(2.0, -2), 0.25);
+        assert!(sqrt(-1.0).is_nan());
+        assert_eq!(ln(0.0), f64::NEG_INFINITY);
+    }
+}
@@ -0,0 +1,385 @@
+//! Métricas de erro entre valores reais e previstos
+//!
+//! ```
+//! use timeseries_analysis::metrics::{calculate_mae, evaluate_all};
+//!
+//! let actual = [1.0, 2.0, 3.0];
+//! let predicted = [1.0, 2.0, 5.0];
+//! assert_eq!(calculate_mae(&actual, &predicted).unwrap(), 2.0 / 3.0);
+//! let report = evaluate_all(&actual, &predicted).unwrap();
+//! assert_eq!(report.rmse, (4.0_f64 / 3.0).sqrt());
+//! ```
+
+use crate::*;
+
+/// Soma compensada de `(a - p)²` para slices de mesmo tamanho
+pub(crate) fn squared_error_sum<T: Float>(actual: &[T], predicted: &[T]) -> T {
+    fold_ranges(
+        actual.len(),
+        |range| {
+            kahan_sum(actual[range.clone()].iter()
+                .zip(predicted[range].iter())
+                .map(|(&a, &p)| (a - p) * (a - p)))
+        },
+        |a, b| a + b,
+    )
+}
+
+/// Exige slices não vazios e de mesmo tamanho
+fn validate_metric_lengths<T>(actual: &[T], predicted: &[T]) -> Result<(), TimeSeriesError> {
+    if actual.len() != predicted.len() {
+        return Err(TimeSeriesError::LengthMismatch {
+            left: actual.len(),
+            right: predicted.len(),
+        });
+    }
+    if actual.is_empty() {
+        return Err(TimeSeriesError::insufficient_data(1, 0));
+    }
+    Ok(())
+}
+
+/// Calcula o Erro Quadrático Médio (MSE)
+///
+/// Erro se os slices tiverem tamanhos diferentes ou estiverem vazios.
+pub fn calculate_mse<T: Float>(actual: &[T], predicted: &[T]) -> Result<T, TimeSeriesError> {
+    validate_metric_lengths(actual, predicted)?;
+    Ok(squared_error_sum(actual, predicted) / T::from_usize(actual.len()))
+}
+
+/// Calcula o Erro Absoluto Médio (MAE)
+///
+/// Erro se os slices tiverem tamanhos diferentes ou estiverem vazios.
+pub fn calculate_mae<T: Float>(actual: &[T], predicted: &[T]) -> Result<T, TimeSeriesError> {
+    validate_metric_lengths(actual, predicted)?;
+
+    let n = T::from_usize(actual.len());
+    let sum_abs_errors = kahan_sum(actual.iter()
+        .zip(predicted.iter())
+        .map(|(&a, &p)| (a - p).abs()));
+
+    Ok(sum_abs_errors / n)
+}
+
+/// Calcula a Raiz do Erro Quadrático Médio (RMSE)
+///
+/// Erro se os slices tiverem tamanhos diferentes ou estiverem vazios.
+pub fn calculate_rmse<T: Float>(actual: &[T], predicted: &[T]) -> Result<T, TimeSeriesError> {
+    Ok(calculate_mse(actual, predicted)?.sqrt())
+}
+
+/// Calcula o Erro Percentual Absoluto Médio (MAPE), em porcentagem
+///
+/// Retorna `None` quando algum valor real é zero, pois o erro percentual não é definido.
+/// Erro se os slices tiverem tamanhos diferentes ou estiverem vazios.
+pub fn calculate_mape<T: Float>(actual: &[T], predicted: &[T]) -> Result<Option<T>, TimeSeriesError> {
+    validate_metric_lengths(actual, predicted)?;
+    if actual.contains(&T::ZERO) {
+        return Ok(None);
+    }
+
+    let n = T::from_usize(actual.len());
+    let sum_pct_errors = kahan_sum(actual.iter()
+        .zip(predicted.iter())
+        .map(|(&a, &p)| ((a - p) / a).abs()));
+
+    Ok(Some(sum_pct_errors / n * T::from_f64(100.0)))
+}
+
+/// Calcula o Erro Percentual Absoluto Médio Simétrico (sMAPE), em porcentagem
+///
+/// Pares em que real e previsto são ambos zero contribuem com erro zero. Erro se os slices
+/// tiverem tamanhos diferentes ou estiverem vazios.
+pub fn calculate_smape<T: Float>(actual: &[T], predicted: &[T]) -> Result<T, TimeSeriesError> {
+    validate_metric_lengths(actual, predicted)?;
+
+    let n = T::from_usize(actual.len());
+    let two = T::from_f64(2.0);
+    let sum_terms = kahan_sum(actual.iter()
+        .zip(predicted.iter())
+        .map(|(&a, &p)| {
+            let denominator = a.abs() + p.abs();
+            if denominator == T::ZERO {
+                T::ZERO
+            } else {
+                two * (a - p).abs() / denominator
+            }
+        }));
+
+    Ok(sum_terms / n * T::from_f64(100.0))
+}
+
+/// Calcula o Coeficiente de Determinação (R²)
+///
+/// A média de `actual` é calculada internamente. Série real constante resulta em 1. Erro
+/// se os slices tiverem tamanhos diferentes ou estiverem vazios.
+pub fn calculate_r_squared<T: Float>(actual: &[T], predicted: &[T]) -> Result<T, TimeSeriesError> {
+    validate_metric_lengths(actual, predicted)?;
+    let y_mean = kahan_sum(actual.iter().copied()) / T::from_usize(actual.len());
+    Ok(r_squared_with_mean(actual, predicted, y_mean))
+}
+
+/// R² com a média de `actual` já calculada; os slices devem ter o mesmo tamanho
+pub(crate) fn r_squared_with_mean<T: Float>(actual: &[T], predicted: &[T], y_mean: T) -> T {
+    let total_sum_squares = fold_ranges(
+        actual.len(),
+        |range| kahan_sum(actual[range].iter().map(|&y| (y - y_mean) * (y - y_mean))),
+        |a, b| a + b,
+    );
+
+    let residual_sum_squares = squared_error_sum(actual, predicted);
+
+    let spread = (total_sum_squares / T::from_usize(actual.len())).sqrt();
+    if approx_zero(spread, max_abs(actual)) {
+        T::ONE
+    } else {
+        T::ONE - (residual_sum_squares / total_sum_squares)
+    }
+}
+
+/// Métricas de erro disponíveis para avaliação de previsões
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorMetric {
+    Mse,
+    Mae,
+    Mape,
+    Rmse,
+    Smape,
+}
+
+impl ErrorMetric {
+    /// Todas as métricas, na ordem em que aparecem no relatório
+    pub const ALL: [ErrorMetric; 5] = [
+        ErrorMetric::Mse,
+        ErrorMetric::Rmse,
+        ErrorMetric::Mae,
+        ErrorMetric::Mape,
+        ErrorMetric::Smape,
+    ];
+
+    /// Nome curto da métrica
+    pub fn name(&self) -> &'static str {
+        match self {
+            ErrorMetric::Mse => "MSE",
+            ErrorMetric::Mae => "MAE",
+            ErrorMetric::Mape => "MAPE",
+            ErrorMetric::Rmse => "RMSE",
+            ErrorMetric::Smape => "sMAPE",
+        }
+    }
+}
+
+impl fmt::Display for ErrorMetric {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Relatório com todas as métricas de erro de um ajuste
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricsReport<T: Float = f64> {
+    pub mse: T,
+    pub rmse: T,
+    pub mae: T,
+    /// `None` quando algum valor real é zero
+    pub mape: Option<T>,
+    pub smape: T,
+}
+
+impl<T: Float> MetricsReport<T> {
+    /// `values` formata MSE/RMSE/MAE e `percents` os erros percentuais
+    fn write_formatted<W: fmt::Write>(
+        &self,
+        f: &mut W,
+        values: &FormatOptions,
+        percents: &FormatOptions,
+    ) -> fmt::Result {
+        writeln!(f, "{:<8} {:>12}", "Metrica", "Valor")?;
+        writeln!(f, "{:<8} {:>12}", "-".repeat(8), "-".repeat(12))?;
+        writeln!(f, "{:<8} {:>12}", "MSE", values.format(self.mse.to_f64()))?;
+        writeln!(f, "{:<8} {:>12}", "RMSE", values.format(self.rmse.to_f64()))?;
+        writeln!(f, "{:<8} {:>12}", "MAE", values.format(self.mae.to_f64()))?;
+        match self.mape {
+            Some(mape) => writeln!(f, "{:<8} {:>11}%", "MAPE", percents.format(mape.to_f64()))?,
+            None => writeln!(f, "{:<8} {:>12}", "MAPE", "n/d")?,
+        }
+        write!(f, "{:<8} {:>11}%", "sMAPE", percents.format(self.smape.to_f64()))
+    }
+}
+
+/// Erros com 4 casas decimais e percentuais com 2; a precisão do formatador vale para ambos
+impl<T: Float> fmt::Display for MetricsReport<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let values = FormatOptions::for_formatter(f, 4);
+        let percents = FormatOptions::for_formatter(f, 2);
+        self.write_formatted(f, &values, &percents)
+    }
+}
+
+impl<T: Float> FormatWith for MetricsReport<T> {
+    fn format_with(&self, options: &FormatOptions) -> String {
+        let mut out = String::new();
+        let _ = self.write_formatted(&mut out, options, options);
+        out
+    }
+}
+
+fn validate_metric_inputs<T: Float>(actual: &[T], predicted: &[T]) -> Result<(), TimeSeriesError> {
+    if actual.is_empty() {
+        return Err(TimeSeriesError::insufficient_data(1, 0));
+    }
+    if actual.len() != predicted.len() {
+        return Err(TimeSeriesError::LengthMismatch {
+            left: actual.len(),
+            right: predicted.len(),
+        });
+    }
+    validate_finite(actual)?;
+    validate_finite(predicted)
+}
+
+/// Calcula uma métrica de erro entre valores reais e previstos
+pub fn evaluate<T: Float>(actual: &[T], predicted: &[T], metric: ErrorMetric) -> Result<T, TimeSeriesError> {
+    validate_metric_inputs(actual, predicted)?;
+
+    match metric {
+        ErrorMetric::Mse => calculate_mse(actual, predicted),
+        ErrorMetric::Mae => calculate_mae(actual, predicted),
+        ErrorMetric::Rmse => calculate_rmse(actual, predicted),
+        ErrorMetric::Smape => calculate_smape(actual, predicted),
+        ErrorMetric::Mape => calculate_mape(actual, predicted)?
+            .ok_or_else(|| TimeSeriesError::new(
+                LocalizedText::new(
+                    "MAPE indefinido: valor real igual a zero",
+                    "MAPE undefined: actual value equal to zero",
+                ),
+            )),
+    }
+}
+
+/// Calcula todas as métricas de erro de uma vez
+pub fn evaluate_all<T: Float>(actual: &[T], predicted: &[T]) -> Result<MetricsReport<T>, TimeSeriesError> {
+    validate_metric_inputs(actual, predicted)?;
+
+    Ok(MetricsReport {
+        mse: calculate_mse(actual, predicted)?,
+        rmse: calculate_rmse(actual, predicted)?,
+        mae: calculate_mae(actual, predicted)?,
+        mape: calculate_mape(actual, predicted)?,
+        smape: calculate_smape(actual, predicted)?,
+    })
+}
+
+/// [`evaluate_all`] sobre séries alinhadas (veja [`align_start`] e [`align_end`])
+///
+/// Só entram os índices em que os dois lados têm valor; retorna as métricas e quantos
+/// índices foram pulados. Erro se os tamanhos diferirem ou nenhum par estiver completo.
+pub fn evaluate_aligned(
+    actual: &[Option<f64>],
+    predicted: &[Option<f64>],
+) -> Result<(MetricsReport, usize), TimeSeriesError> {
+    if actual.len() != predicted.len() {
+        return Err(TimeSeriesError::LengthMismatch {
+            left: actual.len(),
+            right: predicted.len(),
+        });
+    }
+    let (a, p): (Vec<f64>, Vec<f64>) = actual
+        .iter()
+        .zip(predicted)
+        .filter_map(|pair| match pair {
+            (Some(a), Some(p)) => Some((*a, *p)),
+            _ => None,
+        })
+        .unzip();
+    let skipped = actual.len() - a.len();
+    Ok((evaluate_all(&a, &p)?, skipped))
+}
+
+#[cfg(test)]
+mod testes {
+    use super::*;
+    use crate::testes::assert_approx_eq;
+
+    #[test]
+    fn test_calcular_mse() {
+        let actual = vec![1.0, 2.0, 3.0];
+        let predicted = vec![1.0, 2.0, 3.0];
+        assert_approx_eq(calculate_mse(&actual, &predicted).unwrap(), 0.0, 1e-10);
+
+        let predicted2 = vec![2.0, 3.0, 4.0];
+        assert_approx_eq(calculate_mse(&actual, &predicted2).unwrap(), 1.0, 1e-10);
+
+        assert_eq!(
+            calculate_mse(&actual, &predicted2[..2]).unwrap_err(),
+            TimeSeriesError::LengthMismatch { left: 3, right: 2 }
+        );
+        assert_eq!(calculate_mse::<f64>(&[], &[]).unwrap_err(), TimeSeriesError::insufficient_data(1, 0));
+        assert!(calculate_rmse(&actual, &predicted2[..1]).is_err());
+    }
+
+    #[test]
+    fn test_mae_mape_smape_rejeitam_tamanhos_invalidos() {
+        let actual = [1.0, 2.0, 3.0];
+        let mismatch = TimeSeriesError::LengthMismatch { left: 3, right: 2 };
+        let empty = TimeSeriesError::insufficient_data(1, 0);
+
+        assert_eq!(calculate_mae(&actual, &actual[..2]).unwrap_err(), mismatch);
+        assert_eq!(calculate_mape(&actual, &actual[..2]).unwrap_err(), mismatch);
+        assert_eq!(calculate_smape(&actual, &actual[..2]).unwrap_err(), mismatch);
+        assert_eq!(calculate_mae::<f64>(&[], &[]).unwrap_err(), empty);
+        assert_eq!(calculate_mape::<f64>(&[], &[]).unwrap_err(), empty);
+        assert_eq!(calculate_smape::<f64>(&[], &[]).unwrap_err(), empty);
+
+        assert_eq!(calculate_mape(&[0.0, 1.0], &[1.0, 1.0]).unwrap(), None);
+        assert_eq!(calculate_smape(&[0.0, 2.0], &[0.0, 2.0]).unwrap(), 0.0);
+    }
+
+    #[test]
+    fn test_calcular_r_quadrado() {
+        let actual = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+        let predicted = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+        assert_approx_eq(calculate_r_squared(&actual, &predicted).unwrap(), 1.0, 1e-10);
+
+        let predicted2 = vec![3.0, 3.0, 3.0, 3.0, 3.0];
+        assert_approx_eq(calculate_r_squared(&actual, &predicted2).unwrap(), 0.0, 1e-10);
+
+        assert_eq!(
+            calculate_r_squared(&actual, &predicted2[..4]).unwrap_err(),
+            TimeSeriesError::LengthMismatch { left: 5, right: 4 }
+        );
+        assert_eq!(calculate_r_squared::<f64>(&[], &[]).unwrap_err(), TimeSeriesError::insufficient_data(1, 0));
+    }
+
+    #[test]
+    fn test_metricas_de_erro() {
+        let actual = vec![2.0, 4.0, 5.0, 10.0];
+        let predicted = vec![1.0, 5.0, 5.0, 8.0];
+
+        assert_approx_eq(evaluate(&actual, &predicted, ErrorMetric::Mse).unwrap(), 1.5, 1e-10);
+        assert_approx_eq(evaluate(&actual, &predicted, ErrorMetric::Rmse).unwrap(), 1.5_f64.sqrt(), 1e-10);
+        assert_approx_eq(evaluate(&actual, &predicted, ErrorMetric::Mae).unwrap(), 1.0, 1e-10);
+        assert_approx_eq(evaluate(&actual, &predicted, ErrorMetric::Mape).unwrap(), 23.75, 1e-10);
+        let smape = (2.0 / 3.0 + 2.0 / 9.0 + 0.0 + 4.0 / 18.0) / 4.0 * 100.0;
+        assert_approx_eq(evaluate(&actual, &predicted, ErrorMetric::Smape).unwrap(), smape, 1e-10);
+    }
+
+    #[test]
+    fn test_evaluate_all_e_erros() {
+        let actual = vec![0.0, 2.0, 4.0];
+        let predicted = vec![1.0, 2.0, 3.0];
+
+        let report = evaluate_all(&actual, &predicted).unwrap();
+        assert_eq!(report.mape, None);
+        assert!(evaluate(&actual, &predicted, ErrorMetric::Mape).is_err());
+        assert_eq!(report.clone(), report);
+        assert_approx_eq(report.mae, 2.0 / 3.0, 1e-10);
+
+        assert!(evaluate_all::<f64>(&[], &[]).is_err());
+        assert!(evaluate(&[1.0], &[1.0, 2.0], ErrorMetric::Mse).is_err());
+
+        let table = report.to_string();
+        assert_eq!(table.lines().count(), 7);
+        assert!(table.contains("MAPE              n/d"));
+    }
+}
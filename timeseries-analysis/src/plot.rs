@@ -0,0 +1,1460 @@
+//! Gráficos em texto (ASCII, sparklines, boxplot) e SVG
+//!
+//! As funções `*_string` devolvem o gráfico pronto; as versões sem o sufixo imprimem no
+//! terminal e exigem a feature `std`. Os mesmos gráficos estão disponíveis como
+//! componentes [`crate::render::Renderable`] em [`crate::render`].
+//!
+//! ```
+//! use timeseries_analysis::plot::sparkline;
+//!
+//! assert_eq!(sparkline(&[1.0, 2.0, 3.0, 4.0]).unwrap().chars().count(), 4);
+//! ```
+
+use crate::*;
+
+/// Marcadores usados pelo gráfico ASCII
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlotMarkers {
+    /// Marcador do valor real
+    pub actual: char,
+    /// Marcador do valor previsto
+    pub predicted: char,
+    /// Marcador quando real e previsto se sobrepõem
+    pub overlap: char,
+}
+
+impl Default for PlotMarkers {
+    fn default() -> Self {
+        PlotMarkers {
+            actual: 'o',
+            predicted: 'x',
+            overlap: '●',
+        }
+    }
+}
+
+/// Configuração do gráfico ASCII
+///
+/// O padrão reproduz o layout de [`ascii_plot`]: 10 linhas, rótulos com uma casa
+/// decimal, legenda visível e até 60 colunas (30 pontos). Séries mais longas que
+/// `width / 2` pontos são agrupadas em blocos pela média.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlotConfig {
+    /// Largura máxima da área de pontos, em colunas (cada ponto ocupa 2)
+    pub width: usize,
+    /// Número de linhas do gráfico
+    pub height: usize,
+    /// Exibe a legenda ao final
+    pub show_legend: bool,
+    /// Casas decimais dos rótulos do eixo y
+    pub y_label_precision: usize,
+    /// Marcadores dos pontos
+    pub markers: PlotMarkers,
+    /// Colore os marcadores com códigos ANSI (real em verde, previsto em amarelo,
+    /// sobrepostos em ciano). Veja [`terminal_supports_color`].
+    pub color: bool,
+    /// Idioma da legenda e do rótulo do eixo x
+    pub language: Language,
+}
+
+impl Default for PlotConfig {
+    fn default() -> Self {
+        PlotConfig {
+            width: 60,
+            height: 10,
+            show_legend: true,
+            y_label_precision: 1,
+            markers: PlotMarkers::default(),
+            color: false,
+            language: Language::Pt,
+        }
+    }
+}
+
+const ANSI_RESET: &str = "\x1b[0m";
+const ANSI_GREEN: &str = "\x1b[32m";
+const ANSI_YELLOW: &str = "\x1b[33m";
+const ANSI_CYAN: &str = "\x1b[36m";
+
+/// Escreve `marker`, envolvido pelo código de cor ANSI quando `color` está ativo
+fn push_marker(out: &mut String, marker: char, ansi: &str, color: bool) {
+    if color {
+        out.push_str(ansi);
+        out.push(marker);
+        out.push_str(ANSI_RESET);
+    } else {
+        out.push(marker);
+    }
+}
+
+/// Indica se a saída padrão aceita cores ANSI
+///
+/// Falso quando a variável `NO_COLOR` está definida ou quando a saída padrão não é
+/// um terminal (redirecionada para arquivo ou pipe).
+#[cfg(feature = "std")]
+pub fn terminal_supports_color() -> bool {
+    use std::io::IsTerminal;
+
+    std::env::var_os("NO_COLOR").is_none() && std::io::stdout().is_terminal()
+}
+
+/// Gera a visualização ASCII art da série temporal e previsões como texto
+///
+/// Retorna exatamente o que [`ascii_plot`] imprime. Dados vazios, tamanhos diferentes
+/// ou intervalo de valores nulo resultam em erro.
+pub fn ascii_plot_string(actual: &[f64], predicted: &[f64], title: &str) -> Result<String, TimeSeriesError> {
+    ascii_plot_with_config(actual, predicted, title, &PlotConfig::default())
+}
+
+/// Gera o gráfico ASCII com dimensões e marcadores configuráveis
+///
+/// Quando a série tem mais pontos do que cabem em `config.width`, valores consecutivos
+/// são agrupados pela média e o eixo x mostra o primeiro período de cada grupo.
+pub fn ascii_plot_with_config(
+    actual: &[f64],
+    predicted: &[f64],
+    title: &str,
+    config: &PlotConfig,
+) -> Result<String, TimeSeriesError> {
+    render_ascii_plot(actual, predicted, None, title, config)
+}
+
+/// Gera o gráfico ASCII usando `labels` no eixo x no lugar dos números dos períodos
+///
+/// Os rótulos são amostrados a cada k posições para não se sobreporem; `labels` deve
+/// ter um rótulo por ponto.
+pub fn ascii_plot_labeled_string(
+    actual: &[f64],
+    predicted: &[f64],
+    labels: &[String],
+    title: &str,
+) -> Result<String, TimeSeriesError> {
+    if labels.len() != actual.len() {
+        return Err(TimeSeriesError::LengthMismatch {
+            left: actual.len(),
+            right: labels.len(),
+        });
+    }
+    render_ascii_plot(actual, predicted, Some(labels), title, &PlotConfig::default())
+}
+
+fn render_ascii_plot(
+    actual: &[f64],
+    predicted: &[f64],
+    labels: Option<&[String]>,
+    title: &str,
+    config: &PlotConfig,
+) -> Result<String, TimeSeriesError> {
+    if actual.is_empty() {
+        return Err(TimeSeriesError::insufficient_data(1, 0));
+    }
+    if actual.len() != predicted.len() {
+        return Err(TimeSeriesError::LengthMismatch {
+            left: actual.len(),
+            right: predicted.len(),
+        });
+    }
+    validate_plot_config(config)?;
+
+    let starts = bin_starts(actual.len(), config.width / 2);
+    let actual = bin_means(actual, &starts);
+    let predicted = bin_means(predicted, &starts);
+
+    let all_values: Vec<f64> = actual.iter().chain(predicted.iter()).cloned().collect();
+    let min_val = all_values.iter().fold(f64::INFINITY, |a, &b| a.min(b));
+    let max_val = all_values.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
+    let range = max_val - min_val;
+
+    validate_plot_range(min_val, max_val)?;
+
+    let axis: Vec<(usize, String)> = match labels {
+        None => starts.iter().enumerate().map(|(slot, start)| (slot, (start + 1).to_string())).collect(),
+        Some(labels) => {
+            // Cada ponto ocupa 2 colunas: um rótulo de c caracteres precisa de
+            // ceil((c + 1) / 2) slots para não encostar no próximo
+            let longest = starts.iter().map(|&s| labels[s].chars().count()).max().unwrap_or(0);
+            let step = (longest + 1).div_ceil(2);
+            starts
+                .iter()
+                .enumerate()
+                .filter(|(slot, _)| slot % step == 0)
+                .map(|(slot, &start)| (slot, labels[start].clone()))
+                .collect()
+        }
+    };
+
+    let mut out = String::new();
+    write_ascii_plot(&mut out, &actual, &predicted, &axis, title, min_val, range, config)
+        .expect("escrita em String não falha");
+    Ok(out)
+}
+
+/// Rejeita amplitudes desprezíveis frente à magnitude dos valores (veja [`approx_zero`])
+fn validate_plot_range(min_val: f64, max_val: f64) -> Result<(), TimeSeriesError> {
+    if approx_zero(max_val - min_val, min_val.abs().max(max_val.abs())) {
+        return Err(TimeSeriesError::new(
+            LocalizedText::new(
+                "Intervalo de dados muito pequeno para plotagem",
+                "Data range too small to plot",
+            ),
+        ));
+    }
+    Ok(())
+}
+
+pub(crate) fn validate_plot_config(config: &PlotConfig) -> Result<(), TimeSeriesError> {
+    if config.height == 0 {
+        return Err(TimeSeriesError::invalid_parameter(
+            "height",
+            LocalizedText::new("deve ser maior que zero", "must be greater than zero"),
+        ));
+    }
+    if config.width < 2 {
+        return Err(TimeSeriesError::invalid_parameter(
+            "width",
+            LocalizedText::new("deve ser pelo menos 2", "must be at least 2"),
+        ));
+    }
+    Ok(())
+}
+
+/// Índices iniciais dos blocos ao agrupar `n` pontos em no máximo `max_points`
+fn bin_starts(n: usize, max_points: usize) -> Vec<usize> {
+    if n <= max_points {
+        return (0..n).collect();
+    }
+    (0..max_points).map(|k| k * n / max_points).collect()
+}
+
+/// Média de `values` em cada bloco iniciado em `starts`
+fn bin_means(values: &[f64], starts: &[usize]) -> Vec<f64> {
+    starts
+        .iter()
+        .enumerate()
+        .map(|(k, &start)| {
+            let end = starts.get(k + 1).copied().unwrap_or(values.len());
+            values[start..end].iter().sum::<f64>() / (end - start) as f64
+        })
+        .collect()
+}
+
+/// Rótulos do eixo y, de cima para baixo, e a largura da coluna que os contém
+fn y_axis_labels(min_val: f64, range: f64, config: &PlotConfig) -> (Vec<String>, usize) {
+    let height = config.height;
+    let labels: Vec<String> = (0..height)
+        .rev()
+        .map(|row| {
+            let threshold = min_val + (range * (row as f64) / (height as f64));
+            format!("{:.*}", config.y_label_precision, threshold)
+        })
+        .collect();
+    let width = labels.iter().map(|l| l.chars().count()).max().unwrap_or(0).max(8);
+    (labels, width)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn write_ascii_plot(
+    out: &mut String,
+    actual: &[f64],
+    predicted: &[f64],
+    axis: &[(usize, String)],
+    title: &str,
+    min_val: f64,
+    range: f64,
+    config: &PlotConfig,
+) -> fmt::Result {
+    use core::fmt::Write;
+
+    let height = config.height;
+    let width = actual.len() * 2;
+    let markers = config.markers;
+
+    let (y_labels, label_width) = y_axis_labels(min_val, range, config);
+
+    writeln!(out, "\n{}", title)?;
+    writeln!(out, "{}", "-".repeat(width.min(config.width) + label_width + 4))?;
+
+    for (row, label) in (0..height).rev().zip(y_labels.iter()) {
+        let threshold = min_val + (range * (row as f64) / (height as f64));
+
+        write!(out, "{:>w$} | ", label, w = label_width)?;
+
+        for i in 0..actual.len() {
+            let is_actual = actual[i] >= threshold;
+            let is_predicted = predicted[i] >= threshold;
+
+            if is_actual && is_predicted {
+                push_marker(out, markers.overlap, ANSI_CYAN, config.color);
+            } else if is_actual {
+                push_marker(out, markers.actual, ANSI_GREEN, config.color);
+            } else if is_predicted {
+                push_marker(out, markers.predicted, ANSI_YELLOW, config.color);
+            } else {
+                out.push(' ');
+            }
+
+            if i < actual.len() - 1 {
+                out.push(' ');
+            }
+        }
+        out.push('\n');
+    }
+
+    writeln!(out, "{:w$} |{}", "", "-".repeat(width.min(config.width) + 2), w = label_width)?;
+
+    write_x_axis(out, label_width + 3, axis.iter().map(|(slot, label)| (*slot, label.as_str())))?;
+    let text = PlotText::for_language(config.language);
+    writeln!(out, "{:w$}{}", "", text.axis, w = label_width + 2)?;
+
+    if config.show_legend {
+        writeln!(out, "\n{}", text.legend)?;
+        out.push_str("  ");
+        push_marker(out, markers.actual, ANSI_GREEN, config.color);
+        writeln!(out, " = {}", text.actual)?;
+        out.push_str("  ");
+        push_marker(out, markers.predicted, ANSI_YELLOW, config.color);
+        writeln!(out, " = {}", text.predicted)?;
+        out.push_str("  ");
+        push_marker(out, markers.overlap, ANSI_CYAN, config.color);
+        writeln!(out, " = {}", text.overlap)?;
+    }
+    Ok(())
+}
+
+/// Textos fixos dos gráficos configuráveis por [`PlotConfig::language`]
+struct PlotText {
+    axis: &'static str,
+    legend: &'static str,
+    actual: &'static str,
+    predicted: &'static str,
+    overlap: &'static str,
+    fitted: &'static str,
+    fitted_overlap: &'static str,
+    overlapping_series: &'static str,
+    forecast: &'static str,
+    interval: &'static str,
+    horizon_start: &'static str,
+}
+
+impl PlotText {
+    fn for_language(language: Language) -> &'static PlotText {
+        match language {
+            Language::Pt => &PlotText {
+                axis: "Periodo",
+                legend: "Legenda:",
+                actual: "Valor Real",
+                predicted: "Valor Previsto",
+                overlap: "Real e Previsto (sobrepostos)",
+                fitted: "Valor Ajustado",
+                fitted_overlap: "Real e Ajustado (sobrepostos)",
+                overlapping_series: "Series sobrepostas",
+                forecast: "Previsao",
+                interval: "Intervalo de previsao",
+                horizon_start: "Inicio do horizonte de previsao",
+            },
+            Language::En => &PlotText {
+                axis: "Period",
+                legend: "Legend:",
+                actual: "Actual",
+                predicted: "Predicted",
+                overlap: "Actual and Predicted (overlapping)",
+                fitted: "Fitted",
+                fitted_overlap: "Actual and Fitted (overlapping)",
+                overlapping_series: "Overlapping series",
+                forecast: "Forecast",
+                interval: "Prediction interval",
+                horizon_start: "Start of forecast horizon",
+            },
+        }
+    }
+}
+
+/// Marcadores atribuídos, em ordem, às séries de [`ascii_multi_plot`]
+pub const MULTI_PLOT_MARKERS: [char; 6] = ['o', 'x', '+', '*', '#', '@'];
+
+const MULTI_PLOT_COLORS: [&str; 6] = [ANSI_GREEN, ANSI_YELLOW, "\x1b[34m", "\x1b[35m", "\x1b[31m", "\x1b[37m"];
+
+/// Gera um gráfico ASCII com até 6 séries nomeadas sobrepostas
+///
+/// Cada série recebe um marcador de [`MULTI_PLOT_MARKERS`] e é desenhada como pontos
+/// (não como colunas, como em [`ascii_plot_string`]); células ocupadas por mais de
+/// uma série usam `config.markers.overlap`. A escala do eixo y cobre todas as séries,
+/// que devem ter o mesmo tamanho.
+pub fn ascii_multi_plot(series: &[(&str, &[f64])], title: &str, config: &PlotConfig) -> Result<String, TimeSeriesError> {
+    if series.is_empty() || series.len() > MULTI_PLOT_MARKERS.len() {
+        return Err(TimeSeriesError::invalid_parameter(
+            "series",
+            LocalizedText::new(
+                format!("informe de 1 a {} séries, recebido(s) {}", MULTI_PLOT_MARKERS.len(), series.len()),
+                format!("give 1 to {} series, received {}", MULTI_PLOT_MARKERS.len(), series.len()),
+            ),
+        ));
+    }
+    let n = series[0].1.len();
+    if n == 0 {
+        return Err(TimeSeriesError::insufficient_data(1, 0));
+    }
+    if let Some((_, data)) = series.iter().find(|(_, data)| data.len() != n) {
+        return Err(TimeSeriesError::LengthMismatch { left: n, right: data.len() });
+    }
+    validate_plot_config(config)?;
+
+    let starts = bin_starts(n, config.width / 2);
+    let binned: Vec<Vec<f64>> = series.iter().map(|(_, data)| bin_means(data, &starts)).collect();
+
+    let values = binned.iter().flatten();
+    let min_val = values.clone().fold(f64::INFINITY, |a, &b| a.min(b));
+    let max_val = values.fold(f64::NEG_INFINITY, |a, &b| a.max(b));
+    let range = max_val - min_val;
+
+    validate_plot_range(min_val, max_val)?;
+
+    // Cada ponto cai na linha cujo limiar inferior é o maior que não o excede
+    let height = config.height;
+    let row_of = |value: f64| ((((value - min_val) / range) * height as f64).floor() as usize).min(height - 1);
+    let mut grid: Vec<Vec<Option<usize>>> = vec![vec![None; starts.len()]; height];
+    let mut shared = vec![vec![false; starts.len()]; height];
+    for (s, values) in binned.iter().enumerate() {
+        for (i, &value) in values.iter().enumerate() {
+            let row = row_of(value);
+            match grid[row][i] {
+                None => grid[row][i] = Some(s),
+                Some(other) if other != s => shared[row][i] = true,
+                Some(_) => {}
+            }
+        }
+    }
+
+    let axis: Vec<(usize, String)> = starts.iter().enumerate().map(|(slot, start)| (slot, (start + 1).to_string())).collect();
+    let mut out = String::new();
+    write_multi_plot(&mut out, series, &grid, &shared, &axis, title, min_val, range, config)
+        .expect("escrita em String não falha");
+    Ok(out)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn write_multi_plot(
+    out: &mut String,
+    series: &[(&str, &[f64])],
+    grid: &[Vec<Option<usize>>],
+    shared: &[Vec<bool>],
+    axis: &[(usize, String)],
+    title: &str,
+    min_val: f64,
+    range: f64,
+    config: &PlotConfig,
+) -> fmt::Result {
+    use core::fmt::Write;
+
+    let points = axis.len();
+    let width = points * 2;
+    let (y_labels, label_width) = y_axis_labels(min_val, range, config);
+
+    writeln!(out, "\n{}", title)?;
+    writeln!(out, "{}", "-".repeat(width.min(config.width) + label_width + 4))?;
+
+    for (row, label) in (0..config.height).rev().zip(y_labels.iter()) {
+        write!(out, "{:>w$} | ", label, w = label_width)?;
+        for i in 0..points {
+            match grid[row][i] {
+                _ if shared[row][i] => push_marker(out, config.markers.overlap, ANSI_CYAN, config.color),
+                Some(s) => push_marker(out, MULTI_PLOT_MARKERS[s], MULTI_PLOT_COLORS[s], config.color),
+                None => out.push(' '),
+            }
+            if i < points - 1 {
+                out.push(' ');
+            }
+        }
+        out.push('\n');
+    }
+
+    writeln!(out, "{:w$} |{}", "", "-".repeat(width.min(config.width) + 2), w = label_width)?;
+    write_x_axis(out, label_width + 3, axis.iter().map(|(slot, label)| (*slot, label.as_str())))?;
+    let text = PlotText::for_language(config.language);
+    writeln!(out, "{:w$}{}", "", text.axis, w = label_width + 2)?;
+
+    if config.show_legend {
+        writeln!(out, "\n{}", text.legend)?;
+        for (s, (name, _)) in series.iter().enumerate() {
+            out.push_str("  ");
+            push_marker(out, MULTI_PLOT_MARKERS[s], MULTI_PLOT_COLORS[s], config.color);
+            writeln!(out, " = {}", name)?;
+        }
+        out.push_str("  ");
+        push_marker(out, config.markers.overlap, ANSI_CYAN, config.color);
+        writeln!(out, " = {}", text.overlapping_series)?;
+    }
+    Ok(())
+}
+
+/// Escreve a linha de rótulos do eixo x, com cada rótulo na coluna do seu slot
+///
+/// Rótulos que colidiriam com o anterior são omitidos.
+fn write_x_axis<'a>(
+    out: &mut String,
+    origin: usize,
+    labels: impl Iterator<Item = (usize, &'a str)>,
+) -> fmt::Result {
+    use core::fmt::Write;
+
+    let mut axis: Vec<char> = vec![' '; origin];
+    let mut first = true;
+    for (slot, label) in labels {
+        let column = origin + 2 * slot;
+        if column < axis.len() + usize::from(!first) {
+            continue;
+        }
+        axis.resize(column, ' ');
+        axis.extend(label.chars());
+        first = false;
+    }
+    let axis: String = axis.into_iter().collect();
+    writeln!(out, "{}", axis.trim_end())
+}
+
+/// Marcador das previsões futuras em [`ascii_plot_forecast`]
+pub const FORECAST_MARKER: char = '*';
+
+/// Gera o gráfico ASCII do histórico, do ajuste e do horizonte de previsão
+///
+/// O histórico é desenhado com `o`, os valores ajustados com `x` e as previsões com
+/// [`FORECAST_MARKER`], à direita de uma coluna separadora `:` que marca a fronteira
+/// entre passado e futuro. A escala do eixo y inclui as previsões. Usa a configuração
+/// padrão; veja [`ascii_plot_forecast_with_config`].
+pub fn ascii_plot_forecast_string(
+    actual: &[f64],
+    result: &LinearRegressionResult,
+    forecasts: &[f64],
+    title: &str,
+) -> Result<String, TimeSeriesError> {
+    ascii_plot_forecast_with_config(actual, result, forecasts, title, &PlotConfig::default())
+}
+
+/// [`ascii_plot_forecast_string`] com configuração explícita
+///
+/// De `config` são usados a altura, os rótulos do eixo y, a legenda, os marcadores, as
+/// cores e o idioma; cada ponto ocupa uma coluna, sem agrupamento por `width`.
+pub fn ascii_plot_forecast_with_config(
+    actual: &[f64],
+    result: &LinearRegressionResult,
+    forecasts: &[f64],
+    title: &str,
+    config: &PlotConfig,
+) -> Result<String, TimeSeriesError> {
+    validate_plot_config(config)?;
+    let fitted = &result.predictions;
+    if actual.is_empty() {
+        return Err(TimeSeriesError::insufficient_data(1, 0));
+    }
+    if actual.len() != fitted.len() {
+        return Err(TimeSeriesError::LengthMismatch {
+            left: actual.len(),
+            right: fitted.len(),
+        });
+    }
+
+    let values = actual.iter().chain(fitted.iter()).chain(forecasts.iter());
+    let min_val = values.clone().fold(f64::INFINITY, |a, &b| a.min(b));
+    let max_val = values.fold(f64::NEG_INFINITY, |a, &b| a.max(b));
+    let range = max_val - min_val;
+
+    validate_plot_range(min_val, max_val)?;
+
+    let mut out = String::new();
+    write_forecast_plot(&mut out, actual, fitted, forecasts, title, min_val, range, config)
+        .expect("escrita em String não falha");
+    Ok(out)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn write_forecast_plot(
+    out: &mut String,
+    actual: &[f64],
+    fitted: &[f64],
+    forecasts: &[f64],
+    title: &str,
+    min_val: f64,
+    range: f64,
+    config: &PlotConfig,
+) -> fmt::Result {
+    use core::fmt::Write;
+
+    let markers = config.markers;
+    let height = config.height;
+    let n = actual.len();
+    // Histórico, coluna separadora e horizonte de previsão
+    let slots = n + 1 + forecasts.len();
+    let width = slots * 2;
+    let (y_labels, label_width) = y_axis_labels(min_val, range, config);
+
+    writeln!(out, "\n{}", title)?;
+    writeln!(out, "{}", "-".repeat(width + label_width + 4))?;
+
+    for (row, label) in (0..height).rev().zip(y_labels.iter()) {
+        let threshold = min_val + (range * (row as f64) / (height as f64));
+
+        write!(out, "{:>w$} | ", label, w = label_width)?;
+
+        for i in 0..n {
+            let is_actual = actual[i] >= threshold;
+            let is_fitted = fitted[i] >= threshold;
+
+            match (is_actual, is_fitted) {
+                (true, true) => push_marker(out, markers.overlap, ANSI_CYAN, config.color),
+                (true, false) => push_marker(out, markers.actual, ANSI_GREEN, config.color),
+                (false, true) => push_marker(out, markers.predicted, ANSI_YELLOW, config.color),
+                (false, false) => out.push(' '),
+            }
+            out.push(' ');
+        }
+        out.push(':');
+        for &forecast in forecasts {
+            out.push(' ');
+            if forecast >= threshold {
+                push_marker(out, FORECAST_MARKER, ANSI_YELLOW, config.color);
+            } else {
+                out.push(' ');
+            }
+        }
+        out.push('\n');
+    }
+
+    writeln!(out, "{:w$} |{}", "", "-".repeat(width + 2), w = label_width)?;
+    let history = (0..n).map(|i| (i, (i + 1).to_string()));
+    let future = (0..forecasts.len()).map(|j| (n + 1 + j, (n + 1 + j).to_string()));
+    let axis: Vec<(usize, String)> = history.chain(future).collect();
+    write_x_axis(out, label_width + 3, axis.iter().map(|(slot, label)| (*slot, label.as_str())))?;
+    let text = PlotText::for_language(config.language);
+    writeln!(out, "{:w$}{}", "", text.axis, w = label_width + 2)?;
+
+    if config.show_legend {
+        writeln!(out, "\n{}", text.legend)?;
+        out.push_str("  ");
+        push_marker(out, markers.actual, ANSI_GREEN, config.color);
+        writeln!(out, " = {}", text.actual)?;
+        out.push_str("  ");
+        push_marker(out, markers.predicted, ANSI_YELLOW, config.color);
+        writeln!(out, " = {}", text.fitted)?;
+        out.push_str("  ");
+        push_marker(out, markers.overlap, ANSI_CYAN, config.color);
+        writeln!(out, " = {}", text.fitted_overlap)?;
+        out.push_str("  ");
+        push_marker(out, FORECAST_MARKER, ANSI_YELLOW, config.color);
+        writeln!(out, " = {}", text.forecast)?;
+        writeln!(out, "  : = {}", text.horizon_start)?;
+    }
+    Ok(())
+}
+
+/// Marcador da faixa do intervalo de previsão em [`ascii_plot_with_band`]
+pub const BAND_MARKER: char = '·';
+
+/// Gera o gráfico ASCII do histórico seguido das previsões com o intervalo de previsão
+///
+/// O histórico é desenhado como pontos com `config.markers.actual`; à direita da coluna
+/// separadora `:`, cada previsão aparece com [`FORECAST_MARKER`] e as linhas entre
+/// `lower` e `upper` são preenchidas com [`BAND_MARKER`]. A escala do eixo y inclui os
+/// extremos da faixa. Cada ponto ocupa uma coluna, como em [`ascii_plot_forecast_string`];
+/// de `config` são usados a altura, os rótulos, a legenda, os marcadores e as cores.
+pub fn ascii_plot_with_band(
+    actual: &[f64],
+    forecast: &[ForecastPoint],
+    title: &str,
+    config: &PlotConfig,
+) -> Result<String, TimeSeriesError> {
+    if actual.is_empty() {
+        return Err(TimeSeriesError::insufficient_data(1, 0));
+    }
+    validate_plot_config(config)?;
+    if let Some(point) = forecast.iter().find(|p| !(p.lower <= p.value && p.value <= p.upper)) {
+        return Err(TimeSeriesError::invalid_parameter(
+            "forecast",
+            LocalizedText::new(
+                format!("período {}: a previsão deve estar entre lower e upper", point.period_number()),
+                format!("period {}: the forecast must lie between lower and upper", point.period_number()),
+            ),
+        ));
+    }
+
+    let values = actual
+        .iter()
+        .copied()
+        .chain(forecast.iter().flat_map(|p| [p.lower, p.value, p.upper]));
+    let min_val = values.clone().fold(f64::INFINITY, f64::min);
+    let max_val = values.fold(f64::NEG_INFINITY, f64::max);
+    let range = max_val - min_val;
+
+    validate_plot_range(min_val, max_val)?;
+
+    let mut out = String::new();
+    write_band_plot(&mut out, actual, forecast, title, min_val, range, config)
+        .expect("escrita em String não falha");
+    Ok(out)
+}
+
+fn write_band_plot(
+    out: &mut String,
+    actual: &[f64],
+    forecast: &[ForecastPoint],
+    title: &str,
+    min_val: f64,
+    range: f64,
+    config: &PlotConfig,
+) -> fmt::Result {
+    use core::fmt::Write;
+
+    let height = config.height;
+    let n = actual.len();
+    let width = (n + 1 + forecast.len()) * 2;
+    let (y_labels, label_width) = y_axis_labels(min_val, range, config);
+    // Cada valor cai na linha cujo limiar inferior é o maior que não o excede
+    let row_of = |value: f64| ((((value - min_val) / range) * height as f64).floor() as usize).min(height - 1);
+
+    writeln!(out, "\n{}", title)?;
+    writeln!(out, "{}", "-".repeat(width + label_width + 4))?;
+
+    for (row, label) in (0..height).rev().zip(y_labels.iter()) {
+        write!(out, "{:>w$} | ", label, w = label_width)?;
+        for &value in actual {
+            if row_of(value) == row {
+                push_marker(out, config.markers.actual, ANSI_GREEN, config.color);
+            } else {
+                out.push(' ');
+            }
+            out.push(' ');
+        }
+        out.push(':');
+        for point in forecast {
+            out.push(' ');
+            if row_of(point.value) == row {
+                push_marker(out, FORECAST_MARKER, ANSI_YELLOW, config.color);
+            } else if (row_of(point.lower)..=row_of(point.upper)).contains(&row) {
+                out.push(BAND_MARKER);
+            } else {
+                out.push(' ');
+            }
+        }
+        out.push('\n');
+    }
+
+    writeln!(out, "{:w$} |{}", "", "-".repeat(width + 2), w = label_width)?;
+    let history = (0..n).map(|i| (i, (i + 1).to_string()));
+    let future = forecast.iter().enumerate().map(|(j, p)| (n + 1 + j, p.period_number().to_string()));
+    let axis: Vec<(usize, String)> = history.chain(future).collect();
+    write_x_axis(out, label_width + 3, axis.iter().map(|(slot, label)| (*slot, label.as_str())))?;
+    let text = PlotText::for_language(config.language);
+    writeln!(out, "{:w$}{}", "", text.axis, w = label_width + 2)?;
+
+    if config.show_legend {
+        writeln!(out, "\n{}", text.legend)?;
+        out.push_str("  ");
+        push_marker(out, config.markers.actual, ANSI_GREEN, config.color);
+        writeln!(out, " = {}", text.actual)?;
+        out.push_str("  ");
+        push_marker(out, FORECAST_MARKER, ANSI_YELLOW, config.color);
+        writeln!(out, " = {}", text.forecast)?;
+        writeln!(out, "  {} = {}", BAND_MARKER, text.interval)?;
+        writeln!(out, "  : = {}", text.horizon_start)?;
+    }
+    Ok(())
+}
+
+/// Largura máxima, em caracteres, da barra mais longa do histograma
+const HISTOGRAM_BAR_WIDTH: usize = 40;
+
+/// Gera um histograma ASCII horizontal com `bins` intervalos de mesma largura
+///
+/// Os intervalos vão do mínimo ao máximo dos dados (o último inclui o máximo) e as
+/// barras são escaladas pelo intervalo mais populoso. Útil para inspecionar a
+/// distribuição dos resíduos de uma regressão.
+pub fn ascii_histogram(data: &[f64], bins: usize) -> Result<String, TimeSeriesError> {
+    use core::fmt::Write;
+
+    if bins == 0 {
+        return Err(TimeSeriesError::invalid_parameter(
+            "bins",
+            LocalizedText::new("deve ser maior que zero", "must be greater than zero"),
+        ));
+    }
+    if data.is_empty() {
+        return Err(TimeSeriesError::insufficient_data(1, 0));
+    }
+    validate_finite(data)?;
+
+    let min_val = data.iter().fold(f64::INFINITY, |a, &b| a.min(b));
+    let max_val = data.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
+    let range = max_val - min_val;
+
+    validate_plot_range(min_val, max_val)?;
+
+    let bin_width = range / bins as f64;
+    let mut counts = vec![0usize; bins];
+    for &value in data {
+        // `as usize` satura, então valores extremos caem no primeiro ou último intervalo
+        let index = (((value - min_val) / bin_width).floor() as usize).min(bins - 1);
+        counts[index] += 1;
+    }
+    let largest = counts.iter().copied().max().unwrap_or(0);
+
+    let mut out = String::new();
+    for (i, &count) in counts.iter().enumerate() {
+        let low = min_val + bin_width * i as f64;
+        let high = if i == bins - 1 { max_val } else { low + bin_width };
+        let closing = if i == bins - 1 { ']' } else { ')' };
+        let bar = count * HISTOGRAM_BAR_WIDTH / largest;
+        writeln!(
+            out,
+            "[{:>10.2}, {:>10.2}{} | {:<w$} {}",
+            low,
+            high,
+            closing,
+            "#".repeat(bar),
+            count,
+            w = HISTOGRAM_BAR_WIDTH
+        )
+        .expect("escrita em String não falha");
+    }
+    Ok(out)
+}
+
+/// Conjunto de caracteres usado por [`sparkline_with_style`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SparklineStyle {
+    /// Blocos Unicode `▁▂▃▄▅▆▇█`; valores ausentes viram espaço
+    #[default]
+    Unicode,
+    /// Caracteres `_.-~^` para terminais sem Unicode; valores ausentes viram `?`
+    Ascii,
+}
+
+impl SparklineStyle {
+    fn levels(self) -> &'static [char] {
+        match self {
+            SparklineStyle::Unicode => &['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'],
+            SparklineStyle::Ascii => &['_', '.', '-', '~', '^'],
+        }
+    }
+
+    fn missing(self) -> char {
+        match self {
+            SparklineStyle::Unicode => ' ',
+            SparklineStyle::Ascii => '?',
+        }
+    }
+}
+
+/// Resume a série numa única linha de blocos Unicode
+///
+/// Equivale a [`sparkline_with_style`] com [`SparklineStyle::Unicode`].
+pub fn sparkline(data: &[f64]) -> Result<String, TimeSeriesError> {
+    sparkline_with_style(data, SparklineStyle::Unicode)
+}
+
+/// Resume a série numa única linha, um caractere por valor
+///
+/// A altura de cada caractere é proporcional à posição do valor entre o mínimo e o
+/// máximo. Séries constantes viram uma linha plana na altura média e valores não
+/// finitos (NaN, infinitos) são marcados como ausentes.
+pub fn sparkline_with_style(data: &[f64], style: SparklineStyle) -> Result<String, TimeSeriesError> {
+    if data.is_empty() {
+        return Err(TimeSeriesError::insufficient_data(1, 0));
+    }
+
+    let levels = style.levels();
+    let top = levels.len() - 1;
+    let finite = data.iter().copied().filter(|v| v.is_finite());
+    let min_val = finite.clone().fold(f64::INFINITY, f64::min);
+    let max_val = finite.fold(f64::NEG_INFINITY, f64::max);
+    let range = max_val - min_val;
+
+    Ok(data
+        .iter()
+        .map(|&value| {
+            if !value.is_finite() {
+                style.missing()
+            } else if approx_zero(range, min_val.abs().max(max_val.abs())) {
+                levels[top / 2]
+            } else {
+                levels[(((value - min_val) / range) * top as f64).round() as usize]
+            }
+        })
+        .collect())
+}
+
+/// Largura, em caracteres, da escala compartilhada do box plot
+pub(crate) const BOXPLOT_WIDTH: usize = 50;
+
+/// Gera box plots ASCII horizontais, um por série nomeada, numa escala comum
+///
+/// Cada linha mostra os bigodes (`|---`) até o ponto mais extremo dentro de 1,5·IQR,
+/// a caixa `[===]` de Q1 a Q3 com a mediana marcada por `|` e os outliers como `o`.
+/// Cada série precisa de pelo menos 5 pontos.
+pub fn ascii_boxplot(series: &[(&str, &[f64])]) -> Result<String, TimeSeriesError> {
+    use core::fmt::Write;
+
+    if series.is_empty() {
+        return Err(TimeSeriesError::invalid_parameter(
+            "series",
+            LocalizedText::new("informe pelo menos uma série", "give at least one series"),
+        ));
+    }
+
+    let mut sorted_series = Vec::with_capacity(series.len());
+    for (_, data) in series {
+        if data.len() < 5 {
+            return Err(TimeSeriesError::insufficient_data(5, data.len()));
+        }
+        validate_finite(data)?;
+        sorted_series.push(sorted_copy(data));
+    }
+
+    let min_val = sorted_series.iter().map(|s| s[0]).fold(f64::INFINITY, f64::min);
+    let max_val = sorted_series.iter().map(|s| s[s.len() - 1]).fold(f64::NEG_INFINITY, f64::max);
+    let range = max_val - min_val;
+
+    validate_plot_range(min_val, max_val)?;
+
+    let column = |value: f64| (((value - min_val) / range) * (BOXPLOT_WIDTH - 1) as f64).round() as usize;
+    let name_width = series.iter().map(|(name, _)| name.chars().count()).max().unwrap_or(0);
+
+    let mut out = String::new();
+    for ((name, _), sorted) in series.iter().zip(sorted_series.iter()) {
+        let q1 = percentile_sorted(sorted, 25.0);
+        let median = percentile_sorted(sorted, 50.0);
+        let q3 = percentile_sorted(sorted, 75.0);
+        let iqr = q3 - q1;
+        let (low_fence, high_fence) = (q1 - 1.5 * iqr, q3 + 1.5 * iqr);
+        let mut inside = sorted.iter().filter(|&&v| v >= low_fence && v <= high_fence);
+        let low_whisker = inside.next().copied().unwrap_or(q1);
+        let high_whisker = inside.next_back().copied().unwrap_or(low_whisker);
+
+        let mut row = vec![' '; BOXPLOT_WIDTH];
+        for cell in &mut row[column(low_whisker)..=column(high_whisker)] {
+            *cell = '-';
+        }
+        for cell in &mut row[column(q1)..=column(q3)] {
+            *cell = '=';
+        }
+        row[column(low_whisker)] = '|';
+        row[column(high_whisker)] = '|';
+        row[column(q1)] = '[';
+        row[column(q3)] = ']';
+        row[column(median)] = '|';
+        for &outlier in sorted.iter().filter(|&&v| v < low_fence || v > high_fence) {
+            row[column(outlier)] = 'o';
+        }
+
+        let row: String = row.into_iter().collect();
+        writeln!(out, "{:>w$}  {}", name, row.trim_end(), w = name_width).expect("escrita em String não falha");
+    }
+
+    let min_label = format!("{:.1}", min_val);
+    let max_label = format!("{:.1}", max_val);
+    let gap = BOXPLOT_WIDTH.saturating_sub(min_label.len() + max_label.len()).max(1);
+    writeln!(out, "{:w$}  {}", "", "-".repeat(BOXPLOT_WIDTH), w = name_width).expect("escrita em String não falha");
+    writeln!(out, "{:w$}  {}{}{}", "", min_label, " ".repeat(gap), max_label, w = name_width)
+        .expect("escrita em String não falha");
+    Ok(out)
+}
+
+/// Opções de [`to_svg`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SvgOptions {
+    /// Largura do documento, em pixels
+    pub width: u32,
+    /// Altura do documento, em pixels
+    pub height: u32,
+    /// Título exibido no topo (vazio para omitir)
+    pub title: String,
+    /// Cor da série observada
+    pub actual_color: String,
+    /// Cor dos valores ajustados
+    pub fitted_color: String,
+    /// Cor das previsões e da região de previsão
+    pub forecast_color: String,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        SvgOptions {
+            width: 640,
+            height: 360,
+            title: String::new(),
+            actual_color: "#2e7d32".to_string(),
+            fitted_color: "#f9a825".to_string(),
+            forecast_color: "#c62828".to_string(),
+        }
+    }
+}
+
+/// Margem, em pixels, entre a área do gráfico e as bordas do documento
+const SVG_MARGIN: f64 = 48.0;
+
+/// Escapa os caracteres especiais de XML em textos
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Exporta a série, o ajuste e as previsões como um documento SVG autocontido
+///
+/// Cada série não vazia vira um `<polyline>`; as previsões ficam à direita do
+/// histórico, sobre uma região sombreada. `fitted` deve ter o tamanho de `actual` ou
+/// ser vazio. Dados constantes geram um documento válido com a escala expandida em
+/// ±1 em torno do valor.
+pub fn to_svg(actual: &[f64], fitted: &[f64], forecasts: &[f64], options: &SvgOptions) -> Result<String, TimeSeriesError> {
+    use core::fmt::Write;
+
+    if actual.is_empty() {
+        return Err(TimeSeriesError::insufficient_data(1, 0));
+    }
+    if !fitted.is_empty() && fitted.len() != actual.len() {
+        return Err(TimeSeriesError::LengthMismatch {
+            left: actual.len(),
+            right: fitted.len(),
+        });
+    }
+    validate_finite(actual)?;
+    validate_finite(fitted)?;
+    validate_finite(forecasts)?;
+    if f64::from(options.width) <= 2.0 * SVG_MARGIN || f64::from(options.height) <= 2.0 * SVG_MARGIN {
+        return Err(TimeSeriesError::invalid_parameter(
+            "options",
+            LocalizedText::new("dimensões menores que as margens", "dimensions smaller than the margins"),
+        ));
+    }
+
+    let values = actual.iter().chain(fitted.iter()).chain(forecasts.iter());
+    let mut min_val = values.clone().fold(f64::INFINITY, |a, &b| a.min(b));
+    let mut max_val = values.fold(f64::NEG_INFINITY, |a, &b| a.max(b));
+    if approx_zero(max_val - min_val, min_val.abs().max(max_val.abs())) {
+        min_val -= 1.0;
+        max_val += 1.0;
+    }
+    let range = max_val - min_val;
+
+    let (width, height) = (f64::from(options.width), f64::from(options.height));
+    let plot_width = width - 2.0 * SVG_MARGIN;
+    let plot_height = height - 2.0 * SVG_MARGIN;
+    let total = actual.len() + forecasts.len();
+    let x_step = if total > 1 { plot_width / (total - 1) as f64 } else { 0.0 };
+    let x_at = |i: usize| SVG_MARGIN + x_step * i as f64;
+    let y_at = |v: f64| SVG_MARGIN + plot_height * (1.0 - (v - min_val) / range);
+
+    let mut out = String::new();
+    let mut svg = || -> fmt::Result {
+        writeln!(
+            out,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+            w = options.width,
+            h = options.height
+        )?;
+        writeln!(out, r#"<rect width="100%" height="100%" fill="white"/>"#)?;
+        if !options.title.is_empty() {
+            writeln!(
+                out,
+                r#"<text x="{:.1}" y="{:.1}" text-anchor="middle" font-family="sans-serif" font-size="16">{}</text>"#,
+                width / 2.0,
+                SVG_MARGIN / 2.0,
+                escape_xml(&options.title)
+            )?;
+        }
+
+        if !forecasts.is_empty() {
+            let start = x_at(actual.len() - 1);
+            writeln!(
+                out,
+                r#"<rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" fill="{}" fill-opacity="0.1"/>"#,
+                start,
+                SVG_MARGIN,
+                SVG_MARGIN + plot_width - start,
+                plot_height,
+                escape_xml(&options.forecast_color)
+            )?;
+        }
+
+        // Eixos e marcações
+        let bottom = SVG_MARGIN + plot_height;
+        writeln!(
+            out,
+            r#"<g stroke="black" stroke-width="1"><line x1="{m:.1}" y1="{m:.1}" x2="{m:.1}" y2="{b:.1}"/><line x1="{m:.1}" y1="{b:.1}" x2="{r:.1}" y2="{b:.1}"/></g>"#,
+            m = SVG_MARGIN,
+            b = bottom,
+            r = SVG_MARGIN + plot_width
+        )?;
+        writeln!(out, r#"<g font-family="sans-serif" font-size="10">"#)?;
+        for tick in 0..=4 {
+            let value = min_val + range * tick as f64 / 4.0;
+            let y = y_at(value);
+            writeln!(
+                out,
+                r#"<line x1="{:.1}" y1="{y:.1}" x2="{:.1}" y2="{y:.1}" stroke="black"/><text x="{:.1}" y="{:.1}" text-anchor="end">{:.1}</text>"#,
+                SVG_MARGIN - 4.0,
+                SVG_MARGIN,
+                SVG_MARGIN - 6.0,
+                y + 3.0,
+                value,
+                y = y
+            )?;
+        }
+        let x_ticks = total.min(10);
+        for tick in 0..x_ticks {
+            let i = if x_ticks > 1 { tick * (total - 1) / (x_ticks - 1) } else { 0 };
+            let x = x_at(i);
+            writeln!(
+                out,
+                r#"<line x1="{x:.1}" y1="{:.1}" x2="{x:.1}" y2="{:.1}" stroke="black"/><text x="{x:.1}" y="{:.1}" text-anchor="middle">{}</text>"#,
+                bottom,
+                bottom + 4.0,
+                bottom + 16.0,
+                i + 1,
+                x = x
+            )?;
+        }
+        writeln!(out, "</g>")?;
+
+        let mut polyline = |points: &mut dyn Iterator<Item = (usize, f64)>, color: &str, dashed: bool| -> fmt::Result {
+            write!(out, r#"<polyline fill="none" stroke="{}" stroke-width="2""#, escape_xml(color))?;
+            if dashed {
+                write!(out, r#" stroke-dasharray="6 4""#)?;
+            }
+            write!(out, r#" points=""#)?;
+            for (k, (i, v)) in points.enumerate() {
+                if k > 0 {
+                    out.push(' ');
+                }
+                write!(out, "{:.2},{:.2}", x_at(i), y_at(v))?;
+            }
+            writeln!(out, r#""/>"#)
+        };
+        polyline(&mut actual.iter().copied().enumerate(), &options.actual_color, false)?;
+        if !fitted.is_empty() {
+            polyline(&mut fitted.iter().copied().enumerate(), &options.fitted_color, true)?;
+        }
+        if !forecasts.is_empty() {
+            // A previsão parte do último valor observado para não deixar um vão
+            let last = (actual.len() - 1, actual[actual.len() - 1]);
+            let future = forecasts.iter().enumerate().map(|(j, &v)| (actual.len() + j, v));
+            polyline(&mut core::iter::once(last).chain(future), &options.forecast_color, false)?;
+        }
+
+        writeln!(out, "</svg>")
+    };
+    svg().expect("escrita em String não falha");
+    Ok(out)
+}
+
+/// Imprime a visualização ASCII art da série temporal e previsões
+///
+/// Em caso de dados inválidos imprime a mensagem de erro no lugar do gráfico.
+#[cfg(feature = "std")]
+pub fn ascii_plot(actual: &[f64], predicted: &[f64], title: &str) {
+    LinePlot::new(actual, predicted, title).print();
+}
+/// Imprime o gráfico ASCII do histórico, do ajuste e das previsões futuras
+///
+/// Veja [`ascii_plot_forecast_string`]. Em caso de dados inválidos imprime a mensagem
+/// de erro no lugar do gráfico.
+#[cfg(feature = "std")]
+pub fn ascii_plot_forecast(actual: &[f64], result: &LinearRegressionResult, forecasts: &[f64], title: &str) {
+    ForecastPlot::new(actual, result, forecasts, title).print();
+}
+/// Imprime o gráfico ASCII com rótulos personalizados no eixo x
+///
+/// Veja [`ascii_plot_labeled_string`]. Em caso de dados inválidos imprime a mensagem
+/// de erro no lugar do gráfico.
+#[cfg(feature = "std")]
+pub fn ascii_plot_labeled(actual: &[f64], predicted: &[f64], labels: &[String], title: &str) {
+    LabeledPlot {
+        actual,
+        predicted,
+        labels,
+        title,
+    }
+    .print();
+}
+/// Imprime o gráfico ASCII com marcadores coloridos quando o terminal permite
+///
+/// As cores são desativadas automaticamente se a saída não for um terminal ou se
+/// `NO_COLOR` estiver definida; nesse caso a saída é idêntica à de [`ascii_plot`].
+#[cfg(feature = "std")]
+pub fn ascii_plot_colored(actual: &[f64], predicted: &[f64], title: &str) {
+    let config = PlotConfig {
+        color: terminal_supports_color(),
+        ..PlotConfig::default()
+    };
+    LinePlot {
+        config,
+        ..LinePlot::new(actual, predicted, title)
+    }
+    .print();
+}
+
+#[cfg(test)]
+mod testes {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_ascii_plot_dados_invalidos() {
+        ascii_plot(&[], &[], "Vazio");
+        ascii_plot(&[1.0], &[1.0, 2.0], "Tamanhos diferentes");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_ascii_plot_dados_constantes() {
+        let data = vec![5.0, 5.0, 5.0];
+        ascii_plot(&data, &data, "Constantes");
+    }
+
+    #[test]
+    fn test_ascii_plot_string_erros() {
+        assert!(ascii_plot_string(&[], &[], "Vazio").is_err());
+        assert_eq!(
+            ascii_plot_string(&[1.0], &[1.0, 2.0], "Tamanhos").unwrap_err(),
+            TimeSeriesError::LengthMismatch { left: 1, right: 2 }
+        );
+        assert!(ascii_plot_string(&[5.0, 5.0], &[5.0, 5.0], "Constante").is_err());
+    }
+
+    #[test]
+    fn test_ascii_plot_rotulos_alinhados() {
+        let actual: Vec<f64> = (0..12).map(|i| i as f64).collect();
+        let plot = ascii_plot_string(&actual, &actual, "Longa").unwrap();
+        let axis = plot.lines().find(|l| l.trim_start().starts_with("1 ")).unwrap();
+        // Rótulos de dois dígitos que colidiriam com o anterior são omitidos
+        assert_eq!(axis, "           1 2 3 4 5 6 7 8 9 10  12");
+        let first_row = plot.lines().nth(3).unwrap();
+        assert_eq!(first_row.chars().position(|c| c == '●'), Some(31));
+        assert_eq!(axis.find("12"), Some(33));
+    }
+
+    #[test]
+    fn test_ascii_plot_with_config_agrupa_pontos() {
+        let actual: Vec<f64> = (0..100).map(|i| i as f64).collect();
+        let config = PlotConfig {
+            width: 20,
+            height: 4,
+            show_legend: false,
+            y_label_precision: 0,
+            markers: PlotMarkers { actual: '*', predicted: '+', overlap: '#' },
+            color: false,
+            language: Language::Pt,
+        };
+        let plot = ascii_plot_with_config(&actual, &actual, "Agrupado", &config).unwrap();
+        let rows: Vec<&str> = plot.lines().skip(3).take(4).collect();
+        assert!(rows.iter().all(|r| r.chars().filter(|&c| c == '#').count() <= 10));
+        assert_eq!(rows[3].chars().filter(|&c| c == '#').count(), 10);
+        assert!(!plot.contains("Legenda"));
+        assert!(plot.contains(" 91"));
+
+        let invalid = PlotConfig { height: 0, ..PlotConfig::default() };
+        assert!(matches!(
+            ascii_plot_with_config(&actual, &actual, "x", &invalid),
+            Err(TimeSeriesError::InvalidParameter { name: "height", .. })
+        ));
+    }
+
+    #[test]
+    fn test_ascii_plot_forecast_separador() {
+        let data = [1.0, 2.0, 3.0, 4.0];
+        let result = linear_regression(&data).unwrap();
+        let forecasts = predict_future(&result, 2);
+        let plot = ascii_plot_forecast_string(&data, &result, &forecasts, "Previsao").unwrap();
+
+        // 11 colunas de rótulo do eixo y, 2 por ponto do histórico
+        let separator_column = 11 + 2 * data.len();
+        let rows: Vec<Vec<char>> = plot.lines().skip(3).take(10).map(|l| l.chars().collect()).collect();
+        assert!(rows.iter().all(|r| r[separator_column] == ':'));
+        // A previsão mais alta (6.0) define o topo da escala
+        assert_eq!(rows[0][separator_column + 4], FORECAST_MARKER);
+        assert!(rows.iter().all(|r| !r[..separator_column].contains(&FORECAST_MARKER)));
+        assert!(plot.contains("  * = Previsao"));
+    }
+
+    #[test]
+    fn test_ascii_plot_labeled_amostra_rotulos() {
+        let actual = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let labels: Vec<String> = ["jan", "fev", "mar", "abr", "mai", "jun"].iter().map(|s| s.to_string()).collect();
+        let plot = ascii_plot_labeled_string(&actual, &actual, &labels, "Meses").unwrap();
+        // Rótulos de 3 caracteres ocupam 2 slots: um sim, um não
+        assert!(plot.contains("\n           jan mar mai\n"));
+
+        assert_eq!(
+            ascii_plot_labeled_string(&actual, &actual, &labels[..5], "Meses"),
+            Err(TimeSeriesError::LengthMismatch { left: 6, right: 5 })
+        );
+    }
+
+    #[test]
+    fn test_ascii_histogram() {
+        let data = [0.0, 1.0, 1.5, 2.0, 2.5, 3.0, 4.0];
+        let hist = ascii_histogram(&data, 2).unwrap();
+        let lines: Vec<&str> = hist.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("[      0.00,       2.00) | "));
+        assert!(lines[0].ends_with(" 3"));
+        assert!(lines[1].starts_with("[      2.00,       4.00] | "));
+        assert!(lines[1].contains(&format!("{} 4", "#".repeat(40))));
+
+        // Um outlier enorme apenas concentra o resto num único intervalo
+        let outlier = ascii_histogram(&[1.0, 2.0, 3.0, 1e300], 5).unwrap();
+        assert!(outlier.lines().next().unwrap().ends_with(" 3"));
+    }
+
+    #[test]
+    fn test_ascii_histogram_erros() {
+        assert!(matches!(
+            ascii_histogram(&[1.0, 2.0], 0),
+            Err(TimeSeriesError::InvalidParameter { name: "bins", .. })
+        ));
+        assert!(ascii_histogram(&[5.0, 5.0, 5.0], 3).is_err());
+        assert!(ascii_histogram(&[], 3).is_err());
+    }
+
+    #[test]
+    fn test_sparkline() {
+        let data = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, f64::NAN, 1.0];
+        assert_eq!(sparkline(&data).unwrap(), "▁▂▃▄▅▆▇█ ▁");
+        assert_eq!(sparkline_with_style(&[0.0, 1.0, 2.0, 3.0, 4.0, f64::NAN], SparklineStyle::Ascii).unwrap(), "_.-~^?");
+
+        assert_eq!(sparkline(&[3.0, 3.0, 3.0]).unwrap(), "▄▄▄");
+        assert_eq!(sparkline_with_style(&[3.0, 3.0], SparklineStyle::Ascii).unwrap(), "--");
+        assert!(sparkline(&[]).is_err());
+    }
+
+    #[test]
+    fn test_ascii_boxplot() {
+        let norte = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 49.0];
+        let sul = [10.0, 11.0, 12.0, 13.0, 14.0];
+        let plot = ascii_boxplot(&[("Norte", &norte), ("Sul", &sul)]).unwrap();
+        let lines: Vec<&str> = plot.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with("Norte  |"));
+        // 49 fica além de Q3 + 1,5·IQR e ocupa a última coluna da escala
+        assert!(lines[0].ends_with('o'));
+        assert_eq!(lines[0].chars().count(), 7 + BOXPLOT_WIDTH);
+        assert_eq!(lines[3].chars().count(), 7 + BOXPLOT_WIDTH);
+        assert!(lines[1].starts_with("  Sul  "));
+        assert!(!lines[1].contains('o'));
+        assert!(lines[3].trim_start().starts_with("1.0"));
+        assert!(lines[3].ends_with("49.0"));
+    }
+
+    #[test]
+    fn test_ascii_boxplot_erros() {
+        assert!(ascii_boxplot(&[]).is_err());
+        assert_eq!(
+            ascii_boxplot(&[("curta", &[1.0, 2.0, 3.0])]),
+            Err(TimeSeriesError::InsufficientData { required: 5, got: 3 })
+        );
+    }
+
+    #[test]
+    fn test_ascii_plot_cores_ansi() {
+        let (actual, predicted) = ([1.0, 3.0, 2.0], [1.0, 2.0, 3.0]);
+        let plain = ascii_plot_with_config(&actual, &predicted, "Teste", &PlotConfig::default()).unwrap();
+        assert!(!plain.contains('\x1b'));
+        assert_eq!(plain, ascii_plot_string(&actual, &predicted, "Teste").unwrap());
+
+        let config = PlotConfig { color: true, ..PlotConfig::default() };
+        let colored = ascii_plot_with_config(&actual, &predicted, "Teste", &config).unwrap();
+        assert!(colored.contains("\x1b[32mo\x1b[0m"));
+        assert!(colored.contains("\x1b[33mx\x1b[0m"));
+        assert!(colored.contains("\x1b[36m●\x1b[0m"));
+        // Sem os códigos ANSI o gráfico é o mesmo
+        assert_eq!(colored.replace("\x1b[32m", "").replace("\x1b[33m", "").replace("\x1b[36m", "").replace("\x1b[0m", ""), plain);
+    }
+
+    #[test]
+    fn test_ascii_multi_plot() {
+        let raw = [1.0, 4.0, 2.0, 5.0];
+        let smooth = [1.0, 2.5, 3.0, 3.5];
+        let fit = [1.5, 2.5, 3.5, 4.5];
+        let config = PlotConfig { height: 4, ..PlotConfig::default() };
+        // o layout completo está no snapshot `ascii_multi_plot` (tests/render.rs)
+        let plot = ascii_multi_plot(&[("Dados", &raw), ("Media movel", &smooth), ("Ajuste", &fit)], "Varias", &config).unwrap();
+        assert!(plot.contains("  o = Dados\n  x = Media movel\n  + = Ajuste\n"));
+
+        assert_eq!(
+            ascii_multi_plot(&[("a", &raw), ("b", &[1.0, 2.0])], "x", &config),
+            Err(TimeSeriesError::LengthMismatch { left: 4, right: 2 })
+        );
+        let many: Vec<(&str, &[f64])> = (0..7).map(|_| ("s", &raw[..])).collect();
+        assert!(ascii_multi_plot(&many, "x", &config).is_err());
+    }
+
+    #[test]
+    fn test_to_svg() {
+        let data = [1.0, 2.0, 4.0, 3.0];
+        let result = linear_regression(&data).unwrap();
+        let forecasts = predict_future(&result, 2);
+        let options = SvgOptions { title: "Vendas & <Previsão>".to_string(), ..SvgOptions::default() };
+        let svg = to_svg(&data, &result.predictions, &forecasts, &options).unwrap();
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert_eq!(svg.matches("<svg").count(), svg.matches("</svg>").count());
+        assert_eq!(svg.matches("<polyline").count(), 3);
+        assert!(svg.contains("Vendas &amp; &lt;Previsão&gt;"));
+        assert!(svg.contains(r#"fill-opacity="0.1""#));
+
+        // Dados constantes e sem ajuste/previsão ainda geram um documento válido
+        let flat = to_svg(&[5.0, 5.0, 5.0], &[], &[], &SvgOptions::default()).unwrap();
+        assert_eq!(flat.matches("<polyline").count(), 1);
+        assert!(!flat.contains("NaN") && !flat.contains("inf"));
+        assert!(to_svg(&[1.0, 2.0], &[1.0], &[], &SvgOptions::default()).is_err());
+    }
+
+    #[test]
+    fn test_grafico_com_faixa() {
+        let actual = [0.0, 2.0, 4.0, 6.0, 8.0, 10.0];
+        let point = |period: usize, value: f64, lower: f64, upper: f64| ForecastPoint {
+            period,
+            x: period as f64,
+            value,
+            lower,
+            upper,
+        };
+        // Faixa de 9 a 14 no primeiro período; largura zero no segundo
+        let forecast = [point(6, 12.0, 9.0, 14.0), point(7, 14.0, 14.0, 14.0)];
+        let config = PlotConfig {
+            height: 7,
+            ..PlotConfig::default()
+        };
+        let plot = ascii_plot_with_band(&actual, &forecast, "Faixa", &config).unwrap();
+        // Escala de 0 a 14 em 7 linhas de 2 unidades: a faixa ocupa as linhas 8.0 e 10.0
+        // (o layout completo está no snapshot `ascii_plot_faixa`)
+        let band_rows: Vec<&str> = plot.lines().filter(|line| line.contains('·') && line.contains('|')).collect();
+        assert_eq!(band_rows.len(), 2);
+        assert!(band_rows[0].trim_start().starts_with("10.0 |") && band_rows[1].trim_start().starts_with("8.0 |"));
+
+        let inverted = [point(6, 12.0, 13.0, 14.0)];
+        assert!(ascii_plot_with_band(&actual, &inverted, "Faixa", &config).is_err());
+    }
+
+    #[test]
+    fn test_legenda_do_grafico_em_ingles() {
+        let actual = [1.0, 2.0, 3.0];
+        let config = PlotConfig { language: Language::En, ..PlotConfig::default() };
+        let plot = ascii_plot_with_config(&actual, &actual, "Teste", &config).unwrap();
+        assert!(plot.contains("Legend:\n"));
+        assert!(plot.contains("o = Actual\n"));
+        assert!(plot.contains("Period\n"));
+        assert!(!plot.contains("Legenda"));
+
+        let default = ascii_plot_with_config(&actual, &actual, "Teste", &PlotConfig::default()).unwrap();
+        assert!(default.contains("Legenda:\n"));
+        assert!(default.contains("o = Valor Real\n"));
+    }
+}
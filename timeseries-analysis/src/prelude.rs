@@ -0,0 +1,38 @@
+//! Itens de uso comum, para importar com `use timeseries_analysis::prelude::*`
+//!
+//! O prelúdio traz os tipos centrais e as funções de entrada de cada área, mas deixa de fora
+//! nomes genéricos como `median`, `percentile`, `lag` ou `standardize`, que costumam colidir
+//! com funções do próprio código do usuário; esses continuam disponíveis pelo módulo de origem
+//! (por exemplo, [`crate::stats::median`]) ou pela raiz da crate.
+//!
+//! ```
+//! use timeseries_analysis::prelude::*;
+//!
+//! fn median(values: &[f64]) -> f64 {
+//!     values[values.len() / 2]
+//! }
+//!
+//! let data = [1.0, 2.0, 3.0, 4.0, 5.0];
+//! let result = linear_regression(&data).unwrap();
+//! let stats = calculate_descriptive_stats(&data).unwrap();
+//! assert_eq!(predict_future(&result, 1), vec![6.0]);
+//! assert_eq!(stats.median, median(&data));
+//! ```
+
+pub use crate::error::{set_message_language, ErrorKind, Language, LocalizedText, TimeSeriesError};
+pub use crate::forecast::{predict_future, ForecastPoint, TimeSeriesAnalysis};
+pub use crate::formatting::{FormatOptions, FormatWith};
+#[cfg(feature = "std")]
+pub use crate::io::load_series;
+pub use crate::io::{parse_series, ToJson};
+pub use crate::metrics::{evaluate, evaluate_all, ErrorMetric, MetricsReport};
+pub use crate::plot::{ascii_plot_string, sparkline, PlotConfig};
+pub use crate::regression::{linear_regression, LinearRegressionResult, TrendDirection};
+pub use crate::render::Renderable;
+pub use crate::report::{analyze, generate_report, AnalysisResult, ReportOptions};
+pub use crate::seasonal::{decompose, SeasonalModel};
+pub use crate::series::{fill_missing, Date, FillMethod, TimeSeries};
+pub use crate::smoothing::{exponential_moving_average, rolling_mean};
+pub use crate::stats::{calculate_descriptive_stats, DescriptiveStats};
+pub use crate::transform::{Transform, TransformPipeline};
+pub use crate::Float;
@@ -0,0 +1,2525 @@
+//! Regressão linear e modelos derivados
+//!
+//! Ajuste por mínimos quadrados ([`linear_regression`] e variantes em streaming), classificação
+//! e comparação de tendências, diagnóstico dos resíduos, regressões polinomial, múltipla e
+//! ridge e segmentação em trechos lineares.
+//!
+//! ```
+//! use timeseries_analysis::regression::{classify_trend_default, linear_regression, TrendDirection};
+//!
+//! let data = [2.0, 4.0, 6.0, 8.0];
+//! let result = linear_regression(&data).unwrap();
+//! assert_eq!(result.slope, 2.0);
+//! assert_eq!(result.predictions, vec![2.0, 4.0, 6.0, 8.0]);
+//! assert_eq!(classify_trend_default(&data).unwrap(), TrendDirection::Increasing);
+//! ```
+
+use crate::*;
+
+/// Estrutura para armazenar os resultados da regressão linear
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinearRegressionResult<T: Float = f64> {
+    pub slope: T,
+    pub intercept: T,
+    pub r_squared: T,
+    pub mse: T,
+    pub predictions: Vec<T>,
+    /// Número de observações do ajuste; as previsões e [`forecast`](Self::forecast)
+    /// numeram os períodos a partir daqui, mesmo quando `predictions` fica vazio
+    pub count: usize,
+}
+
+/// Cabeçalho do formato textual de [`LinearRegressionResult::to_model_string`]
+pub const MODEL_FORMAT_VERSION: &str = "tsmodel/v1";
+
+impl LinearRegressionResult {
+    /// Serializa o modelo em uma linha legível, por exemplo
+    /// `tsmodel/v1 linear slope=14.5 intercept=102 n=6 r_squared=0.98 mse=3.2`
+    ///
+    /// Os números usam a representação mais curta que volta exatamente ao mesmo `f64`. As
+    /// previsões do ajuste não são gravadas; veja [`from_model_string`](Self::from_model_string).
+    pub fn to_model_string(&self) -> String {
+        format!(
+            "{} linear slope={} intercept={} n={} r_squared={} mse={}",
+            MODEL_FORMAT_VERSION,
+            self.slope,
+            self.intercept,
+            self.count,
+            self.r_squared,
+            self.mse
+        )
+    }
+
+    /// Restaura um modelo gravado por [`to_model_string`](Self::to_model_string)
+    ///
+    /// `slope`, `intercept` e `n` (de 2 a 2^53) são obrigatórios; `r_squared` e `mse`, se
+    /// ausentes, ficam NaN. `predictions` fica vazio e `n` vai para `count`, o que mantém
+    /// [`forecast`](Self::forecast) e [`predict_future`] numerando a partir do fim dos
+    /// dados. Chaves desconhecidas são ignoradas, para aceitar gravações de versões
+    /// futuras compatíveis; versões ou tipos de modelo desconhecidos, campos malformados ou
+    /// repetidos geram erro com o trecho problemático.
+    pub fn from_model_string(text: &str) -> Result<Self, TimeSeriesError> {
+        fn invalid(pt: impl Into<String>, en: impl Into<String>) -> TimeSeriesError {
+            TimeSeriesError::invalid_parameter("model", LocalizedText::new(pt, en))
+        }
+        let mut tokens = text.split_whitespace();
+        match tokens.next() {
+            Some(MODEL_FORMAT_VERSION) => {}
+            Some(other) => {
+                return Err(invalid(format!("versão desconhecida '{}'", other), format!("unknown version '{}'", other)))
+            }
+            None => return Err(invalid("texto vazio", "empty text")),
+        }
+        match tokens.next() {
+            Some("linear") => {}
+            Some(other) => {
+                return Err(invalid(
+                    format!("tipo de modelo desconhecido '{}'", other),
+                    format!("unknown model type '{}'", other),
+                ))
+            }
+            None => return Err(invalid("tipo de modelo ausente", "missing model type")),
+        }
+
+        let (mut slope, mut intercept, mut n, mut r_squared, mut mse) = (None, None, None, None, None);
+        for token in tokens {
+            let (key, value) = token
+                .split_once('=')
+                .ok_or_else(|| invalid(format!("campo sem '=': '{}'", token), format!("field without '=': '{}'", token)))?;
+            let float = || {
+                value
+                    .parse::<f64>()
+                    .map_err(|_| invalid(format!("número inválido em '{}'", token), format!("invalid number in '{}'", token)))
+            };
+            let duplicate = match key {
+                "slope" => slope.replace(float()?).is_some(),
+                "intercept" => intercept.replace(float()?).is_some(),
+                "r_squared" => r_squared.replace(float()?).is_some(),
+                "mse" => mse.replace(float()?).is_some(),
+                "n" => {
+                    let count = value
+                        .parse::<usize>()
+                        .map_err(|_| invalid(format!("contagem inválida em '{}'", token), format!("invalid count in '{}'", token)))?;
+                    n.replace(count).is_some()
+                }
+                _ => false,
+            };
+            if duplicate {
+                return Err(invalid(format!("campo repetido '{}'", token), format!("repeated field '{}'", token)));
+            }
+        }
+
+        let required = |value: Option<f64>, key: &str| {
+            value.ok_or_else(|| invalid(format!("campo '{}' ausente", key), format!("missing field '{}'", key)))
+        };
+        let slope = required(slope, "slope")?;
+        let intercept = required(intercept, "intercept")?;
+        let n = n.ok_or_else(|| invalid("campo 'n' ausente", "missing field 'n'"))?;
+        // O ajuste exige 2 pontos e índices exatos em f64 (veja `validate_index_range`)
+        if n < 2 || validate_index_range::<f64>(n).is_err() {
+            return Err(invalid(
+                format!("contagem fora do intervalo em 'n={}' (de 2 a 2^53)", n),
+                format!("count out of range in 'n={}' (from 2 to 2^53)", n),
+            ));
+        }
+        if !slope.is_finite() || !intercept.is_finite() {
+            return Err(invalid("slope e intercept devem ser finitos", "slope and intercept must be finite"));
+        }
+
+        Ok(LinearRegressionResult {
+            slope,
+            intercept,
+            r_squared: r_squared.unwrap_or(f64::NAN),
+            mse: mse.unwrap_or(f64::NAN),
+            predictions: Vec::new(),
+            count: n,
+        })
+    }
+}
+
+/// Acumulador de passagem única para a regressão linear com x = 0, 1, 2, ...
+///
+/// Os valores são deslocados pelo primeiro elemento antes de somar (compensação de
+/// Kahan), o que mantém a precisão em séries com valores grandes e parecidos. As somas
+/// em x têm forma fechada e não precisam ser acumuladas.
+#[derive(Debug, Clone, Copy, Default)]
+struct OnlineRegression<T: Float = f64> {
+    count: usize,
+    shift: T,
+    sum_z: KahanSum<T>,
+    sum_iz: KahanSum<T>,
+    sum_zz: KahanSum<T>,
+}
+
+/// Garante que os índices `0..count` são inteiros exatos em `T` (até 2^53 em `f64`,
+/// 2^24 em `f32`); acima disso índices vizinhos se confundem e o ajuste perde sentido
+pub(crate) fn validate_index_range<T: Float>(count: usize) -> Result<(), TimeSeriesError> {
+    let limit = (2.0 / T::EPSILON.to_f64()) as u64;
+    if count as u64 > limit {
+        return Err(TimeSeriesError::invalid_parameter(
+            "data",
+            LocalizedText::new(
+                format!("{} pontos excedem o maior índice representável exatamente ({})", count, limit),
+                format!("{} points exceed the largest exactly representable index ({})", count, limit),
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Coeficientes e métricas de ajuste produzidos pelo [`OnlineRegression`]
+struct RegressionFit<T> {
+    slope: T,
+    intercept: T,
+    r_squared: T,
+    mse: T,
+    count: usize,
+}
+
+impl<T: Float> OnlineRegression<T> {
+    fn new() -> Self {
+        OnlineRegression::default()
+    }
+
+    /// Acumula `data[range]` com um deslocamento fixo, usando os índices globais
+    fn from_range(data: &[T], range: Range<usize>, shift: T) -> Self {
+        let mut online = OnlineRegression {
+            shift,
+            ..OnlineRegression::default()
+        };
+        for i in range {
+            let z = data[i] - shift;
+            online.sum_z.add(z);
+            online.sum_iz.add(T::from_usize(i) * z);
+            online.sum_zz.add(z * z);
+            online.count += 1;
+        }
+        online
+    }
+
+    /// Combina acumuladores de intervalos consecutivos com o mesmo deslocamento
+    fn merge(self, other: Self) -> Self {
+        OnlineRegression {
+            count: self.count + other.count,
+            shift: self.shift,
+            sum_z: self.sum_z.merge(other.sum_z),
+            sum_iz: self.sum_iz.merge(other.sum_iz),
+            sum_zz: self.sum_zz.merge(other.sum_zz),
+        }
+    }
+
+    fn push(&mut self, y: T) {
+        if self.count == 0 {
+            self.shift = y;
+        }
+        let z = y - self.shift;
+        self.sum_z.add(z);
+        self.sum_iz.add(T::from_usize(self.count) * z);
+        self.sum_zz.add(z * z);
+        self.count += 1;
+    }
+
+    fn fit(&self) -> Result<RegressionFit<T>, TimeSeriesError> {
+        if self.count < 2 {
+            return Err(TimeSeriesError::insufficient_data(2, self.count));
+        }
+        validate_index_range::<T>(self.count)?;
+
+        // Somas de x = 0..n-1 em forma fechada: Σx = n(n-1)/2 e Sxx = n(n²-1)/12. Com
+        // 2 ≤ n ≤ 2^53 (2^24 em f32), n³ fica muito abaixo do maior valor finito e Sxx > 0.
+        let n = T::from_usize(self.count);
+        let x_mean = (n - T::ONE) / T::from_f64(2.0);
+        let sxx = n * (n * n - T::ONE) / T::from_f64(12.0);
+
+        let sum_z = self.sum_z.total();
+        // Infinito nas somas é estouro; NaN vindo dos dados (via `linear_regression_unchecked`)
+        // continua sendo propagado
+        let sums = [self.sum_z.sum, self.sum_iz.sum, self.sum_zz.sum];
+        if sums.iter().any(|s| s.to_f64().is_infinite()) {
+            return Err(TimeSeriesError::new(
+                LocalizedText::new(
+                    "Estouro numérico na regressão: valores grandes demais para o tipo de ponto flutuante",
+                    "Numeric overflow in regression: values too large for the floating-point type",
+                ),
+            ));
+        }
+        let z_mean = sum_z / n;
+        let sxy = self.sum_iz.total() - x_mean * sum_z;
+        let syy = self.sum_zz.total() - z_mean * sum_z;
+
+        let slope = sxy / sxx;
+        let y_mean = self.shift + z_mean;
+
+        // RSS = Syy - b·Sxy; pode ficar levemente negativo por arredondamento
+        let mut rss = syy - slope * sxy;
+        if rss < T::ZERO {
+            rss = T::ZERO;
+        }
+        // Variação desprezível frente à magnitude dos dados: série constante
+        let spread = (if syy > T::ZERO { syy } else { T::ZERO } / n).sqrt();
+        let scale = y_mean.abs() + (self.sum_zz.total() / n).sqrt();
+        let r_squared = if approx_zero(spread, scale) {
+            T::ONE
+        } else {
+            T::ONE - rss / syy
+        };
+
+        Ok(RegressionFit {
+            slope,
+            intercept: y_mean - slope * x_mean,
+            r_squared,
+            mse: rss / n,
+            count: self.count,
+        })
+    }
+}
+
+/// Realiza regressão linear em uma série temporal
+///
+/// Retorna erro se a série contiver NaN ou infinito; veja [`linear_regression_unchecked`].
+pub fn linear_regression<T: Float>(data: &[T]) -> Result<LinearRegressionResult<T>, TimeSeriesError> {
+    validate_finite(data)?;
+    linear_regression_unchecked(data)
+}
+
+/// Regressão linear sem validação de valores não finitos, para dados já validados
+///
+/// Os dados são lidos uma única vez: inclinação, intercepto, MSE e R² saem das somas
+/// acumuladas, e as previsões são geradas a partir dos coeficientes.
+pub fn linear_regression_unchecked<T: Float>(data: &[T]) -> Result<LinearRegressionResult<T>, TimeSeriesError> {
+    let fit = fit_slice(data)?;
+
+    let predictions: Vec<T> = (0..data.len())
+        .map(|i| fit.intercept + fit.slope * T::from_usize(i))
+        .collect();
+
+    Ok(LinearRegressionResult {
+        slope: fit.slope,
+        intercept: fit.intercept,
+        r_squared: fit.r_squared,
+        mse: fit.mse,
+        predictions,
+        count: data.len(),
+    })
+}
+
+/// Ajuste em passagem única sobre o slice, em blocos com a feature `parallel`
+fn fit_slice<T: Float>(data: &[T]) -> Result<RegressionFit<T>, TimeSeriesError> {
+    let shift = data.first().copied().unwrap_or(T::ZERO);
+    fold_ranges(
+        data.len(),
+        |range| OnlineRegression::from_range(data, range, shift),
+        OnlineRegression::merge,
+    )
+    .fit()
+}
+
+/// Regressão linear sem gerar o vetor `predictions`
+///
+/// Calcula coeficientes, MSE e R² na mesma passagem de [`linear_regression`], mas não
+/// aloca as previsões — útil para séries muito longas. Use
+/// [`LinearRegressionResult::predict`] ou [`LinearRegressionResult::predict_range`] para
+/// obter valores sob demanda; [`predict_future`] continua numerando a partir do fim da
+/// série, pelo campo `count`.
+pub fn linear_regression_lean<T: Float>(data: &[T]) -> Result<LinearRegressionResult<T>, TimeSeriesError> {
+    validate_finite(data)?;
+    let fit = fit_slice(data)?;
+
+    Ok(LinearRegressionResult {
+        slope: fit.slope,
+        intercept: fit.intercept,
+        r_squared: fit.r_squared,
+        mse: fit.mse,
+        predictions: Vec::new(),
+        count: fit.count,
+    })
+}
+
+/// Regressão linear em passagem única sobre um iterador, sem armazenar a entrada
+///
+/// Produz os mesmos coeficientes e métricas de [`linear_regression`], mas o vetor
+/// `predictions` fica vazio.
+pub fn linear_regression_iter<T, I>(data: I) -> Result<LinearRegressionResult<T>, TimeSeriesError>
+where
+    T: Float,
+    I: IntoIterator<Item = T>,
+{
+    let mut online = OnlineRegression::new();
+    for (index, y) in data.into_iter().enumerate() {
+        if !y.is_finite() {
+            return Err(TimeSeriesError::NonFiniteValue { index });
+        }
+        online.push(y);
+    }
+    let fit = online.fit()?;
+
+    Ok(LinearRegressionResult {
+        slope: fit.slope,
+        intercept: fit.intercept,
+        r_squared: fit.r_squared,
+        mse: fit.mse,
+        predictions: Vec::new(),
+        count: fit.count,
+    })
+}
+
+/// Regressão linear sobre uma série lida em blocos, mantendo apenas um bloco por vez
+///
+/// Os blocos são consumidos em ordem e tratados como uma única série contínua; os
+/// coeficientes e métricas são os de [`linear_regression`] sobre a concatenação, e
+/// `predictions` fica vazio como em [`linear_regression_iter`]. O índice de um valor não
+/// finito no erro conta desde o início do primeiro bloco.
+pub fn linear_regression_chunked<T, I>(chunks: I) -> Result<LinearRegressionResult<T>, TimeSeriesError>
+where
+    T: Float,
+    I: IntoIterator,
+    I::Item: AsRef<[T]>,
+{
+    let mut online = OnlineRegression::new();
+    for chunk in chunks {
+        let chunk = chunk.as_ref();
+        if let Err(TimeSeriesError::NonFiniteValue { index }) = validate_finite(chunk) {
+            return Err(TimeSeriesError::NonFiniteValue { index: online.count + index });
+        }
+        chunk.iter().for_each(|&y| online.push(y));
+    }
+    let fit = online.fit()?;
+
+    Ok(LinearRegressionResult {
+        slope: fit.slope,
+        intercept: fit.intercept,
+        r_squared: fit.r_squared,
+        mse: fit.mse,
+        predictions: Vec::new(),
+        count: fit.count,
+    })
+}
+
+/// Regressão linear de `y` contra um preditor `x` arbitrário (em vez do índice do período)
+///
+/// `predictions` traz os valores ajustados em cada `x[i]` e [`LinearRegressionResult::predict`]
+/// recebe um valor de `x`. Exige ao menos 2 pares e `x` não constante.
+pub fn linear_regression_xy(x: &[f64], y: &[f64]) -> Result<LinearRegressionResult, TimeSeriesError> {
+    if x.len() != y.len() {
+        return Err(TimeSeriesError::LengthMismatch {
+            left: x.len(),
+            right: y.len(),
+        });
+    }
+    if x.len() < 2 {
+        return Err(TimeSeriesError::insufficient_data(2, x.len()));
+    }
+    validate_finite(x)?;
+    validate_finite(y)?;
+
+    let n = x.len() as f64;
+    let x_mean = kahan_sum(x.iter().copied()) / n;
+    let y_mean = kahan_sum(y.iter().copied()) / n;
+    let sxx = kahan_sum(x.iter().map(|xi| (xi - x_mean) * (xi - x_mean)));
+    let sxy = kahan_sum(x.iter().zip(y).map(|(xi, yi)| (xi - x_mean) * (yi - y_mean)));
+    let syy = kahan_sum(y.iter().map(|yi| (yi - y_mean) * (yi - y_mean)));
+    if approx_zero((sxx / n).sqrt(), max_abs(x)) {
+        return Err(TimeSeriesError::invalid_parameter(
+            "x",
+            LocalizedText::new("preditor constante", "constant predictor"),
+        ));
+    }
+
+    let slope = sxy / sxx;
+    let intercept = y_mean - slope * x_mean;
+    let predictions: Vec<f64> = x.iter().map(|xi| intercept + slope * xi).collect();
+    let rss = squared_error_sum(y, &predictions);
+    let r_squared = if approx_zero((syy / n).sqrt(), max_abs(y)) { 1.0 } else { 1.0 - rss / syy };
+
+    Ok(LinearRegressionResult {
+        slope,
+        intercept,
+        r_squared,
+        mse: rss / n,
+        predictions,
+        count: x.len(),
+    })
+}
+
+/// Regressão linear pela origem: `y = slope · x`, com `x` = índice do período e intercepto fixo em 0
+///
+/// `slope = Σxy / Σx²`. O `r_squared` segue a convenção da regressão sem intercepto:
+/// `1 - RSS / Σy²`, com a soma de quadrados total **não centrada** na média. Por isso ele não
+/// é comparável ao R² de [`linear_regression`] e costuma ser bem maior para séries afastadas
+/// de zero; uma série toda nula tem R² = 1. Como o período 0 tem `x = 0`, a primeira
+/// observação não influencia a inclinação, só os resíduos. Exige ao menos 2 pontos.
+pub fn linear_regression_through_origin(data: &[f64]) -> Result<LinearRegressionResult, TimeSeriesError> {
+    if data.len() < 2 {
+        return Err(TimeSeriesError::insufficient_data(2, data.len()));
+    }
+    validate_finite(data)?;
+
+    let n = data.len() as f64;
+    let sxx = kahan_sum((0..data.len()).map(|i| (i * i) as f64));
+    let sxy = kahan_sum(data.iter().enumerate().map(|(i, y)| i as f64 * y));
+    let syy = kahan_sum(data.iter().map(|y| y * y));
+
+    let slope = sxy / sxx;
+    let predictions: Vec<f64> = (0..data.len()).map(|i| slope * i as f64).collect();
+    let rss = squared_error_sum(data, &predictions);
+    let r_squared = if approx_zero((syy / n).sqrt(), max_abs(data)) { 1.0 } else { 1.0 - rss / syy };
+
+    Ok(LinearRegressionResult {
+        slope,
+        intercept: 0.0,
+        r_squared,
+        mse: rss / n,
+        predictions,
+        count: data.len(),
+    })
+}
+
+impl<T: Float> LinearRegressionResult<T> {
+    /// Valor da reta ajustada no período `x` (0 é a primeira observação)
+    pub fn predict(&self, x: T) -> T {
+        self.intercept + self.slope * x
+    }
+
+    /// Valores da reta ajustada para os períodos de `range`, calculados sob demanda
+    pub fn predict_range(&self, range: Range<usize>) -> impl Iterator<Item = T> + '_ {
+        range.map(move |i| self.predict(T::from_usize(i)))
+    }
+
+    /// Previsões para os `periods` períodos seguintes ao fim da série
+    ///
+    /// Períodos além de `usize::MAX` não são representáveis e ficam de fora.
+    pub fn forecast(&self, periods: usize) -> Vec<ForecastPoint<T>> {
+        let n = self.count;
+        (n..n.saturating_add(periods))
+            .map(|period| {
+                let x = T::from_usize(period);
+                let value = self.predict(x);
+                ForecastPoint {
+                    period,
+                    x,
+                    value,
+                    lower: value,
+                    upper: value,
+                }
+            })
+            .collect()
+    }
+
+    /// Previsões até o período `last_period` (índice a partir de 0), inclusive
+    ///
+    /// Erro se `last_period` estiver antes da última observação; igual a ela, o resultado é vazio.
+    pub fn forecast_until(&self, last_period: usize) -> Result<Vec<ForecastPoint<T>>, TimeSeriesError> {
+        let n = self.count;
+        let end = last_period.saturating_add(1);
+        if end < n {
+            return Err(TimeSeriesError::invalid_parameter(
+                "last_period",
+                LocalizedText::new(
+                    format!("deve ser pelo menos {} (último período observado)", n.saturating_sub(1)),
+                    format!("must be at least {} (last observed period)", n.saturating_sub(1)),
+                ),
+            ));
+        }
+        Ok(self.forecast(end - n))
+    }
+}
+
+impl<T: Float> LinearRegressionResult<T> {
+    fn write_formatted<W: fmt::Write>(&self, f: &mut W, options: &FormatOptions) -> fmt::Result {
+        let num = |value: T| options.format(value.to_f64());
+        let (sign, slope) = if self.slope < T::ZERO {
+            ('-', -self.slope)
+        } else {
+            ('+', self.slope)
+        };
+        writeln!(f, "Regressao Linear: y = {} {} {}·x", num(self.intercept), sign, num(slope))?;
+        writeln!(f, "   R²: {}", num(self.r_squared))?;
+        writeln!(f, "   MSE: {}", num(self.mse))?;
+        write!(f, "   n: {}", self.count)
+    }
+}
+
+/// Resumo compacto: equação `y = a + b·x`, R², MSE e número de observações, com 4 casas
+/// decimais ou a precisão do formatador
+impl<T: Float> fmt::Display for LinearRegressionResult<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write_formatted(f, &FormatOptions::for_formatter(f, 4))
+    }
+}
+
+impl<T: Float> FormatWith for LinearRegressionResult<T> {
+    fn format_with(&self, options: &FormatOptions) -> String {
+        let mut out = String::new();
+        let _ = self.write_formatted(&mut out, options);
+        out
+    }
+}
+
+/// Direção da tendência identificada por [`classify_trend`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrendDirection {
+    /// Tendência de alta significativa
+    Increasing,
+    /// Tendência de queda significativa
+    Decreasing,
+    /// Inclinação desprezível frente ao nível da série
+    Flat,
+    /// Inclinação relevante, mas ajuste linear fraco demais para afirmar a direção
+    Inconclusive,
+}
+
+impl TrendDirection {
+    /// Nome da direção
+    pub fn name(&self) -> &'static str {
+        match self {
+            TrendDirection::Increasing => "Alta",
+            TrendDirection::Decreasing => "Queda",
+            TrendDirection::Flat => "Estavel",
+            TrendDirection::Inconclusive => "Inconclusiva",
+        }
+    }
+}
+
+impl fmt::Display for TrendDirection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// R² mínimo usado por [`classify_trend_default`]
+pub const DEFAULT_TREND_MIN_R_SQUARED: f64 = 0.5;
+
+/// Inclinação relativa mínima (1% do nível por período) usada por [`classify_trend_default`]
+pub const DEFAULT_TREND_MIN_RELATIVE_SLOPE: f64 = 0.01;
+
+/// Classifica a tendência linear da série como alta, queda, estável ou inconclusiva
+///
+/// A inclinação relativa é `slope / média(|y|)`. Abaixo de `min_relative_slope` a série
+/// é [`TrendDirection::Flat`]; acima, a direção só é afirmada se o R² do ajuste for pelo
+/// menos `min_r_squared`, caso contrário o resultado é [`TrendDirection::Inconclusive`].
+pub fn classify_trend(
+    data: &[f64],
+    min_r_squared: f64,
+    min_relative_slope: f64,
+) -> Result<TrendDirection, TimeSeriesError> {
+    if !(0.0..=1.0).contains(&min_r_squared) {
+        return Err(TimeSeriesError::invalid_parameter(
+            "min_r_squared",
+            LocalizedText::new("deve estar entre 0 e 1", "must be between 0 and 1"),
+        ));
+    }
+    if !min_relative_slope.is_finite() || min_relative_slope < 0.0 {
+        return Err(TimeSeriesError::invalid_parameter(
+            "min_relative_slope",
+            LocalizedText::new("deve ser finito e não negativo", "must be finite and non-negative"),
+        ));
+    }
+
+    let result = linear_regression(data)?;
+    let level = data.iter().map(|v| v.abs()).sum::<f64>() / data.len() as f64;
+    if approx_zero(level, max_abs(data)) {
+        return Ok(TrendDirection::Flat);
+    }
+
+    let relative_slope = result.slope / level;
+    Ok(if relative_slope.abs() < min_relative_slope {
+        TrendDirection::Flat
+    } else if result.r_squared < min_r_squared {
+        TrendDirection::Inconclusive
+    } else if relative_slope > 0.0 {
+        TrendDirection::Increasing
+    } else {
+        TrendDirection::Decreasing
+    })
+}
+
+/// [`classify_trend`] com [`DEFAULT_TREND_MIN_R_SQUARED`] e [`DEFAULT_TREND_MIN_RELATIVE_SLOPE`]
+pub fn classify_trend_default(data: &[f64]) -> Result<TrendDirection, TimeSeriesError> {
+    classify_trend(data, DEFAULT_TREND_MIN_R_SQUARED, DEFAULT_TREND_MIN_RELATIVE_SLOPE)
+}
+
+/// Resultado de [`compare_trends`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrendComparison {
+    pub slope_a: f64,
+    pub slope_b: f64,
+    /// `slope_a - slope_b`
+    pub difference: f64,
+    /// Erro padrão da diferença, com variância residual combinada das duas séries
+    pub standard_error: f64,
+    /// `len(a) + len(b) - 4`
+    pub degrees_of_freedom: usize,
+    pub confidence: f64,
+    /// Limites do intervalo de confiança da diferença
+    pub ci_lower: f64,
+    pub ci_upper: f64,
+    /// O intervalo de confiança não contém zero
+    pub significantly_different: bool,
+}
+
+/// Resíduos e Sxx de uma série para [`compare_trends`]; `name` identifica a série nos erros
+fn trend_fit(data: &[f64], name: &'static str) -> Result<(f64, f64, f64), TimeSeriesError> {
+    if data.len() < 3 {
+        return Err(TimeSeriesError::invalid_parameter(
+            name,
+            LocalizedText::new(
+                format!("necessário(s) 3 ponto(s), recebido(s) {}", data.len()),
+                format!("3 point(s) required, {} received", data.len()),
+            ),
+        ));
+    }
+    validate_finite(data)?;
+    if data.iter().all(|&v| v == data[0]) {
+        return Err(TimeSeriesError::invalid_parameter(
+            name,
+            LocalizedText::new("série constante", "constant series"),
+        ));
+    }
+    let result = linear_regression(data)?;
+    let n = data.len() as f64;
+    let rss = residual_sum_of_squares(data, &result.predictions)?;
+    Ok((result.slope, rss, n * (n * n - 1.0) / 12.0))
+}
+
+/// Testa se as inclinações das séries `a` e `b` diferem, com nível de confiança `confidence`
+///
+/// Cada série é ajustada contra os próprios períodos `0..n` (os tamanhos podem diferir). O
+/// erro padrão da diferença usa a variância residual combinada
+/// `(RSS_a + RSS_b) / (n_a + n_b - 4)` e o intervalo usa a t de Student. Cada série precisa
+/// de ao menos 3 pontos e não pode ser constante; o erro indica qual entrada falhou.
+pub fn compare_trends(a: &[f64], b: &[f64], confidence: f64) -> Result<TrendComparison, TimeSeriesError> {
+    if !(confidence > 0.0 && confidence < 1.0) {
+        return Err(TimeSeriesError::invalid_parameter(
+            "confidence",
+            LocalizedText::new("deve estar entre 0 e 1 (exclusivos)", "must be between 0 and 1 (exclusive)"),
+        ));
+    }
+    let (slope_a, rss_a, sxx_a) = trend_fit(a, "a")?;
+    let (slope_b, rss_b, sxx_b) = trend_fit(b, "b")?;
+
+    let degrees_of_freedom = a.len() + b.len() - 4;
+    let pooled_variance = (rss_a + rss_b) / degrees_of_freedom as f64;
+    let standard_error = (pooled_variance * (1.0 / sxx_a + 1.0 / sxx_b)).sqrt();
+    let critical = student_t_quantile(1.0 - (1.0 - confidence) / 2.0, degrees_of_freedom as f64);
+    let difference = slope_a - slope_b;
+    let margin = critical * standard_error;
+
+    Ok(TrendComparison {
+        slope_a,
+        slope_b,
+        difference,
+        standard_error,
+        degrees_of_freedom,
+        confidence,
+        ci_lower: difference - margin,
+        ci_upper: difference + margin,
+        significantly_different: difference.abs() > margin,
+    })
+}
+
+/// Menor RSS considerado nos critérios de informação; RSS nulo (ajuste perfeito)
+/// é limitado a este valor para que o logaritmo permaneça finito
+pub const MIN_RSS: f64 = f64::MIN_POSITIVE;
+
+/// Critério de Informação de Akaike: `n·ln(RSS/n) + 2k`
+///
+/// `n` é o número de observações, `k` o número de parâmetros ajustados.
+/// RSS menor que [`MIN_RSS`] é limitado a esse valor; `n = 0` retorna infinito.
+pub fn aic(n: usize, k: usize, rss: f64) -> f64 {
+    if n == 0 {
+        return f64::INFINITY;
+    }
+    let n_f = n as f64;
+    n_f * (rss.max(MIN_RSS) / n_f).ln() + 2.0 * k as f64
+}
+
+/// Critério de Informação Bayesiano: `n·ln(RSS/n) + k·ln(n)`
+///
+/// Mesmas convenções de [`aic`], mas penaliza mais os parâmetros quando `n > 7`.
+pub fn bic(n: usize, k: usize, rss: f64) -> f64 {
+    if n == 0 {
+        return f64::INFINITY;
+    }
+    let n_f = n as f64;
+    n_f * (rss.max(MIN_RSS) / n_f).ln() + k as f64 * n_f.ln()
+}
+
+/// Soma dos quadrados dos resíduos entre valores reais e ajustados
+pub(crate) fn residual_sum_of_squares(actual: &[f64], fitted: &[f64]) -> Result<f64, TimeSeriesError> {
+    if actual.len() != fitted.len() {
+        return Err(TimeSeriesError::LengthMismatch {
+            left: actual.len(),
+            right: fitted.len(),
+        });
+    }
+    Ok(actual.iter()
+        .zip(fitted.iter())
+        .map(|(&a, &p)| (a - p).powi(2))
+        .sum())
+}
+
+impl LinearRegressionResult {
+    /// AIC do ajuste linear (k = 2: intercepto e inclinação)
+    pub fn aic(&self, actual: &[f64]) -> Result<f64, TimeSeriesError> {
+        let rss = residual_sum_of_squares(actual, &self.predictions)?;
+        Ok(aic(actual.len(), 2, rss))
+    }
+
+    /// BIC do ajuste linear (k = 2: intercepto e inclinação)
+    pub fn bic(&self, actual: &[f64]) -> Result<f64, TimeSeriesError> {
+        let rss = residual_sum_of_squares(actual, &self.predictions)?;
+        Ok(bic(actual.len(), 2, rss))
+    }
+
+    /// Erros padrão `(intercepto, inclinação)` dos coeficientes, com x = 0, 1, ..., n-1
+    ///
+    /// Usa a variância residual `RSS / (n - 2)`; exige ao menos 3 observações.
+    pub fn standard_errors(&self, actual: &[f64]) -> Result<(f64, f64), TimeSeriesError> {
+        let n = actual.len();
+        if n < 3 {
+            return Err(TimeSeriesError::insufficient_data(3, n));
+        }
+        let rss = residual_sum_of_squares(actual, &self.predictions)?;
+        let variance = rss / (n - 2) as f64;
+        let nf = n as f64;
+        let x_mean = (nf - 1.0) / 2.0;
+        let sxx = nf * (nf * nf - 1.0) / 12.0;
+        let se_slope = (variance / sxx).sqrt();
+        let se_intercept = (variance * (1.0 / nf + x_mean * x_mean / sxx)).sqrt();
+        Ok((se_intercept, se_slope))
+    }
+
+    /// Previsões como em [`forecast`](LinearRegressionResult::forecast), com intervalo de
+    /// previsão de nível `confidence`
+    ///
+    /// O erro padrão de uma nova observação em x é `s·√(1 + 1/n + (x - x̄)²/Sxx)`, com
+    /// `s² = RSS / (n - 2)`, e o intervalo usa a t de Student com `n - 2` graus de
+    /// liberdade. Exige ao menos 3 observações; um ajuste perfeito dá intervalos de
+    /// largura zero.
+    pub fn forecast_with_interval(
+        &self,
+        actual: &[f64],
+        periods: usize,
+        confidence: f64,
+    ) -> Result<Vec<ForecastPoint>, TimeSeriesError> {
+        if !(confidence > 0.0 && confidence < 1.0) {
+            return Err(TimeSeriesError::invalid_parameter(
+                "confidence",
+                LocalizedText::new(
+                    "deve estar entre 0 e 1 (exclusivos)",
+                    "must be between 0 and 1 (exclusive)",
+                ),
+            ));
+        }
+        let n = actual.len();
+        if n < 3 {
+            return Err(TimeSeriesError::insufficient_data(3, n));
+        }
+        let rss = residual_sum_of_squares(actual, &self.predictions)?;
+        let nf = n as f64;
+        let s = (rss / (nf - 2.0)).sqrt();
+        let x_mean = (nf - 1.0) / 2.0;
+        let sxx = nf * (nf * nf - 1.0) / 12.0;
+        let critical = student_t_quantile(1.0 - (1.0 - confidence) / 2.0, nf - 2.0);
+
+        Ok(self
+            .forecast(periods)
+            .into_iter()
+            .map(|point| {
+                let margin = critical * s * (1.0 + 1.0 / nf + (point.x - x_mean).powi(2) / sxx).sqrt();
+                ForecastPoint {
+                    lower: point.value - margin,
+                    upper: point.value + margin,
+                    ..point
+                }
+            })
+            .collect())
+    }
+
+    /// Resumo detalhado: o [`Display`](fmt::Display) do ajuste, estatísticas dos resíduos
+    /// `actual - previsto` e, com ao menos 3 pontos, os erros padrão dos coeficientes
+    ///
+    /// Se `actual` não corresponder ao ajuste, as seções extras indicam o problema.
+    /// Números com 4 casas decimais; veja [`summary_with`](Self::summary_with).
+    pub fn summary(&self, actual: &[f64]) -> String {
+        self.summary_with(actual, &FormatOptions::with_decimals(4))
+    }
+
+    /// [`summary`](Self::summary) com formatação numérica configurável
+    pub fn summary_with(&self, actual: &[f64], options: &FormatOptions) -> String {
+        let num = |value: f64| options.format(value);
+        let mut out = format!("{}\n", self.format_with(options));
+        if actual.len() != self.predictions.len() {
+            out.push_str(&format!(
+                "\nResiduos: n/d ({} valores reais para {} previsões)",
+                actual.len(),
+                self.predictions.len()
+            ));
+            return out;
+        }
+
+        let residuals: Vec<f64> = actual.iter().zip(&self.predictions).map(|(a, p)| a - p).collect();
+        match calculate_descriptive_stats(&residuals) {
+            Ok(stats) => {
+                out.push_str("\nResiduos:\n");
+                // a média dos resíduos é zero a menos de arredondamento; evita "-0.0000"
+                let rounding = 0.5 * 10f64.powi(-(options.decimals as i32));
+                let mean = if stats.mean.abs() < rounding { 0.0 } else { stats.mean };
+                out.push_str(&format!("   Media: {}\n", num(mean)));
+                out.push_str(&format!("   Desvio Padrao: {}\n", num(stats.std_dev)));
+                out.push_str(&format!("   Minimo: {}\n", num(stats.min)));
+                out.push_str(&format!("   Maximo: {}", num(stats.max)));
+            }
+            Err(err) => out.push_str(&format!("\nResiduos: n/d ({})", err)),
+        }
+
+        if let Ok((se_intercept, se_slope)) = self.standard_errors(actual) {
+            out.push_str("\n\nErros Padrao:\n");
+            out.push_str(&format!("   Intercept (β0): {}\n", num(se_intercept)));
+            out.push_str(&format!("   Slope (β1): {}", num(se_slope)));
+        }
+        out
+    }
+}
+
+/// Diagnóstico dos resíduos `actual - previsto` de um ajuste linear
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResidualDiagnostics {
+    pub mean: f64,
+    /// Desvio padrão populacional (divisor n)
+    pub std_dev: f64,
+    /// Assimetria `m3/m2^(3/2)` (momentos com divisor n, sem correção amostral)
+    pub skewness: f64,
+    /// Curtose em excesso `m4/m2² - 3` (normal ≈ 0)
+    pub excess_kurtosis: f64,
+    /// `Σ(e[t] - e[t-1])² / Σe[t]²`: ≈ 2 sem autocorrelação, → 0 com autocorrelação positiva
+    pub durbin_watson: f64,
+    pub lag1_autocorrelation: f64,
+    /// Estatística de Jarque–Bera; `None` quando os resíduos são todos nulos
+    pub jarque_bera: Option<f64>,
+    /// p-valor aproximado de Jarque–Bera (qui-quadrado com 2 graus de liberdade)
+    pub jarque_bera_p_value: Option<f64>,
+}
+
+impl fmt::Display for ResidualDiagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Diagnostico dos Residuos:")?;
+        writeln!(f, "   Media: {:.4}", self.mean)?;
+        writeln!(f, "   Desvio Padrao: {:.4}", self.std_dev)?;
+        writeln!(f, "   Assimetria: {:.4}", self.skewness)?;
+        writeln!(f, "   Curtose (excesso): {:.4}", self.excess_kurtosis)?;
+        writeln!(f, "   Durbin-Watson: {:.4}", self.durbin_watson)?;
+        writeln!(f, "   Autocorrelacao (lag 1): {:.4}", self.lag1_autocorrelation)?;
+        match (self.jarque_bera, self.jarque_bera_p_value) {
+            (Some(jb), Some(p)) => write!(f, "   Jarque-Bera: {:.4} (p = {:.4})", jb, p),
+            _ => write!(f, "   Normalidade: nao avaliavel (residuos nulos)"),
+        }
+    }
+}
+
+/// Resíduos `actual - previsto`, validando tamanhos, valores finitos e o mínimo de pontos
+pub(crate) fn regression_residuals(
+    actual: &[f64],
+    result: &LinearRegressionResult,
+    min_len: usize,
+) -> Result<Vec<f64>, TimeSeriesError> {
+    if actual.len() != result.predictions.len() {
+        return Err(TimeSeriesError::LengthMismatch {
+            left: actual.len(),
+            right: result.predictions.len(),
+        });
+    }
+    if actual.len() < min_len {
+        return Err(TimeSeriesError::insufficient_data(min_len, actual.len()));
+    }
+    validate_finite(actual)?;
+    Ok(actual.iter().zip(&result.predictions).map(|(a, p)| a - p).collect())
+}
+
+/// Calcula o diagnóstico dos resíduos de `result` frente a `actual`
+///
+/// Um ajuste perfeito (resíduos nulos a menos de arredondamento) devolve todas as
+/// estatísticas zeradas e a normalidade como não avaliável. Exige ao menos 3 pontos.
+pub fn residual_diagnostics(
+    actual: &[f64],
+    result: &LinearRegressionResult,
+) -> Result<ResidualDiagnostics, TimeSeriesError> {
+    let residuals = regression_residuals(actual, result, 3)?;
+    let (m2, m3, m4) = central_moments(&residuals);
+    if approx_zero(m2.sqrt(), max_abs(actual)) {
+        return Ok(ResidualDiagnostics {
+            mean: 0.0,
+            std_dev: 0.0,
+            skewness: 0.0,
+            excess_kurtosis: 0.0,
+            durbin_watson: 0.0,
+            lag1_autocorrelation: 0.0,
+            jarque_bera: None,
+            jarque_bera_p_value: None,
+        });
+    }
+
+    let n = residuals.len() as f64;
+    let mean = residuals.iter().sum::<f64>() / n;
+    let skewness = m3 / m2.powf(1.5);
+    let excess_kurtosis = m4 / (m2 * m2) - 3.0;
+    let sum_squares: f64 = residuals.iter().map(|e| e * e).sum();
+    let durbin_watson = residuals.windows(2).map(|w| (w[1] - w[0]).powi(2)).sum::<f64>() / sum_squares;
+    let lag1_autocorrelation = residuals
+        .windows(2)
+        .map(|w| (w[1] - mean) * (w[0] - mean))
+        .sum::<f64>()
+        / (m2 * n);
+    let jarque_bera = n / 6.0 * (skewness * skewness + excess_kurtosis * excess_kurtosis / 4.0);
+
+    Ok(ResidualDiagnostics {
+        mean,
+        std_dev: m2.sqrt(),
+        skewness,
+        excess_kurtosis,
+        durbin_watson,
+        lag1_autocorrelation,
+        jarque_bera: Some(jarque_bera),
+        // A cauda da qui-quadrado com 2 graus de liberdade é exatamente e^(-x/2)
+        jarque_bera_p_value: Some((-jarque_bera / 2.0).exp()),
+    })
+}
+
+/// Medidas de influência de um ponto no ajuste linear, calculadas por [`influence_measures`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InfluencePoint {
+    pub index: usize,
+    /// `actual - previsto`
+    pub residual: f64,
+    /// Alavancagem `h_ii = 1/n + (x_i - x̄)²/Sxx`: quanto o ponto puxa o próprio ajuste
+    pub leverage: f64,
+    /// Resíduo padronizado `e_i / (s·√(1 - h_ii))`, com `s² = RSS/(n - 2)`
+    pub standardized_residual: f64,
+    /// Distância de Cook `e_i²/(2·s²) · h_ii/(1 - h_ii)²`
+    pub cooks_distance: f64,
+}
+
+/// Alavancagem, resíduo padronizado e distância de Cook de cada ponto do ajuste `result`,
+/// com x = 0, 1, ..., n-1
+///
+/// Um ajuste perfeito (resíduos nulos a menos de arredondamento) dá resíduos
+/// padronizados e distâncias de Cook nulos. Exige ao menos 3 pontos.
+pub fn influence_measures(
+    actual: &[f64],
+    result: &LinearRegressionResult,
+) -> Result<Vec<InfluencePoint>, TimeSeriesError> {
+    let residuals = regression_residuals(actual, result, 3)?;
+    let nf = residuals.len() as f64;
+    let x_mean = (nf - 1.0) / 2.0;
+    let sxx = nf * (nf * nf - 1.0) / 12.0;
+    let variance = kahan_sum(residuals.iter().map(|e| e * e)) / (nf - 2.0);
+    let perfect_fit = approx_zero(variance.sqrt(), max_abs(actual));
+
+    Ok(residuals
+        .iter()
+        .enumerate()
+        .map(|(index, &residual)| {
+            let leverage = 1.0 / nf + (index as f64 - x_mean).powi(2) / sxx;
+            let (standardized_residual, cooks_distance) = if perfect_fit {
+                (0.0, 0.0)
+            } else {
+                (
+                    residual / (variance * (1.0 - leverage)).sqrt(),
+                    residual * residual / (2.0 * variance) * leverage / (1.0 - leverage).powi(2),
+                )
+            };
+            InfluencePoint {
+                index,
+                residual,
+                leverage,
+                standardized_residual,
+                cooks_distance,
+            }
+        })
+        .collect())
+}
+
+/// Índices com distância de Cook acima de `threshold`, do mais para o menos influente
+///
+/// Sem `threshold`, usa o corte convencional `4/n`.
+pub fn high_influence_indices(measures: &[InfluencePoint], threshold: Option<f64>) -> Vec<usize> {
+    let cutoff = threshold.unwrap_or(4.0 / measures.len() as f64);
+    let mut influential: Vec<&InfluencePoint> = measures.iter().filter(|m| m.cooks_distance > cutoff).collect();
+    influential.sort_by(|a, b| b.cooks_distance.total_cmp(&a.cooks_distance));
+    influential.iter().map(|m| m.index).collect()
+}
+
+/// Resultado de [`breusch_pagan`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeteroscedasticityResult {
+    /// Estatística LM = n·R² da regressão dos resíduos ao quadrado contra o tempo
+    pub lm_statistic: f64,
+    /// p-valor aproximado (qui-quadrado com 1 grau de liberdade)
+    pub p_value: f64,
+    /// `p_value < 0.05`: variância dos resíduos muda com o tempo
+    pub heteroscedastic: bool,
+}
+
+/// Teste de Breusch–Pagan para heterocedasticidade dos resíduos de `result`
+///
+/// Regride os resíduos ao quadrado contra o índice do período; uma variância que cresce
+/// (ou diminui) com o tempo dá R² alto nessa regressão auxiliar. Exige ao menos 3 pontos.
+///
+/// Resíduos ao quadrado sem dispersão frente à escala dos dados (ajuste perfeito, por
+/// exemplo) não têm variância a explicar: o resultado é `LM = 0` e `heteroscedastic = false`.
+pub fn breusch_pagan(actual: &[f64], result: &LinearRegressionResult) -> Result<HeteroscedasticityResult, TimeSeriesError> {
+    let squared: Vec<f64> = regression_residuals(actual, result, 3)?.iter().map(|e| e * e).collect();
+    // Sem esta checagem o ruído de arredondamento dá R² = 1 na regressão auxiliar e LM = n
+    let (spread, _, _) = central_moments(&squared);
+    let r_squared = if approx_zero(spread.sqrt(), max_abs(actual)) {
+        0.0
+    } else {
+        let auxiliary = linear_regression(&squared)?;
+        if auxiliary.r_squared.is_finite() { auxiliary.r_squared } else { 0.0 }
+    };
+    let lm_statistic = squared.len() as f64 * r_squared;
+    // Qui-quadrado com 1 grau de liberdade é o quadrado de uma normal padrão
+    let p_value = 2.0 * (1.0 - normal_cdf(lm_statistic.sqrt()));
+
+    Ok(HeteroscedasticityResult {
+        lm_statistic,
+        p_value,
+        heteroscedastic: p_value < 0.05,
+    })
+}
+
+/// Razão entre a variância dos resíduos na segunda e na primeira metade da série
+///
+/// Valores bem acima de 1 indicam resíduos que se abrem com o tempo. Com `n` ímpar o ponto
+/// central é ignorado. Exige ao menos 4 pontos e variância não nula na primeira metade.
+pub fn residual_spread_ratio(actual: &[f64], result: &LinearRegressionResult) -> Result<f64, TimeSeriesError> {
+    let residuals = regression_residuals(actual, result, 4)?;
+    let half = residuals.len() / 2;
+    let (first, _, _) = central_moments(&residuals[..half]);
+    let (second, _, _) = central_moments(&residuals[residuals.len() - half..]);
+    if first <= 0.0 {
+        return Err(TimeSeriesError::new(
+            LocalizedText::new(
+                "Variância nula dos resíduos na primeira metade",
+                "Zero residual variance in the first half",
+            ),
+        ));
+    }
+    Ok(second / first)
+}
+
+/// Resultado de [`runs_test`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunsTestResult {
+    /// Número de sequências de sinais iguais
+    pub runs: usize,
+    pub positives: usize,
+    pub negatives: usize,
+    /// `2·n₊·n₋/n + 1`
+    pub expected_runs: f64,
+    /// `2·n₊·n₋·(2·n₊·n₋ - n) / (n²·(n - 1))`
+    pub variance: f64,
+    /// Positivo com sinais alternando demais; negativo com sinais agrupados (autocorrelação)
+    pub z_statistic: f64,
+    /// p-valor bilateral pela aproximação normal
+    pub p_value: f64,
+    /// `p_value < 0.05`: os sinais não parecem aleatórios
+    pub non_random: bool,
+}
+
+/// Teste de sequências de Wald–Wolfowitz sobre os sinais dos resíduos
+///
+/// Conta as sequências de resíduos positivos e negativos e compara com o esperado se os
+/// sinais fossem aleatórios. Resíduos exatamente zero são descartados antes da contagem.
+/// Exige ao menos 2 resíduos de cada sinal.
+pub fn runs_test(residuals: &[f64]) -> Result<RunsTestResult, TimeSeriesError> {
+    validate_finite(residuals)?;
+    let signs: Vec<bool> = residuals.iter().filter(|&&r| r != 0.0).map(|&r| r > 0.0).collect();
+    let positives = signs.iter().filter(|&&positive| positive).count();
+    let negatives = signs.len() - positives;
+    if positives.min(negatives) < 2 {
+        return Err(TimeSeriesError::new(
+            LocalizedText::new(
+                format!("Teste de sequências exige ao menos 2 resíduos de cada sinal ({} positivos, {} negativos)", positives, negatives),
+                format!("Runs test requires at least 2 residuals of each sign ({} positive, {} negative)", positives, negatives),
+            ),
+        ));
+    }
+
+    let runs = 1 + signs.windows(2).filter(|pair| pair[0] != pair[1]).count();
+    let n = signs.len() as f64;
+    let product = 2.0 * positives as f64 * negatives as f64;
+    let expected_runs = product / n + 1.0;
+    let variance = product * (product - n) / (n * n * (n - 1.0));
+    let z_statistic = (runs as f64 - expected_runs) / variance.sqrt();
+    let p_value = erfc(z_statistic.abs() / core::f64::consts::SQRT_2);
+
+    Ok(RunsTestResult {
+        runs,
+        positives,
+        negatives,
+        expected_runs,
+        variance,
+        z_statistic,
+        p_value,
+        non_random: p_value < 0.05,
+    })
+}
+
+/// Menor número de reamostragens aceito por [`bootstrap_regression`]
+pub const MIN_BOOTSTRAP_ITERATIONS: usize = 100;
+
+/// Resultado de [`bootstrap_regression`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct BootstrapResult {
+    /// Ajuste da série original
+    pub fit: LinearRegressionResult,
+    pub confidence: f64,
+    /// Intervalo percentil `(inferior, superior)` da inclinação
+    pub slope_interval: (f64, f64),
+    /// Intervalo percentil `(inferior, superior)` do intercepto
+    pub intercept_interval: (f64, f64),
+    /// `(inclinação, intercepto)` de cada reamostragem
+    pub replicates: Vec<(f64, f64)>,
+}
+
+impl BootstrapResult {
+    /// Intervalo percentil da reta ajustada `horizon` períodos após a última observação
+    ///
+    /// É um intervalo de confiança para o valor esperado da previsão (o ruído de uma nova
+    /// observação não entra); `horizon = 0` corresponde à última observação.
+    pub fn forecast_interval(&self, horizon: usize) -> (f64, f64) {
+        let x = (self.fit.count - 1 + horizon) as f64;
+        let values: Vec<f64> = self.replicates.iter().map(|(slope, intercept)| intercept + slope * x).collect();
+        percentile_interval(values, self.confidence)
+    }
+}
+
+fn percentile_interval(values: Vec<f64>, confidence: f64) -> (f64, f64) {
+    let mut sorted = values;
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let tail = 100.0 * (1.0 - confidence) / 2.0;
+    (percentile_sorted(&sorted, tail), percentile_sorted(&sorted, 100.0 - tail))
+}
+
+/// Intervalos de confiança por bootstrap dos resíduos (x fixo) para a reta ajustada
+///
+/// A série é ajustada uma vez; em cada uma das `iterations` reamostragens os resíduos são
+/// sorteados com reposição e somados aos valores ajustados, e a reta é reajustada com
+/// [`linear_regression_lean`]. Os intervalos são os percentis `(1 - confidence)/2` e
+/// `(1 + confidence)/2` dos coeficientes reamostrados, sem supor resíduos normais. O sorteio
+/// usa o gerador interno com `seed`, então o resultado é reprodutível. Exige ao menos 3
+/// pontos, `iterations >= MIN_BOOTSTRAP_ITERATIONS` e `confidence` em (0, 1).
+pub fn bootstrap_regression(
+    data: &[f64],
+    iterations: usize,
+    seed: u64,
+    confidence: f64,
+) -> Result<BootstrapResult, TimeSeriesError> {
+    if !(confidence > 0.0 && confidence < 1.0) {
+        return Err(TimeSeriesError::invalid_parameter(
+            "confidence",
+            LocalizedText::new("deve estar entre 0 e 1 (exclusivos)", "must be between 0 and 1 (exclusive)"),
+        ));
+    }
+    if iterations < MIN_BOOTSTRAP_ITERATIONS {
+        return Err(TimeSeriesError::invalid_parameter(
+            "iterations",
+            LocalizedText::new(
+                format!("deve ser pelo menos {}", MIN_BOOTSTRAP_ITERATIONS),
+                format!("must be at least {}", MIN_BOOTSTRAP_ITERATIONS),
+            ),
+        ));
+    }
+    if data.len() < 3 {
+        return Err(TimeSeriesError::insufficient_data(3, data.len()));
+    }
+    let fit = linear_regression(data)?;
+    let residuals = regression_residuals(data, &fit, 3)?;
+
+    let n = data.len();
+    let mut rng = generators::SeededRng::new(seed);
+    let mut resampled = vec![0.0; n];
+    let mut replicates = Vec::with_capacity(iterations);
+    for _ in 0..iterations {
+        for (value, fitted) in resampled.iter_mut().zip(&fit.predictions) {
+            *value = fitted + residuals[(rng.next_u64() % n as u64) as usize];
+        }
+        let refit = linear_regression_lean(&resampled)?;
+        replicates.push((refit.slope, refit.intercept));
+    }
+
+    Ok(BootstrapResult {
+        slope_interval: percentile_interval(replicates.iter().map(|r| r.0).collect(), confidence),
+        intercept_interval: percentile_interval(replicates.iter().map(|r| r.1).collect(), confidence),
+        fit,
+        confidence,
+        replicates,
+    })
+}
+
+/// Estrutura para armazenar os resultados da regressão polinomial
+#[derive(Debug, Clone, PartialEq)]
+pub struct PolynomialRegressionResult {
+    /// Coeficientes em ordem crescente de grau (`coefficients[0]` é o intercepto)
+    pub coefficients: Vec<f64>,
+    pub r_squared: f64,
+    pub mse: f64,
+    pub predictions: Vec<f64>,
+}
+
+impl PolynomialRegressionResult {
+    /// Grau do polinômio ajustado
+    pub fn degree(&self) -> usize {
+        self.coefficients.len() - 1
+    }
+
+    /// Avalia o polinômio no período `x` (método de Horner)
+    pub fn predict(&self, x: f64) -> f64 {
+        self.coefficients.iter().rev().fold(0.0, |acc, &c| acc * x + c)
+    }
+
+    /// AIC do ajuste polinomial (k = grau + 1)
+    pub fn aic(&self, actual: &[f64]) -> Result<f64, TimeSeriesError> {
+        let rss = residual_sum_of_squares(actual, &self.predictions)?;
+        Ok(aic(actual.len(), self.coefficients.len(), rss))
+    }
+
+    /// BIC do ajuste polinomial (k = grau + 1)
+    pub fn bic(&self, actual: &[f64]) -> Result<f64, TimeSeriesError> {
+        let rss = residual_sum_of_squares(actual, &self.predictions)?;
+        Ok(bic(actual.len(), self.coefficients.len(), rss))
+    }
+}
+
+/// Resolve o sistema linear `a·x = b` por eliminação de Gauss com pivoteamento parcial
+pub(crate) fn solve_linear_system(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Result<Vec<f64>, TimeSeriesError> {
+    let n = b.len();
+    let scale = a.iter()
+        .flat_map(|row| row.iter())
+        .fold(0.0_f64, |acc, &v| acc.max(v.abs()));
+
+    for col in 0..n {
+        let pivot_row = (col..n)
+            .max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))
+            .unwrap_or(col);
+
+        if approx_zero(a[pivot_row][col], scale) {
+            return Err(TimeSeriesError::SingularMatrix);
+        }
+
+        a.swap(col, pivot_row);
+        b.swap(col, pivot_row);
+
+        for row in (col + 1)..n {
+            let factor = a[row][col] / a[col][col];
+            if factor == 0.0 {
+                continue;
+            }
+            let (upper, lower) = a.split_at_mut(row);
+            for (target, &pivot) in lower[0][col..].iter_mut().zip(&upper[col][col..]) {
+                *target -= factor * pivot;
+            }
+            b[row] -= factor * b[col];
+        }
+    }
+
+    let mut x = vec![0.0; n];
+    for row in (0..n).rev() {
+        let tail: f64 = ((row + 1)..n).map(|k| a[row][k] * x[k]).sum();
+        x[row] = (b[row] - tail) / a[row][row];
+    }
+    Ok(x)
+}
+
+/// Realiza regressão polinomial de grau `degree` em uma série temporal (x = 0..n)
+pub fn polynomial_regression(data: &[f64], degree: usize) -> Result<PolynomialRegressionResult, TimeSeriesError> {
+    validate_finite(data)?;
+    if data.len() < degree + 1 {
+        return Err(TimeSeriesError::insufficient_data(degree + 1, data.len()));
+    }
+
+    let terms = degree + 1;
+    let mut xtx = vec![vec![0.0; terms]; terms];
+    let mut xty = vec![0.0; terms];
+
+    for (i, &y) in data.iter().enumerate() {
+        let powers: Vec<f64> = (0..terms).map(|p| (i as f64).powi(p as i32)).collect();
+        for r in 0..terms {
+            xty[r] += powers[r] * y;
+            for c in 0..terms {
+                xtx[r][c] += powers[r] * powers[c];
+            }
+        }
+    }
+
+    let coefficients = solve_linear_system(xtx, xty)?;
+    let mut result = PolynomialRegressionResult {
+        coefficients,
+        r_squared: 0.0,
+        mse: 0.0,
+        predictions: Vec::new(),
+    };
+
+    let predictions: Vec<f64> = (0..data.len()).map(|i| result.predict(i as f64)).collect();
+    result.mse = calculate_mse(data, &predictions)?;
+    result.r_squared = calculate_r_squared(data, &predictions)?;
+    result.predictions = predictions;
+
+    Ok(result)
+}
+
+/// Estrutura para armazenar os resultados da regressão linear múltipla
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultipleRegressionResult {
+    /// Intercepto seguido de um coeficiente por preditor, na ordem de `xs`
+    pub coefficients: Vec<f64>,
+    pub r_squared: f64,
+    /// R² ajustado pelo número de preditores: `1 - (1 - R²)(n - 1)/(n - p - 1)`
+    pub adjusted_r_squared: f64,
+    pub mse: f64,
+    pub fitted: Vec<f64>,
+}
+
+impl MultipleRegressionResult {
+    /// Número de preditores (sem contar o intercepto)
+    pub fn num_predictors(&self) -> usize {
+        self.coefficients.len() - 1
+    }
+
+    /// Prevê a resposta para uma linha de preditores, na mesma ordem do ajuste
+    pub fn predict(&self, x_row: &[f64]) -> Result<f64, TimeSeriesError> {
+        if x_row.len() != self.num_predictors() {
+            return Err(TimeSeriesError::LengthMismatch {
+                left: self.num_predictors(),
+                right: x_row.len(),
+            });
+        }
+        Ok(self.coefficients[0] + self.coefficients[1..].iter().zip(x_row).map(|(b, x)| b * x).sum::<f64>())
+    }
+}
+
+/// Valida resposta e preditores e monta as equações normais `XᵀX·β = Xᵀy`, com a
+/// coluna do intercepto em primeiro lugar
+fn normal_equations(y: &[f64], xs: &[&[f64]]) -> Result<(Vec<Vec<f64>>, Vec<f64>), TimeSeriesError> {
+    if let Some(x) = xs.iter().find(|x| x.len() != y.len()) {
+        return Err(TimeSeriesError::LengthMismatch {
+            left: y.len(),
+            right: x.len(),
+        });
+    }
+    let terms = xs.len() + 1;
+    if y.len() < terms + 1 {
+        return Err(TimeSeriesError::insufficient_data(terms + 1, y.len()));
+    }
+    validate_finite(y)?;
+    for x in xs {
+        validate_finite(x)?;
+    }
+
+    let mut xtx = vec![vec![0.0; terms]; terms];
+    let mut xty = vec![0.0; terms];
+    let mut row = vec![1.0; terms];
+    for (i, &target) in y.iter().enumerate() {
+        for (j, x) in xs.iter().enumerate() {
+            row[j + 1] = x[i];
+        }
+        for r in 0..terms {
+            xty[r] += row[r] * target;
+            for c in 0..terms {
+                xtx[r][c] += row[r] * row[c];
+            }
+        }
+    }
+    Ok((xtx, xty))
+}
+
+/// Monta o resultado a partir dos coeficientes, calculando ajuste e métricas
+fn multiple_regression_result(y: &[f64], xs: &[&[f64]], coefficients: Vec<f64>) -> MultipleRegressionResult {
+    let fitted: Vec<f64> = (0..y.len())
+        .map(|i| coefficients[0] + xs.iter().zip(&coefficients[1..]).map(|(x, b)| b * x[i]).sum::<f64>())
+        .collect();
+    let n = y.len() as f64;
+    let p = xs.len() as f64;
+    let y_mean = y.iter().sum::<f64>() / n;
+    let r_squared = r_squared_with_mean(y, &fitted, y_mean);
+
+    MultipleRegressionResult {
+        coefficients,
+        r_squared,
+        adjusted_r_squared: 1.0 - (1.0 - r_squared) * (n - 1.0) / (n - p - 1.0),
+        mse: squared_error_sum(y, &fitted) / n,
+        fitted,
+    }
+}
+
+/// Regressão linear múltipla de `y` sobre os preditores `xs` (mínimos quadrados)
+///
+/// Cada elemento de `xs` é uma coluna com o mesmo tamanho de `y`; o intercepto é
+/// incluído automaticamente. Exige ao menos `xs.len() + 2` observações. Preditores
+/// colineares resultam em [`TimeSeriesError::SingularMatrix`].
+pub fn multiple_regression(y: &[f64], xs: &[&[f64]]) -> Result<MultipleRegressionResult, TimeSeriesError> {
+    let (xtx, xty) = normal_equations(y, xs)?;
+    let coefficients = solve_linear_system(xtx, xty)?;
+    Ok(multiple_regression_result(y, xs, coefficients))
+}
+
+fn validate_ridge_lambda(lambda: f64) -> Result<(), TimeSeriesError> {
+    if !lambda.is_finite() || lambda < 0.0 {
+        return Err(TimeSeriesError::invalid_parameter(
+            "lambda",
+            LocalizedText::new("deve ser finito e não negativo", "must be finite and non-negative"),
+        ));
+    }
+    Ok(())
+}
+
+/// Soma `lambda` à diagonal das equações normais, exceto na posição do intercepto
+fn ridge_coefficients(mut xtx: Vec<Vec<f64>>, xty: Vec<f64>, lambda: f64) -> Result<Vec<f64>, TimeSeriesError> {
+    for (i, row) in xtx.iter_mut().enumerate().skip(1) {
+        row[i] += lambda;
+    }
+    solve_linear_system(xtx, xty)
+}
+
+/// Regressão ridge: mínimos quadrados com penalidade `lambda·Σβ²` nos coeficientes
+///
+/// O intercepto não é penalizado. `lambda = 0` equivale a [`multiple_regression`];
+/// valores positivos estabilizam o ajuste com preditores quase colineares ou poucos
+/// pontos, encolhendo os coeficientes em direção a zero.
+pub fn ridge_regression(y: &[f64], xs: &[&[f64]], lambda: f64) -> Result<MultipleRegressionResult, TimeSeriesError> {
+    validate_ridge_lambda(lambda)?;
+    let (xtx, xty) = normal_equations(y, xs)?;
+    let coefficients = ridge_coefficients(xtx, xty, lambda)?;
+    Ok(multiple_regression_result(y, xs, coefficients))
+}
+
+/// Coeficientes ridge (intercepto primeiro) para cada valor de `lambdas`
+///
+/// As equações normais são montadas uma única vez. Combine com
+/// [`train_test_split`] para escolher `lambda` pelo erro fora da amostra.
+pub fn ridge_path(y: &[f64], xs: &[&[f64]], lambdas: &[f64]) -> Result<Vec<Vec<f64>>, TimeSeriesError> {
+    for &lambda in lambdas {
+        validate_ridge_lambda(lambda)?;
+    }
+    let (xtx, xty) = normal_equations(y, xs)?;
+    lambdas
+        .iter()
+        .map(|&lambda| ridge_coefficients(xtx.clone(), xty.clone(), lambda))
+        .collect()
+}
+
+/// Trecho aproximadamente linear encontrado por [`segment_series`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Segment {
+    /// Primeiro índice do trecho
+    pub start: usize,
+    /// Índice seguinte ao último do trecho (exclusivo)
+    pub end: usize,
+    /// Ajuste linear do trecho, com x = 0 em `start`
+    pub fit: LinearRegressionResult,
+}
+
+impl fmt::Display for Segment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Periodos {}-{}: {:+.4} por periodo a partir de {:.4} (R² {:.4})",
+            self.start + 1,
+            self.end,
+            self.fit.slope,
+            self.fit.intercept,
+            self.fit.r_squared
+        )
+    }
+}
+
+/// Fração do RSS do ajuste único abaixo da qual uma nova divisão não compensa
+pub const SEGMENT_MIN_IMPROVEMENT: f64 = 0.05;
+
+/// Somas acumuladas para calcular o RSS do ajuste linear de qualquer trecho em O(1)
+struct SegmentCost {
+    sum_y: Vec<f64>,
+    sum_yy: Vec<f64>,
+    sum_iy: Vec<f64>,
+}
+
+impl SegmentCost {
+    fn new(data: &[f64]) -> Self {
+        // Centrar na média reduz o cancelamento nas somas de quadrados
+        let mean = kahan_sum(data.iter().copied()) / data.len() as f64;
+        let mut cost = SegmentCost {
+            sum_y: vec![0.0; data.len() + 1],
+            sum_yy: vec![0.0; data.len() + 1],
+            sum_iy: vec![0.0; data.len() + 1],
+        };
+        for (i, &y) in data.iter().enumerate() {
+            let z = y - mean;
+            cost.sum_y[i + 1] = cost.sum_y[i] + z;
+            cost.sum_yy[i + 1] = cost.sum_yy[i] + z * z;
+            cost.sum_iy[i + 1] = cost.sum_iy[i] + i as f64 * z;
+        }
+        cost
+    }
+
+    /// RSS da reta ajustada a `data[start..end]`
+    fn rss(&self, start: usize, end: usize) -> f64 {
+        let m = (end - start) as f64;
+        let sy = self.sum_y[end] - self.sum_y[start];
+        let syy = self.sum_yy[end] - self.sum_yy[start] - sy * sy / m;
+        if end - start < 2 {
+            return syy.max(0.0);
+        }
+        // x local = i - start: Σxy = Σiy - start·Σy; x̄ = (m - 1)/2; Sxx = m(m² - 1)/12
+        let sxy = self.sum_iy[end] - self.sum_iy[start] - start as f64 * sy - (m - 1.0) / 2.0 * sy;
+        let sxx = m * (m * m - 1.0) / 12.0;
+        (syy - sxy * sxy / sxx).max(0.0)
+    }
+
+    /// Melhor ponto de divisão de `[start, end)` com trechos de ao menos `min_segment`
+    /// pontos, como `(ponto, redução do RSS)`
+    fn best_split(&self, start: usize, end: usize, min_segment: usize) -> Option<(usize, f64)> {
+        let whole = self.rss(start, end);
+        (start + min_segment..=end.checked_sub(min_segment)?)
+            .map(|split| (split, whole - self.rss(start, split) - self.rss(split, end)))
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+    }
+}
+
+/// Divide a série em até `max_segments` trechos aproximadamente lineares (segmentação binária)
+///
+/// A cada passo, divide o trecho cuja melhor divisão mais reduz a soma dos quadrados dos
+/// resíduos das retas ajustadas. Para quando atinge `max_segments` ou quando a melhor
+/// redução fica abaixo de [`SEGMENT_MIN_IMPROVEMENT`] vezes o RSS do ajuste único; ao
+/// final, trechos vizinhos cuja união aumenta o RSS menos que isso são reunidos. Cada
+/// trecho tem ao menos `min_segment` pontos (no mínimo 2); erro se
+/// `max_segments · min_segment` passar do tamanho da série.
+pub fn segment_series(data: &[f64], max_segments: usize, min_segment: usize) -> Result<Vec<Segment>, TimeSeriesError> {
+    if max_segments == 0 {
+        return Err(TimeSeriesError::invalid_parameter(
+            "max_segments",
+            LocalizedText::new("deve ser maior que zero", "must be greater than zero"),
+        ));
+    }
+    if min_segment < 2 {
+        return Err(TimeSeriesError::invalid_parameter(
+            "min_segment",
+            LocalizedText::new("deve ser pelo menos 2", "must be at least 2"),
+        ));
+    }
+    let required = max_segments
+        .checked_mul(min_segment)
+        .ok_or_else(|| TimeSeriesError::invalid_parameter(
+            "max_segments",
+            LocalizedText::new("grande demais", "too large"),
+        ))?;
+    if required > data.len() {
+        return Err(TimeSeriesError::insufficient_data(required, data.len()));
+    }
+    validate_finite(data)?;
+
+    let cost = SegmentCost::new(data);
+    let min_improvement = SEGMENT_MIN_IMPROVEMENT * cost.rss(0, data.len());
+    let mut bounds = vec![0, data.len()];
+    while bounds.len() - 1 < max_segments {
+        let best = bounds
+            .windows(2)
+            .filter_map(|pair| cost.best_split(pair[0], pair[1], min_segment))
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+        match best {
+            Some((split, improvement)) if improvement > min_improvement => {
+                let position = bounds.partition_point(|&b| b < split);
+                bounds.insert(position, split);
+            }
+            _ => break,
+        }
+    }
+
+    // Uma divisão feita cedo pode ficar redundante depois das seguintes (por exemplo, no
+    // meio de uma rampa); junta trechos vizinhos cuja união quase não aumenta o RSS
+    while bounds.len() > 2 {
+        let (position, increase) = (1..bounds.len() - 1)
+            .map(|k| {
+                let (a, b, c) = (bounds[k - 1], bounds[k], bounds[k + 1]);
+                (k, cost.rss(a, c) - cost.rss(a, b) - cost.rss(b, c))
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .expect("há ao menos uma fronteira interna");
+        if increase > min_improvement {
+            break;
+        }
+        bounds.remove(position);
+    }
+
+    bounds
+        .windows(2)
+        .map(|pair| {
+            Ok(Segment {
+                start: pair[0],
+                end: pair[1],
+                fit: linear_regression(&data[pair[0]..pair[1]])?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod testes {
+    use super::*;
+    use crate::testes::{assert_approx_eq, pseudo_random};
+
+    #[test]
+    fn test_regressao_linear_ajuste_perfeito() {
+        let data = vec![1.0, 3.0, 5.0, 7.0, 9.0];
+        let result = linear_regression(&data).unwrap();
+
+        assert_approx_eq(result.slope, 2.0, 1e-10);
+        assert_approx_eq(result.intercept, 1.0, 1e-10);
+        assert_approx_eq(result.r_squared, 1.0, 1e-10);
+        assert_approx_eq(result.mse, 0.0, 1e-10);
+    }
+
+    #[test]
+    fn test_regressao_linear_dados_constantes() {
+        let data = vec![5.0, 5.0, 5.0, 5.0, 5.0];
+        let result = linear_regression(&data).unwrap();
+
+        assert_approx_eq(result.slope, 0.0, 1e-10);
+        assert_approx_eq(result.intercept, 5.0, 1e-10);
+        assert_approx_eq(result.r_squared, 1.0, 1e-10);
+    }
+
+    #[test]
+    fn test_regressao_linear_slope_negativo() {
+        let data = vec![5.0, 4.0, 3.0, 2.0, 1.0];
+        let result = linear_regression(&data).unwrap();
+
+        assert_approx_eq(result.slope, -1.0, 1e-10);
+        assert_approx_eq(result.intercept, 5.0, 1e-10);
+        assert_approx_eq(result.r_squared, 1.0, 1e-10);
+    }
+
+    #[test]
+    fn test_dados_vazios() {
+        let data: Vec<f64> = vec![];
+        let result = linear_regression(&data);
+        assert!(result.is_err());
+
+        let stats_result = calculate_descriptive_stats(&data);
+        assert!(stats_result.is_err());
+    }
+
+    #[test]
+    fn test_unico_ponto_dado() {
+        let data = vec![5.0];
+        let result = linear_regression(&data);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_caso_limite_denominador_zero() {
+        let data = vec![1.0, 1.0, 1.0, 1.0];
+        let result = linear_regression(&data).unwrap();
+
+        assert_approx_eq(result.slope, 0.0, 1e-10);
+        assert_approx_eq(result.intercept, 1.0, 1e-10);
+        assert_approx_eq(result.r_squared, 1.0, 1e-10);
+    }
+
+    #[test]
+    fn test_documentacao_exemplos() {
+        let dados = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+        let resultado = linear_regression(&dados).unwrap();
+
+        assert!(resultado.slope.is_finite());
+        assert!(resultado.intercept.is_finite());
+
+        let previsoes = predict_future(&resultado, 3);
+        assert_eq!(previsoes.len(), 3);
+
+        #[cfg(feature = "std")]
+        ascii_plot(&dados, &resultado.predictions, "Teste");
+    }
+
+    #[test]
+    fn test_regressao_polinomial_quadratica() {
+        let data: Vec<f64> = (0..8).map(|i| 1.0 + 2.0 * i as f64 - 0.5 * (i * i) as f64).collect();
+        let result = polynomial_regression(&data, 2).unwrap();
+
+        assert_eq!(result.degree(), 2);
+        assert_approx_eq(result.coefficients[0], 1.0, 1e-8);
+        assert_approx_eq(result.coefficients[1], 2.0, 1e-8);
+        assert_approx_eq(result.coefficients[2], -0.5, 1e-8);
+        assert_approx_eq(result.r_squared, 1.0, 1e-10);
+
+        assert!(polynomial_regression(&[1.0, 2.0], 2).is_err());
+    }
+
+    #[test]
+    fn test_aic_bic_penalizam_parametros() {
+        let noise = [0.3, -0.2, 0.1, -0.4, 0.25, 0.05, -0.15, 0.35, -0.3, 0.2,
+                     -0.05, 0.15, -0.25, 0.4, -0.1, 0.0, 0.3, -0.35, 0.1, -0.2];
+        let data: Vec<f64> = noise.iter().enumerate().map(|(i, e)| 3.0 + 0.5 * i as f64 + e).collect();
+
+        let linear = linear_regression(&data).unwrap();
+        let degree_one = polynomial_regression(&data, 1).unwrap();
+        let degree_five = polynomial_regression(&data, 5).unwrap();
+
+        assert!(degree_five.mse < degree_one.mse);
+        assert!(degree_one.bic(&data).unwrap() < degree_five.bic(&data).unwrap());
+        assert_approx_eq(linear.bic(&data).unwrap(), degree_one.bic(&data).unwrap(), 1e-8);
+        assert_approx_eq(linear.aic(&data).unwrap(), degree_one.aic(&data).unwrap(), 1e-8);
+        assert!(linear.aic(&data[..5]).is_err());
+    }
+
+    #[test]
+    fn test_aic_bic_rss_zero() {
+        assert!(aic(10, 2, 0.0).is_finite());
+        assert!(bic(10, 2, 0.0) < bic(10, 3, 0.0));
+        assert_approx_eq(aic(10, 2, 10.0), 4.0, 1e-12);
+        assert_approx_eq(bic(10, 2, 10.0), 2.0 * 10.0_f64.ln(), 1e-12);
+        assert_eq!(aic(0, 2, 1.0), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_regressao_valores_grandes_soma_compensada() {
+        // 1e6 pontos em torno de 1e9: a soma ingênua desloca o intercepto em ~1e-6
+        let data: Vec<f64> = (0..1_000_000).map(|i| 1e9 + 0.001 * i as f64).collect();
+        let result = linear_regression(&data).unwrap();
+
+        assert_approx_eq(result.slope, 0.001, 1e-12);
+        assert_approx_eq(result.intercept, 1e9, 1e-7);
+        assert_approx_eq(result.mse, 0.0, 1e-12);
+    }
+
+    #[test]
+    fn test_valores_nao_finitos_rejeitados() {
+        let base = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+        for bad in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            for index in [0, 2, 4] {
+                let mut data = base.clone();
+                data[index] = bad;
+
+                let err = linear_regression(&data).unwrap_err();
+                assert!(err.to_string().contains(&format!("índice {}", index)), "{}", err);
+                assert!(calculate_descriptive_stats(&data).is_err());
+                assert!(validate_finite(&data).is_err());
+                assert!(evaluate_all(&base, &data).is_err());
+                assert!(median(&data).is_err());
+            }
+        }
+        assert!(validate_finite(&base).is_ok());
+    }
+
+    #[test]
+    fn test_regressao_sem_validacao() {
+        let data = vec![1.0, f64::NAN, 3.0];
+        let result = linear_regression_unchecked(&data).unwrap();
+        assert!(result.slope.is_nan());
+
+        let clean = vec![1.0, 3.0, 5.0];
+        assert_eq!(linear_regression_unchecked(&clean).unwrap(), linear_regression(&clean).unwrap());
+    }
+
+    fn check_core_suite<T: Float>(epsilon: f64) {
+        let close = |a: T, b: f64| assert_approx_eq(a.to_f64(), b, epsilon);
+        let data: Vec<T> = [1.0, 3.0, 5.0, 7.0, 9.0].iter().map(|&v| T::from_f64(v)).collect();
+
+        let result = linear_regression(&data).unwrap();
+        close(result.slope, 2.0);
+        close(result.intercept, 1.0);
+        close(result.r_squared, 1.0);
+        close(result.mse, 0.0);
+
+        let future = predict_future(&result, 2);
+        close(future[0], 11.0);
+        close(future[1], 13.0);
+
+        let predicted: Vec<T> = [2.0, 3.0, 4.0, 8.0, 9.0].iter().map(|&v| T::from_f64(v)).collect();
+        close(calculate_mse(&data, &predicted).unwrap(), 0.6);
+        close(calculate_mae(&data, &predicted).unwrap(), 0.6);
+        close(calculate_rmse(&data, &predicted).unwrap(), 0.6_f64.sqrt());
+        let report = evaluate_all(&data, &predicted).unwrap();
+        close(report.mape.unwrap(), (1.0 + 0.2 + 1.0 / 7.0) / 5.0 * 100.0);
+
+        let mut bad = data.clone();
+        bad[2] = T::from_f64(f64::NAN);
+        assert!(linear_regression(&bad).is_err());
+    }
+
+    #[test]
+    fn test_suite_generica_f64() {
+        check_core_suite::<f64>(1e-10);
+    }
+
+    #[test]
+    fn test_suite_generica_f32() {
+        check_core_suite::<f32>(1e-4);
+        let result = linear_regression::<f32>(&[2.0, 4.0, 6.0]).unwrap();
+        let slope: f32 = result.slope;
+        assert!((slope - 2.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_regressao_iterador_igual_a_slice() {
+        let data: Vec<f64> = (0..200).map(|i| 50.0 + 0.3 * i as f64 + ((i * 13) % 7) as f64).collect();
+        let from_slice = linear_regression(&data).unwrap();
+        let from_iter = linear_regression_iter(data.iter().copied()).unwrap();
+
+        assert_eq!(from_iter.slope, from_slice.slope);
+        assert_eq!(from_iter.intercept, from_slice.intercept);
+        assert_approx_eq(from_iter.mse, from_slice.mse, 1e-9);
+        assert_approx_eq(from_iter.r_squared, from_slice.r_squared, 1e-12);
+        assert!(from_iter.predictions.is_empty());
+
+        let streamed = linear_regression_iter((0..5).map(|i| 1.0 + 2.0 * i as f64)).unwrap();
+        assert_approx_eq(streamed.slope, 2.0, 1e-12);
+        assert_approx_eq(streamed.r_squared, 1.0, 1e-12);
+
+        assert_eq!(
+            linear_regression_iter(vec![1.0, f64::INFINITY]).unwrap_err(),
+            TimeSeriesError::NonFiniteValue { index: 1 }
+        );
+        assert!(linear_regression_iter(std::iter::once(1.0)).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn test_blocos_paralelos_igual_a_sequencial() {
+        let data: Vec<f64> = (0..10_000).map(|i| 1e6 + (i as f64 * 0.01).sin() + 0.002 * i as f64).collect();
+        let shift = data[0];
+
+        let sequential = OnlineRegression::from_range(&data, 0..data.len(), shift).fit().unwrap();
+        let parallel = fold_ranges_threaded(
+            data.len(),
+            777,
+            |range| OnlineRegression::from_range(&data, range, shift),
+            OnlineRegression::merge,
+        )
+        .fit()
+        .unwrap();
+
+        assert_approx_eq(parallel.slope, sequential.slope, 1e-12);
+        assert_approx_eq(parallel.intercept, sequential.intercept, 1e-9);
+    }
+
+    #[test]
+    #[ignore = "série de 20 milhões de pontos; rode com --ignored"]
+    fn test_serie_muito_grande() {
+        let n = 20_000_000;
+        let data: Vec<f64> = (0..n).map(|i| 1e9 + 0.5 * i as f64 + ((i % 7) as f64 - 3.0)).collect();
+
+        let result = linear_regression(&data).unwrap();
+        assert_approx_eq(result.slope, 0.5, 1e-9);
+
+        let stats = calculate_descriptive_stats(&data).unwrap();
+        let mean = data.iter().sum::<f64>() / n as f64;
+        assert!(((stats.mean - mean) / mean).abs() < 1e-9);
+
+        let reference = linear_regression_iter(data.iter().copied()).unwrap();
+        assert_approx_eq(result.intercept, reference.intercept, 1e-6);
+    }
+
+    #[test]
+    fn test_regressao_passagem_unica_metricas() {
+        let data: Vec<f64> = (0..300).map(|i| 10.0 - 0.2 * i as f64 + ((i * 31) % 17) as f64 * 0.5).collect();
+        let result = linear_regression(&data).unwrap();
+        assert_approx_eq(result.mse, calculate_mse(&data, &result.predictions).unwrap(), 1e-9);
+        assert_approx_eq(result.r_squared, calculate_r_squared(&data, &result.predictions).unwrap(), 1e-9);
+    }
+
+    #[test]
+    fn test_classify_trend() {
+        let noise = pseudo_random(3, 40);
+        let up: Vec<f64> = noise.iter().enumerate().map(|(i, e)| 100.0 + 5.0 * i as f64 + e).collect();
+        assert_eq!(classify_trend_default(&up).unwrap(), TrendDirection::Increasing);
+        let down: Vec<f64> = up.iter().rev().cloned().collect();
+        assert_eq!(classify_trend_default(&down).unwrap(), TrendDirection::Decreasing);
+
+        let flat: Vec<f64> = noise.iter().map(|e| 100.0 + e).collect();
+        assert_eq!(classify_trend_default(&flat).unwrap(), TrendDirection::Flat);
+
+        // Ruído puro em torno de zero: inclinação relativa alta, mas R² baixo
+        let pure: Vec<f64> = noise[..10].iter().map(|e| e - 0.5).collect();
+        assert_eq!(classify_trend_default(&pure).unwrap(), TrendDirection::Inconclusive);
+
+        assert!(classify_trend(&up, 1.5, 0.01).is_err());
+        assert!(classify_trend(&up, 0.5, -0.1).is_err());
+
+        // O nível é comparado à escala da própria série, não a um epsilon absoluto
+        for scale in [1e-20, 1e150] {
+            let scaled: Vec<f64> = up.iter().map(|v| v * scale).collect();
+            assert_eq!(classify_trend_default(&scaled).unwrap(), TrendDirection::Increasing);
+            let flat: Vec<f64> = flat.iter().map(|v| v * scale).collect();
+            assert_eq!(classify_trend_default(&flat).unwrap(), TrendDirection::Flat);
+        }
+        assert_eq!(classify_trend_default(&[0.0; 5]).unwrap(), TrendDirection::Flat);
+    }
+
+    #[test]
+    fn test_linear_regression_lean() {
+        let data: Vec<f64> = pseudo_random(11, 200).iter().enumerate().map(|(i, e)| 3.0 * i as f64 + e).collect();
+        let full = linear_regression(&data).unwrap();
+        let lean = linear_regression_lean(&data).unwrap();
+        assert!(lean.predictions.is_empty());
+        assert_eq!(lean.count, data.len());
+        assert_eq!(predict_future(&lean, 3), predict_future(&full, 3));
+        assert_eq!(lean.forecast_until(data.len() + 1).unwrap(), full.forecast(2));
+        assert_eq!(
+            (lean.slope, lean.intercept, lean.mse, lean.r_squared),
+            (full.slope, full.intercept, full.mse, full.r_squared)
+        );
+
+        let lazy: Vec<f64> = lean.predict_range(0..data.len()).collect();
+        assert_eq!(lazy, full.predictions);
+        assert_eq!(lean.predict(250.0), full.intercept + full.slope * 250.0);
+        assert!(linear_regression_lean(&[1.0, f64::NAN]).is_err());
+    }
+
+    #[test]
+    fn test_multiple_regression_equivale_a_linear() {
+        let y = [3.0, 5.5, 6.0, 9.0, 10.5, 12.0];
+        let time: Vec<f64> = (0..y.len()).map(|i| i as f64).collect();
+        let multiple = multiple_regression(&y, &[&time]).unwrap();
+        let simple = linear_regression(&y).unwrap();
+        assert_approx_eq(multiple.coefficients[0], simple.intercept, 1e-9);
+        assert_approx_eq(multiple.coefficients[1], simple.slope, 1e-9);
+        assert_approx_eq(multiple.r_squared, simple.r_squared, 1e-9);
+        assert_approx_eq(multiple.mse, simple.mse, 1e-9);
+        assert!(multiple.adjusted_r_squared < multiple.r_squared);
+    }
+
+    #[test]
+    fn test_multiple_regression_dois_preditores() {
+        // y = 2 + 1.5·promo - 0.5·temp
+        let promo = [0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0];
+        let temp = [20.0, 22.0, 25.0, 18.0, 30.0, 27.0, 21.0, 24.0];
+        let y: Vec<f64> = promo.iter().zip(&temp).map(|(p, t)| 2.0 + 1.5 * p - 0.5 * t).collect();
+        let result = multiple_regression(&y, &[&promo, &temp]).unwrap();
+        assert_approx_eq(result.coefficients[0], 2.0, 1e-9);
+        assert_approx_eq(result.coefficients[1], 1.5, 1e-9);
+        assert_approx_eq(result.coefficients[2], -0.5, 1e-9);
+        assert_approx_eq(result.predict(&[1.0, 10.0]).unwrap(), -1.5, 1e-9);
+        assert!(result.predict(&[1.0]).is_err());
+
+        let doubled: Vec<f64> = temp.iter().map(|t| 2.0 * t).collect();
+        assert_eq!(multiple_regression(&y, &[&temp, &doubled]), Err(TimeSeriesError::SingularMatrix));
+        assert_eq!(
+            multiple_regression(&y, &[&promo[..5]]),
+            Err(TimeSeriesError::LengthMismatch { left: 8, right: 5 })
+        );
+    }
+
+    #[test]
+    fn test_sistema_linear_em_qualquer_escala() {
+        // A singularidade é julgada frente ao maior coeficiente, não a um limite absoluto
+        for scale in [1e-200, 1e200] {
+            let a = vec![vec![2.0 * scale, 1.0 * scale], vec![1.0 * scale, 3.0 * scale]];
+            let x = solve_linear_system(a, vec![5.0 * scale, 10.0 * scale]).unwrap();
+            assert_approx_eq(x[0], 1.0, 1e-12);
+            assert_approx_eq(x[1], 3.0, 1e-12);
+
+            let singular = vec![vec![1.0 * scale, 2.0 * scale], vec![2.0 * scale, 4.0 * scale]];
+            assert_eq!(solve_linear_system(singular, vec![1.0, 2.0]), Err(TimeSeriesError::SingularMatrix));
+        }
+    }
+
+    #[test]
+    fn test_ridge_regression() {
+        let noise = pseudo_random(5, 30);
+        let x1: Vec<f64> = (0..30).map(|i| i as f64).collect();
+        let x2: Vec<f64> = x1.iter().zip(&noise).map(|(x, e)| x + e).collect();
+        let y: Vec<f64> = x1.iter().zip(&x2).map(|(a, b)| 1.0 + 2.0 * a - b).collect();
+
+        let ols = multiple_regression(&y, &[&x1, &x2]).unwrap();
+        let ridge0 = ridge_regression(&y, &[&x1, &x2], 0.0).unwrap();
+        for (a, b) in ols.coefficients.iter().zip(&ridge0.coefficients) {
+            assert_approx_eq(*a, *b, 1e-9);
+        }
+
+        let lambdas = [0.0, 0.1, 1.0, 10.0, 100.0, 1e4];
+        let path = ridge_path(&y, &[&x1, &x2], &lambdas).unwrap();
+        let norms: Vec<f64> = path.iter().map(|c| c[1..].iter().map(|b| b * b).sum::<f64>().sqrt()).collect();
+        assert!(norms.windows(2).all(|w| w[1] <= w[0]), "{:?}", norms);
+        assert!(norms[5] < norms[0]);
+
+        assert!(ridge_regression(&y, &[&x1], -1.0).is_err());
+    }
+
+    #[test]
+    fn test_display_regressao() {
+        let data = [3.0, 5.0, 7.5, 8.5, 11.0];
+        let result = linear_regression(&data).unwrap();
+        assert_eq!(
+            result.to_string(),
+            "Regressao Linear: y = 3.1000 + 1.9500·x\n   R²: 0.9877\n   MSE: 0.0950\n   n: 5"
+        );
+        let decreasing = linear_regression(&[5.0, 3.0, 1.0]).unwrap();
+        assert!(decreasing.to_string().starts_with("Regressao Linear: y = 5.0000 - 2.0000·x"));
+        assert!(linear_regression_lean(&data).unwrap().to_string().ends_with("n: 5"));
+    }
+
+    #[test]
+    fn test_summary_regressao() {
+        let data = [3.0, 5.0, 7.5, 8.5, 11.0];
+        let result = linear_regression(&data).unwrap();
+        let summary = result.summary(&data);
+        assert!(summary.starts_with("Regressao Linear: y = 3.1000 + 1.9500·x\n"));
+        assert!(!summary.contains("NaN"));
+        assert!(result.summary(&data[..3]).contains("Residuos: n/d"));
+    }
+
+    #[test]
+    fn test_compare_trends() {
+        let noise = pseudo_random(7, 30);
+        let a: Vec<f64> = noise.iter().enumerate().map(|(i, e)| 2.0 * i as f64 + e).collect();
+        let same = compare_trends(&a, &a, 0.95).unwrap();
+        assert!(!same.significantly_different);
+        assert_eq!(same.difference, 0.0);
+        assert!(same.ci_lower < 0.0 && same.ci_upper > 0.0);
+
+        let b: Vec<f64> = noise.iter().take(20).enumerate().map(|(i, e)| 0.5 * i as f64 - e).collect();
+        let diverging = compare_trends(&a, &b, 0.99).unwrap();
+        assert!(diverging.significantly_different);
+        assert_eq!(diverging.degrees_of_freedom, 46);
+        assert_approx_eq(diverging.difference, 1.5, 0.1);
+
+        match compare_trends(&a, &[3.0, 3.0, 3.0], 0.95).unwrap_err() {
+            TimeSeriesError::InvalidParameter { name, .. } => assert_eq!(name, "b"),
+            other => panic!("erro inesperado: {:?}", other),
+        }
+        match compare_trends(&[1.0, 2.0], &a, 0.95).unwrap_err() {
+            TimeSeriesError::InvalidParameter { name, .. } => assert_eq!(name, "a"),
+            other => panic!("erro inesperado: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_diagnostico_residuos() {
+        let data = [3.0, 5.0, 7.5, 8.5, 11.0];
+        let result = linear_regression(&data).unwrap();
+        let diagnostics = residual_diagnostics(&data, &result).unwrap();
+        // resíduos: -0.1, -0.05, 0.5, -0.45, 0.1
+        assert_approx_eq(diagnostics.mean, 0.0, 1e-12);
+        assert_approx_eq(diagnostics.std_dev, 0.095_f64.sqrt(), 1e-12);
+        assert_approx_eq(diagnostics.durbin_watson, 1.51 / 0.475, 1e-12);
+        let jb = diagnostics.jarque_bera.unwrap();
+        assert_approx_eq(diagnostics.jarque_bera_p_value.unwrap(), (-jb / 2.0).exp(), 1e-15);
+        assert!(diagnostics.to_string().contains("Jarque-Bera:"));
+
+        let line = [1.0, 3.0, 5.0, 7.0];
+        let perfect = residual_diagnostics(&line, &linear_regression(&line).unwrap()).unwrap();
+        assert_eq!(perfect.durbin_watson, 0.0);
+        assert_eq!(perfect.jarque_bera, None);
+        let text = perfect.to_string();
+        assert!(!text.contains("NaN"));
+        assert!(text.ends_with("Normalidade: nao avaliavel (residuos nulos)"));
+
+        // A tolerância acompanha a escala dos dados em qualquer ordem de grandeza
+        for scale in [1e-13, 1e15] {
+            let scaled: Vec<f64> = data.iter().map(|v| v * scale).collect();
+            let diagnostics = residual_diagnostics(&scaled, &linear_regression(&scaled).unwrap()).unwrap();
+            assert_approx_eq(diagnostics.std_dev / scale, 0.095_f64.sqrt(), 1e-9);
+            assert!(diagnostics.jarque_bera.is_some());
+
+            let line: Vec<f64> = line.iter().map(|v| v * scale).collect();
+            let perfect = residual_diagnostics(&line, &linear_regression(&line).unwrap()).unwrap();
+            assert_eq!(perfect.std_dev, 0.0);
+            assert_eq!(perfect.jarque_bera, None);
+        }
+    }
+
+    #[test]
+    fn test_breusch_pagan() {
+        let noise: Vec<f64> = pseudo_random(11, 200).iter().map(|u| u - 0.5).collect();
+        let constant: Vec<f64> = noise.iter().enumerate().map(|(i, e)| 5.0 + 0.3 * i as f64 + 4.0 * e).collect();
+        let growing: Vec<f64> = noise
+            .iter()
+            .enumerate()
+            .map(|(i, e)| 5.0 + 0.3 * i as f64 + 0.2 * i as f64 * e)
+            .collect();
+
+        let fit = linear_regression(&constant).unwrap();
+        let homoscedastic = breusch_pagan(&constant, &fit).unwrap();
+        assert!(!homoscedastic.heteroscedastic, "{:?}", homoscedastic);
+        assert!(residual_spread_ratio(&constant, &fit).unwrap() < 1.5);
+
+        let fit = linear_regression(&growing).unwrap();
+        let heteroscedastic = breusch_pagan(&growing, &fit).unwrap();
+        assert!(heteroscedastic.heteroscedastic);
+        assert!(heteroscedastic.p_value < 0.001);
+        assert!(residual_spread_ratio(&growing, &fit).unwrap() > 3.0);
+
+        // Reta perfeita: resíduos só de arredondamento não indicam heterocedasticidade
+        let line: Vec<f64> = (0..20).map(|i| 1.0 + 2.0 * i as f64).collect();
+        let perfect = breusch_pagan(&line, &linear_regression(&line).unwrap()).unwrap();
+        assert_eq!(perfect.lm_statistic, 0.0);
+        assert_approx_eq(perfect.p_value, 1.0, 1e-6);
+        assert!(!perfect.heteroscedastic);
+
+        assert_approx_eq(normal_cdf(1.959_963_985), 0.975, 1e-7);
+    }
+
+    #[test]
+    fn test_regressao_xy() {
+        let result = linear_regression_xy(&[1.0, 2.0, 4.0], &[3.0, 5.0, 9.0]).unwrap();
+        assert_approx_eq(result.slope, 2.0, 1e-12);
+        assert_approx_eq(result.intercept, 1.0, 1e-12);
+        assert_approx_eq(result.r_squared, 1.0, 1e-12);
+        assert_approx_eq(result.predict(10.0), 21.0, 1e-12);
+        assert!(linear_regression_xy(&[2.0, 2.0, 2.0], &[1.0, 2.0, 3.0]).is_err());
+    }
+
+    #[test]
+    fn test_regressao_limites_numericos() {
+        // n = 2 é o menor caso válido: Sxx = 0.5 nunca é nulo
+        let two = linear_regression(&[1.0, 3.0]).unwrap();
+        assert_eq!((two.slope, two.intercept, two.r_squared), (2.0, 1.0, 1.0));
+        assert_eq!(linear_regression(&[1.0]).unwrap_err(), TimeSeriesError::insufficient_data(2, 1));
+
+        // índices até 2^53 (f64) e 2^24 (f32) são exatos; acima disso é erro
+        assert!(validate_index_range::<f64>(1 << 53).is_ok());
+        assert_eq!(validate_index_range::<f64>((1 << 53) + 1).unwrap_err().kind(), ErrorKind::InvalidParameter);
+        assert!(validate_index_range::<f32>(1 << 24).is_ok());
+        assert!(validate_index_range::<f32>((1 << 24) + 1).is_err());
+
+        // somas que estouram viram erro em vez de NaN/infinito silencioso
+        assert!(linear_regression(&[1e200, -1e200, 1e200]).is_err());
+        assert!(linear_regression(&[3e38_f32, -3e38, 3e38]).is_err());
+        assert!(linear_regression_iter([1e200, -1e200, 1e200]).is_err());
+    }
+
+    #[test]
+    fn test_forecast_indices_de_periodo() {
+        let data = vec![1.0, 3.0, 5.0, 7.0, 9.0, 11.0];
+        let result = linear_regression(&data).unwrap();
+        let points = result.forecast(3);
+        let values = predict_future(&result, 3);
+
+        assert_eq!(points.len(), 3);
+        for (i, point) in points.iter().enumerate() {
+            assert_eq!(point.period, data.len() + i);
+            // Mesma numeração que o binário calculava à mão
+            assert_eq!(point.period_number(), data.len() + i + 1);
+            assert_approx_eq(point.x, (data.len() + i) as f64, 1e-12);
+            assert_approx_eq(point.value, values[i], 1e-12);
+        }
+        assert_approx_eq(points[0].value, 13.0, 1e-9);
+    }
+
+    #[test]
+    fn test_forecast_until() {
+        let data = vec![2.0, 4.0, 6.0, 8.0];
+        let result = linear_regression(&data).unwrap();
+
+        let points = result.forecast_until(6).unwrap();
+        assert_eq!(points.iter().map(|p| p.period).collect::<Vec<_>>(), vec![4, 5, 6]);
+        assert_approx_eq(points[2].value, 14.0, 1e-9);
+
+        assert!(result.forecast_until(3).unwrap().is_empty());
+        assert!(matches!(
+            result.forecast_until(2),
+            Err(TimeSeriesError::InvalidParameter { name: "last_period", .. })
+        ));
+    }
+
+    #[test]
+    fn test_forecast_with_interval() {
+        let data = [1.0, 3.0, 2.0, 5.0, 4.0, 6.0];
+        let result = linear_regression(&data).unwrap();
+        let points = result.forecast_with_interval(&data, 2, 0.95).unwrap();
+        // RSS = 3.7714, s² = RSS/4; t(0.975, 4) = 2.7764; x̄ = 2.5, Sxx = 17.5
+        let s = (result.mse * 6.0 / 4.0).sqrt();
+        let margin = 2.776_445 * s * (1.0 + 1.0 / 6.0 + 3.5 * 3.5 / 17.5).sqrt();
+        assert_approx_eq(points[0].upper - points[0].value, margin, 1e-5);
+        assert_approx_eq(points[0].value - points[0].lower, margin, 1e-5);
+        assert!(points[1].upper - points[1].lower > points[0].upper - points[0].lower);
+
+        let perfect = [2.0, 4.0, 6.0, 8.0];
+        let fit = linear_regression(&perfect).unwrap();
+        let point = fit.forecast_with_interval(&perfect, 1, 0.95).unwrap()[0];
+        assert_approx_eq(point.upper - point.lower, 0.0, 1e-9);
+        assert!(fit.forecast_with_interval(&perfect[..2], 1, 0.95).is_err());
+    }
+
+    #[test]
+    fn test_tolerancia_relativa_escalas_extremas() {
+        let pattern = [0.0, 3.0, 1.0, 4.0, 2.0, 5.0];
+        let reference = linear_regression(&pattern).unwrap();
+        for &scale in &[1e-12, 1e12] {
+            // Mesma estrutura em escala minúscula e, em torno de 1e12, variando ±100·escala/1e12
+            let offset = if scale > 1.0 { 1e12 } else { 0.0 };
+            let unit = if scale > 1.0 { 100.0 } else { scale };
+            let data: Vec<f64> = pattern.iter().map(|v| offset + v * unit).collect();
+
+            let result = linear_regression(&data).unwrap();
+            assert_approx_eq(result.slope / unit, reference.slope, 1e-6);
+            assert_approx_eq(result.r_squared, reference.r_squared, 1e-6);
+            assert_approx_eq(calculate_r_squared(&data, &result.predictions).unwrap(), reference.r_squared, 1e-6);
+            assert!(ascii_plot_string(&data, &result.predictions, "Escala").is_ok(), "escala {}", scale);
+            assert!(standardize(&data).is_ok());
+
+            let constant = vec![offset + 7.0 * scale; 6];
+            assert_eq!(linear_regression(&constant).unwrap().r_squared, 1.0);
+            assert!(ascii_plot_string(&constant, &constant, "Constante").is_err());
+        }
+        assert!(approx_zero(1e-30, 1e-12));
+        assert!(!approx_zero(1e-14, 1e-12));
+        assert!(!approx_zero(f64::MIN_POSITIVE, 0.0));
+    }
+
+    #[test]
+    fn test_segmentacao_tres_regimes() {
+        // Estável em 10 até 40, sobe 2 por período até 70, cai 1 por período até 100
+        let noise = pseudo_random(21, 100);
+        let data: Vec<f64> = (0..100)
+            .map(|i| {
+                let base = match i {
+                    0..=39 => 10.0,
+                    40..=69 => 10.0 + 2.0 * (i - 39) as f64,
+                    _ => 70.0 - (i - 69) as f64,
+                };
+                base + noise[i] - 0.5
+            })
+            .collect();
+
+        let segments = segment_series(&data, 5, 5).unwrap();
+        assert_eq!(segments.len(), 3, "{:?}", segments.iter().map(|s| (s.start, s.end)).collect::<Vec<_>>());
+        assert_eq!((segments[0].start, segments[2].end), (0, 100));
+        assert!(segments[0].end.abs_diff(40) <= 2);
+        assert!(segments[1].end.abs_diff(70) <= 2);
+        assert_approx_eq(segments[1].fit.slope, 2.0, 0.1);
+        assert_approx_eq(segments[2].fit.slope, -1.0, 0.1);
+        assert!(segments[0].to_string().starts_with("Periodos 1-"));
+
+        assert!(segment_series(&data, 21, 5).is_err());
+        assert!(segment_series(&data, 3, 1).is_err());
+        assert_eq!(segment_series(&data, 1, 5).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_modelo_texto_ida_e_volta() {
+        let data = [1.3, 2.9, 3.4, 5.8, 6.1, 7.7];
+        let result = linear_regression(&data).unwrap();
+        let text = result.to_model_string();
+        assert!(text.starts_with("tsmodel/v1 linear slope="));
+
+        let restored = LinearRegressionResult::from_model_string(&text).unwrap();
+        assert_eq!(restored.slope, result.slope);
+        assert_eq!(restored.intercept, result.intercept);
+        assert_eq!(restored.r_squared, result.r_squared);
+        assert_eq!(restored.mse, result.mse);
+        assert!(restored.predictions.is_empty());
+        assert_eq!(restored.count, data.len());
+        assert_eq!(predict_future(&restored, 3), predict_future(&result, 3));
+    }
+
+    #[test]
+    fn test_modelo_texto_compatibilidade_e_erros() {
+        // Chaves desconhecidas de versões futuras são ignoradas
+        let restored =
+            LinearRegressionResult::from_model_string("tsmodel/v1 linear slope=2 intercept=1 n=3 trained_on=edge").unwrap();
+        assert_eq!(restored.forecast(1)[0].period, 3);
+        assert_approx_eq(restored.forecast(1)[0].value, 7.0, 1e-12);
+        assert!(restored.r_squared.is_nan());
+
+        let message = |text: &str| LinearRegressionResult::from_model_string(text).unwrap_err().to_string();
+        assert!(message("tsmodel/v2 linear slope=2 intercept=1 n=3").contains("'tsmodel/v2'"));
+        assert!(message("tsmodel/v1 cubic slope=2").contains("'cubic'"));
+        assert!(message("tsmodel/v1 linear slope=abc intercept=1 n=3").contains("'slope=abc'"));
+        assert!(message("tsmodel/v1 linear slope=2 intercept=1 n=-1").contains("'n=-1'"));
+        assert!(message("tsmodel/v1 linear slope=2 n=3").contains("'intercept'"));
+        assert!(message("tsmodel/v1 linear slope=2 slope=3 intercept=1 n=3").contains("'slope=3'"));
+        assert!(message("").contains("vazio"));
+
+        // n fora do intervalo que um ajuste pode produzir é rejeitado
+        assert!(message("tsmodel/v1 linear slope=1 intercept=0 n=0").contains("'n=0'"));
+        assert!(message("tsmodel/v1 linear slope=1 intercept=0 n=1").contains("'n=1'"));
+        assert!(message("tsmodel/v1 linear slope=1 intercept=0 n=9007199254740993").contains("'n=9007199254740993'"));
+        assert!(message("tsmodel/v1 linear slope=1 intercept=0 n=18446744073709551615").contains("2^53"));
+        let largest = LinearRegressionResult::from_model_string("tsmodel/v1 linear slope=1 intercept=0 n=9007199254740992");
+        assert_eq!(largest.unwrap().count, 1 << 53);
+
+        // Previsões no limite de usize param em usize::MAX em vez de transbordar
+        let mut edge = restored.clone();
+        edge.count = usize::MAX - 1;
+        let points = edge.forecast(5);
+        assert_eq!(points.len(), 1);
+        assert_eq!(points[0].period, usize::MAX - 1);
+    }
+
+    #[test]
+    fn test_runs_test() {
+        // n₊ = n₋ = 10: E = 11, Var = 200·180 / (400·19)
+        let sd = (200.0 * 180.0 / (400.0 * 19.0_f64)).sqrt();
+        let alternating: Vec<f64> = (0..20).map(|i| if i % 2 == 0 { 1.0 } else { -0.5 }).collect();
+        let result = runs_test(&alternating).unwrap();
+        assert_eq!(result.runs, 20);
+        assert_approx_eq(result.expected_runs, 11.0, 1e-12);
+        assert_approx_eq(result.z_statistic, 9.0 / sd, 1e-9);
+        assert!(result.non_random);
+
+        let mut blocks = vec![2.0; 10];
+        blocks.extend([-2.0; 10]);
+        blocks.insert(5, 0.0);
+        let result = runs_test(&blocks).unwrap();
+        assert_eq!((result.runs, result.positives, result.negatives), (2, 10, 10));
+        assert_approx_eq(result.z_statistic, -9.0 / sd, 1e-9);
+        assert!(result.p_value < 1e-3);
+
+        assert!(runs_test(&[1.0, 2.0, 3.0, -1.0]).is_err());
+    }
+
+    #[test]
+    fn test_analise_em_blocos() {
+        let data = generators::random_walk(100_000, 1.0, 8).unwrap();
+        let sizes = [1, 7, 4_096, 33_333, 2, 50_000];
+        let mut chunks: Vec<Vec<f64>> = Vec::new();
+        let mut start = 0;
+        for &size in sizes.iter().cycle() {
+            if start == data.len() {
+                break;
+            }
+            let end = (start + size).min(data.len());
+            chunks.push(data[start..end].to_vec());
+            start = end;
+        }
+
+        let batch = linear_regression(&data).unwrap();
+        let chunked = linear_regression_chunked(&chunks).unwrap();
+        let scale = batch.intercept.abs().max(1.0);
+        assert_approx_eq(chunked.slope, batch.slope, 1e-9);
+        assert_approx_eq(chunked.intercept / scale, batch.intercept / scale, 1e-9);
+        assert_approx_eq(chunked.r_squared, batch.r_squared, 1e-9);
+        assert_approx_eq(chunked.mse / batch.mse, 1.0, 1e-9);
+
+        // Previsões a partir do ajuste em blocos seguem o fim da série, como no ajuste único
+        assert_eq!(chunked.count, data.len());
+        let (from_chunks, from_batch) = (chunked.forecast(3), batch.forecast(3));
+        for (a, b) in from_chunks.iter().zip(&from_batch) {
+            assert_eq!(a.period, b.period);
+            assert_approx_eq(a.value / scale, b.value / scale, 1e-9);
+        }
+        assert_eq!(from_chunks[0].period, data.len());
+
+        let stats = calculate_descriptive_stats(&data).unwrap();
+        let summary = descriptive_stats_chunked(chunks).unwrap();
+        assert_eq!(summary.count, stats.count);
+        assert_approx_eq(summary.mean, stats.mean, 1e-9);
+        assert_approx_eq(summary.std_dev, stats.std_dev, 1e-9);
+        assert_eq!((summary.min, summary.max), (stats.min, stats.max));
+
+        let empty: Vec<Vec<f64>> = Vec::new();
+        assert!(linear_regression_chunked::<f64, _>(&empty).is_err());
+        assert!(descriptive_stats_chunked(&empty).is_err());
+        assert_eq!(
+            descriptive_stats_chunked([vec![1.0, 2.0], vec![3.0, f64::NAN]]),
+            Err(TimeSeriesError::NonFiniteValue { index: 3 })
+        );
+    }
+
+    #[test]
+    fn test_regressao_pela_origem() {
+        // x = 0, 1, 2: Σxy = 10, Σx² = 5, inclinação 2, ajustados [0, 2, 4]
+        let result = linear_regression_through_origin(&[1.0, 2.0, 4.0]).unwrap();
+        assert_eq!(result.slope, 2.0);
+        assert_eq!(result.intercept, 0.0);
+        assert_eq!(result.predictions, vec![0.0, 2.0, 4.0]);
+        // RSS = 1, Σy² = 21 (soma total não centrada)
+        assert_approx_eq(result.r_squared, 20.0 / 21.0, 1e-12);
+        assert_approx_eq(result.mse, 1.0 / 3.0, 1e-12);
+        assert_eq!(predict_future(&result, 2), vec![6.0, 8.0]);
+
+        assert_eq!(linear_regression_through_origin(&[0.0, 0.0, 0.0]).unwrap().r_squared, 1.0);
+        assert!(matches!(
+            linear_regression_through_origin(&[1.0]),
+            Err(TimeSeriesError::InsufficientData { required: 2, got: 1 })
+        ));
+    }
+
+    #[test]
+    fn test_regressao_pela_origem_coincide_com_ajuste_comum() {
+        // Série que de fato passa pela origem, com ruído pequeno
+        let noise = pseudo_random(21, 50);
+        let data: Vec<f64> = (0..50).map(|i| 3.0 * i as f64 + (noise[i] - 0.5) * 0.1).collect();
+        let origin = linear_regression_through_origin(&data).unwrap();
+        let ordinary = linear_regression(&data).unwrap();
+        assert_approx_eq(origin.slope, ordinary.slope, 1e-3);
+        assert_approx_eq(ordinary.intercept, 0.0, 0.05);
+        assert!(origin.r_squared > 0.999);
+    }
+
+    #[test]
+    fn test_bootstrap_concorda_com_intervalo_analitico() {
+        let data = generators::linear_series(80, 0.5, 3.0, 2.0, 17).unwrap();
+        let boot = bootstrap_regression(&data, 2000, 42, 0.95).unwrap();
+        assert_eq!(boot.replicates.len(), 2000);
+        assert_eq!(boot.fit, linear_regression(&data).unwrap());
+
+        // Intervalo analítico da inclinação: ± t·se, com t(0.975, 78) ≈ 1.99
+        let (_, se_slope) = boot.fit.standard_errors(&data).unwrap();
+        let margin = student_t_quantile(0.975, 78.0) * se_slope;
+        let (lower, upper) = boot.slope_interval;
+        assert!(lower < boot.fit.slope && boot.fit.slope < upper);
+        assert!(lower < 0.5 && 0.5 < upper);
+        let ratio = (upper - lower) / (2.0 * margin);
+        assert!((0.85..1.15).contains(&ratio), "razão das larguras {}", ratio);
+        assert!(boot.intercept_interval.0 < 3.0 && 3.0 < boot.intercept_interval.1);
+
+        // A previsão mais distante tem intervalo mais largo
+        let near = boot.forecast_interval(0);
+        let far = boot.forecast_interval(20);
+        assert!(far.1 - far.0 > near.1 - near.0);
+        let expected = boot.fit.predict(99.0);
+        assert!(far.0 < expected && expected < far.1);
+
+        // Mesma semente, mesmo resultado
+        assert_eq!(bootstrap_regression(&data, 2000, 42, 0.95).unwrap(), boot);
+    }
+
+    #[test]
+    fn test_bootstrap_parametros_invalidos() {
+        let data = [1.0, 2.5, 2.9, 4.2, 5.1];
+        assert!(matches!(
+            bootstrap_regression(&data, 99, 1, 0.95),
+            Err(TimeSeriesError::InvalidParameter { name: "iterations", .. })
+        ));
+        assert!(matches!(
+            bootstrap_regression(&data, 100, 1, 1.0),
+            Err(TimeSeriesError::InvalidParameter { name: "confidence", .. })
+        ));
+        assert!(matches!(
+            bootstrap_regression(&data[..2], 100, 1, 0.9),
+            Err(TimeSeriesError::InsufficientData { required: 3, got: 2 })
+        ));
+    }
+
+    #[test]
+    fn test_influencia_ponto_extremo() {
+        let noise = pseudo_random(8, 20);
+        let mut data: Vec<f64> = noise.iter().enumerate().map(|(i, e)| 5.0 + 0.5 * i as f64 + (e - 0.5)).collect();
+        data[19] += 15.0;
+        let result = linear_regression(&data).unwrap();
+        let measures = influence_measures(&data, &result).unwrap();
+        assert_eq!(measures.len(), 20);
+        let top = measures.iter().max_by(|a, b| a.cooks_distance.total_cmp(&b.cooks_distance)).unwrap();
+        assert_eq!(top.index, 19);
+        assert_eq!(high_influence_indices(&measures, None)[0], 19);
+        assert!(high_influence_indices(&measures, Some(f64::INFINITY)).is_empty());
+        // alavancagem soma p = 2 e é máxima nas pontas
+        assert_approx_eq(measures.iter().map(|m| m.leverage).sum::<f64>(), 2.0, 1e-12);
+        assert!(measures[0].leverage > measures[10].leverage);
+        assert_approx_eq(measures[3].residual, data[3] - result.predictions[3], 1e-12);
+    }
+
+    #[test]
+    fn test_influencia_ajuste_perfeito() {
+        let data = [1.0, 3.0, 5.0, 7.0, 9.0];
+        let result = linear_regression(&data).unwrap();
+        let measures = influence_measures(&data, &result).unwrap();
+        assert!(measures.iter().all(|m| m.cooks_distance == 0.0 && m.standardized_residual == 0.0));
+        assert!(high_influence_indices(&measures, None).is_empty());
+        assert!(influence_measures(&data[..2], &linear_regression(&data[..2]).unwrap()).is_err());
+        assert!(matches!(influence_measures(&data[..4], &result), Err(TimeSeriesError::LengthMismatch { .. })));
+    }
+}
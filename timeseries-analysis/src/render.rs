@@ -0,0 +1,206 @@
+//! Camada comum de renderização: gráficos e seções de relatório como texto
+//!
+//! Cada componente visual implementa [`Renderable`] e produz uma `String` — o que permite
+//! comparar a saída em testes de snapshot. [`Renderable::print`] é o único ponto que
+//! escreve no terminal.
+//!
+//! ```
+//! use timeseries_analysis::render::{Renderable, Sparkline};
+//!
+//! assert_eq!(Sparkline::new(&[1.0, 2.0, 3.0]).render().unwrap(), "▁▅█");
+//! ```
+
+use crate::*;
+
+/// Componente que gera sua representação em texto
+pub trait Renderable {
+    /// Texto do componente, exatamente como seria impresso
+    fn render(&self) -> Result<String, TimeSeriesError>;
+
+    /// Imprime o componente; em caso de erro imprime a mensagem no lugar
+    #[cfg(feature = "std")]
+    fn print(&self) {
+        match self.render() {
+            Ok(text) => print!("{}", text),
+            Err(err) => println!("{}", err),
+        }
+    }
+}
+
+/// Gráfico de valores reais vs. previstos ([`ascii_plot_with_config`])
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinePlot<'a> {
+    pub actual: &'a [f64],
+    pub predicted: &'a [f64],
+    pub title: &'a str,
+    pub config: PlotConfig,
+}
+
+impl<'a> LinePlot<'a> {
+    /// Gráfico com a configuração padrão
+    pub fn new(actual: &'a [f64], predicted: &'a [f64], title: &'a str) -> Self {
+        LinePlot {
+            actual,
+            predicted,
+            title,
+            config: PlotConfig::default(),
+        }
+    }
+}
+
+impl Renderable for LinePlot<'_> {
+    fn render(&self) -> Result<String, TimeSeriesError> {
+        ascii_plot_with_config(self.actual, self.predicted, self.title, &self.config)
+    }
+}
+
+/// Gráfico com rótulos no eixo x ([`ascii_plot_labeled_string`])
+#[derive(Debug, Clone, PartialEq)]
+pub struct LabeledPlot<'a> {
+    pub actual: &'a [f64],
+    pub predicted: &'a [f64],
+    pub labels: &'a [String],
+    pub title: &'a str,
+}
+
+impl Renderable for LabeledPlot<'_> {
+    fn render(&self) -> Result<String, TimeSeriesError> {
+        ascii_plot_labeled_string(self.actual, self.predicted, self.labels, self.title)
+    }
+}
+
+/// Histórico, ajuste e previsões ([`ascii_plot_forecast_with_config`])
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForecastPlot<'a> {
+    pub actual: &'a [f64],
+    pub result: &'a LinearRegressionResult,
+    pub forecasts: &'a [f64],
+    pub title: &'a str,
+    pub config: PlotConfig,
+}
+
+impl<'a> ForecastPlot<'a> {
+    /// Gráfico com a configuração padrão
+    pub fn new(actual: &'a [f64], result: &'a LinearRegressionResult, forecasts: &'a [f64], title: &'a str) -> Self {
+        ForecastPlot {
+            actual,
+            result,
+            forecasts,
+            title,
+            config: PlotConfig::default(),
+        }
+    }
+}
+
+impl Renderable for ForecastPlot<'_> {
+    fn render(&self) -> Result<String, TimeSeriesError> {
+        ascii_plot_forecast_with_config(self.actual, self.result, self.forecasts, self.title, &self.config)
+    }
+}
+
+/// Várias séries nomeadas no mesmo gráfico ([`ascii_multi_plot`])
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultiPlot<'a> {
+    pub series: &'a [(&'a str, &'a [f64])],
+    pub title: &'a str,
+    pub config: PlotConfig,
+}
+
+impl Renderable for MultiPlot<'_> {
+    fn render(&self) -> Result<String, TimeSeriesError> {
+        ascii_multi_plot(self.series, self.title, &self.config)
+    }
+}
+
+/// Histograma horizontal ([`ascii_histogram`])
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Histogram<'a> {
+    pub data: &'a [f64],
+    pub bins: usize,
+}
+
+impl Renderable for Histogram<'_> {
+    fn render(&self) -> Result<String, TimeSeriesError> {
+        ascii_histogram(self.data, self.bins)
+    }
+}
+
+/// Sparkline de uma linha ([`sparkline_with_style`])
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sparkline<'a> {
+    pub data: &'a [f64],
+    pub style: SparklineStyle,
+}
+
+impl<'a> Sparkline<'a> {
+    /// Sparkline no estilo padrão (Unicode)
+    pub fn new(data: &'a [f64]) -> Self {
+        Sparkline {
+            data,
+            style: SparklineStyle::default(),
+        }
+    }
+}
+
+impl Renderable for Sparkline<'_> {
+    fn render(&self) -> Result<String, TimeSeriesError> {
+        sparkline_with_style(self.data, self.style)
+    }
+}
+
+/// Box plots em escala comum ([`ascii_boxplot`])
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoxPlot<'a> {
+    pub series: &'a [(&'a str, &'a [f64])],
+}
+
+impl Renderable for BoxPlot<'_> {
+    fn render(&self) -> Result<String, TimeSeriesError> {
+        ascii_boxplot(self.series)
+    }
+}
+
+/// Relatório completo ([`generate_report`])
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report<'a> {
+    pub data: &'a [f64],
+    pub options: ReportOptions,
+}
+
+impl Renderable for Report<'_> {
+    fn render(&self) -> Result<String, TimeSeriesError> {
+        generate_report(self.data, &self.options)
+    }
+}
+
+// Seções do relatório: o texto é o `Display` de cada resultado
+
+impl Renderable for DescriptiveStats {
+    fn render(&self) -> Result<String, TimeSeriesError> {
+        Ok(self.to_string())
+    }
+}
+
+impl Renderable for GrowthStats {
+    fn render(&self) -> Result<String, TimeSeriesError> {
+        Ok(self.to_string())
+    }
+}
+
+impl<T: Float> Renderable for LinearRegressionResult<T> {
+    fn render(&self) -> Result<String, TimeSeriesError> {
+        Ok(self.to_string())
+    }
+}
+
+impl<T: Float> Renderable for MetricsReport<T> {
+    fn render(&self) -> Result<String, TimeSeriesError> {
+        Ok(self.to_string())
+    }
+}
+
+impl Renderable for AnalysisResult {
+    fn render(&self) -> Result<String, TimeSeriesError> {
+        Ok(self.to_string())
+    }
+}
@@ -0,0 +1,427 @@
+//! Relatório em texto com estatísticas, ajuste, métricas e previsões
+//!
+//! ```
+//! use timeseries_analysis::report::{generate_report, ReportOptions};
+//!
+//! let options = ReportOptions { plot: false, ..ReportOptions::default() };
+//! let report = generate_report(&[1.0, 2.0, 3.0, 4.0], &options).unwrap();
+//! assert!(!report.is_empty());
+//! ```
+
+use crate::*;
+
+/// |z-score| acima do qual um ponto é tratado como anomalia em [`analyze`] e, por padrão,
+/// em [`generate_report`]
+pub const DEFAULT_ANOMALY_THRESHOLD: f64 = 3.0;
+
+/// Nível dos intervalos de previsão de [`analyze`]
+pub const ANALYSIS_CONFIDENCE: f64 = 0.95;
+
+/// Seções e parâmetros de [`generate_report`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReportOptions {
+    /// Título do cabeçalho
+    pub title: String,
+    pub descriptive_stats: bool,
+    /// Resumo da regressão linear ([`LinearRegressionResult::summary`])
+    pub regression: bool,
+    /// Classificação da tendência ([`classify_trend_default`])
+    pub trend: bool,
+    /// Resumo de crescimento ([`growth_stats`])
+    pub growth: bool,
+    /// Métricas de erro do ajuste linear
+    pub metrics: bool,
+    /// Pontos com |z-score| acima de `anomaly_threshold`
+    pub anomalies: bool,
+    pub anomaly_threshold: f64,
+    /// Gráfico ASCII real vs. ajustado
+    pub plot: bool,
+    pub plot_config: PlotConfig,
+    /// Períodos da tabela de previsões (0 omite a seção)
+    pub forecast_periods: usize,
+    /// Formatação de todos os números do relatório; `None` mantém a precisão de cada seção
+    /// (2 casas nas estatísticas, 4 na regressão e nas métricas...)
+    pub format: Option<FormatOptions>,
+}
+
+impl Default for ReportOptions {
+    fn default() -> Self {
+        ReportOptions {
+            title: "ANALISE DE SERIES TEMPORAIS".to_string(),
+            descriptive_stats: true,
+            regression: true,
+            trend: true,
+            growth: true,
+            metrics: true,
+            anomalies: true,
+            anomaly_threshold: DEFAULT_ANOMALY_THRESHOLD,
+            plot: true,
+            plot_config: PlotConfig::default(),
+            forecast_periods: 3,
+            format: None,
+        }
+    }
+}
+
+/// Gera o relatório completo em texto: estatísticas, regressão, tendência, métricas,
+/// anomalias, gráfico e previsões, conforme as seções ativas em `options`
+///
+/// O resultado é determinístico para a mesma entrada. Uma seção que não pode ser
+/// calculada (poucos dados, valores não finitos...) mostra o motivo em vez de abortar o
+/// relatório; só opções inválidas geram erro.
+pub fn generate_report(data: &[f64], options: &ReportOptions) -> Result<String, TimeSeriesError> {
+    if !(options.anomaly_threshold.is_finite() && options.anomaly_threshold > 0.0) {
+        return Err(TimeSeriesError::invalid_parameter(
+            "anomaly_threshold",
+            LocalizedText::new("deve ser finito e positivo", "must be finite and positive"),
+        ));
+    }
+    if options.plot {
+        validate_plot_config(&options.plot_config)?;
+    }
+
+    let format = options.format.as_ref();
+    let mut out = format!("=== {} ===\nObservacoes: {}\n", options.title, data.len());
+    if let Ok(line) = sparkline(data) {
+        out.push_str(&format!("Tendencia: {}\n", line));
+    }
+
+    let unavailable = |out: &mut String, heading: &str, err: &TimeSeriesError| {
+        out.push_str(&format!("\n{}\n   Indisponivel: {}\n", heading, err));
+    };
+
+    if options.descriptive_stats {
+        match calculate_descriptive_stats(data) {
+            Ok(stats) => out.push_str(&format!("\n{}\n", display_or_format(&stats, format))),
+            Err(err) => unavailable(&mut out, "Estatisticas Descritivas:", &err),
+        }
+    }
+
+    let regression = linear_regression(data);
+    if options.regression {
+        match &regression {
+            Ok(result) => {
+                let summary = match format {
+                    Some(format) => result.summary_with(data, format),
+                    None => result.summary(data),
+                };
+                out.push_str(&format!("\n{}\n", summary));
+            }
+            Err(err) => unavailable(&mut out, "Regressao Linear:", err),
+        }
+    }
+
+    if options.trend {
+        match classify_trend_default(data) {
+            Ok(direction) => out.push_str(&format!("\nClassificacao da Tendencia: {}\n", direction)),
+            Err(err) => unavailable(&mut out, "Classificacao da Tendencia:", &err),
+        }
+    }
+
+    if options.growth {
+        match growth_stats(data) {
+            Ok(growth) => out.push_str(&format!("\n{}\n", display_or_format(&growth, format))),
+            Err(err) => unavailable(&mut out, "Crescimento:", &err),
+        }
+    }
+
+    if options.metrics {
+        match regression.as_ref().map_err(Clone::clone).and_then(|r| evaluate_all(data, &r.predictions)) {
+            Ok(report) => out.push_str(&format!("\nMetricas de Erro:\n{}\n", display_or_format(&report, format))),
+            Err(err) => unavailable(&mut out, "Metricas de Erro:", &err),
+        }
+    }
+
+    if options.anomalies {
+        let heading = format!("Anomalias (|z| > {}):", options.anomaly_threshold);
+        match remove_outliers_zscore(data, options.anomaly_threshold)
+            .and_then(|(_, removed)| Ok((removed, calculate_descriptive_stats(data)?)))
+        {
+            Ok((indices, stats)) => {
+                out.push_str(&format!("\n{}\n", heading));
+                if indices.is_empty() {
+                    out.push_str("   Nenhuma\n");
+                }
+                for i in indices {
+                    let z = (data[i] - stats.mean) / stats.std_dev;
+                    out.push_str(&format!(
+                        "   Indice {}: {} (z = {})\n",
+                        i,
+                        format_number(data[i], format),
+                        format_number(z, format)
+                    ));
+                }
+            }
+            Err(err) => unavailable(&mut out, &heading, &err),
+        }
+    }
+
+    if options.plot {
+        match regression
+            .as_ref()
+            .map_err(Clone::clone)
+            .and_then(|r| ascii_plot_with_config(data, &r.predictions, "Real vs Previsto", &options.plot_config))
+        {
+            Ok(plot) => out.push_str(&format!("\n{}", plot)),
+            Err(err) => unavailable(&mut out, "Grafico:", &err),
+        }
+    }
+
+    if options.forecast_periods > 0 {
+        let heading = format!("Previsoes para os proximos {} periodos:", options.forecast_periods);
+        match &regression {
+            Ok(result) => {
+                out.push_str(&format!("\n{}\n", heading));
+                for point in result.forecast(options.forecast_periods) {
+                    out.push_str(&format!(
+                        "   Periodo {}: {}\n",
+                        point.period_number(),
+                        format_number(point.value, format)
+                    ));
+                }
+            }
+            Err(err) => unavailable(&mut out, &heading, err),
+        }
+    }
+    Ok(out)
+}
+
+/// Resultado de [`analyze`]: cada componente vem da função pública correspondente e fica
+/// `None` quando não pode ser calculado para a série
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnalysisResult {
+    pub observations: usize,
+    /// [`calculate_descriptive_stats`]
+    pub stats: Option<DescriptiveStats>,
+    /// [`linear_regression`]
+    pub regression: Option<LinearRegressionResult>,
+    /// [`classify_trend_default`]
+    pub trend: Option<TrendDirection>,
+    /// [`evaluate_all`] sobre o ajuste dentro da amostra
+    pub metrics: Option<MetricsReport>,
+    /// Índices com |z| acima de [`DEFAULT_ANOMALY_THRESHOLD`] ([`remove_outliers_zscore`])
+    pub anomalies: Option<Vec<usize>>,
+    /// Previsões com intervalo de [`ANALYSIS_CONFIDENCE`]
+    /// ([`LinearRegressionResult::forecast_with_interval`]); com 2 pontos, sem intervalo
+    pub forecast: Option<Vec<ForecastPoint>>,
+}
+
+/// Análise completa de uma série em uma chamada: estatísticas, ajuste linear, tendência,
+/// métricas do ajuste, anomalias e previsões para `forecast_periods` períodos
+///
+/// Só falha com menos de 2 pontos; os componentes que não puderem ser calculados (por
+/// exemplo, anomalias numa série constante) ficam `None`.
+///
+/// ```
+/// use timeseries_analysis::analyze;
+///
+/// let result = analyze(&[100.0, 120.0, 130.0, 145.0, 160.0], 2).unwrap();
+/// assert_eq!(result.regression.unwrap().slope, 14.5);
+/// ```
+pub fn analyze(data: &[f64], forecast_periods: usize) -> Result<AnalysisResult, TimeSeriesError> {
+    if data.len() < 2 {
+        return Err(TimeSeriesError::insufficient_data(2, data.len()));
+    }
+    let regression = linear_regression(data).ok();
+    let metrics = regression.as_ref().and_then(|r| evaluate_all(data, &r.predictions).ok());
+    let forecast = regression.as_ref().map(|r| {
+        r.forecast_with_interval(data, forecast_periods, ANALYSIS_CONFIDENCE)
+            .unwrap_or_else(|_| r.forecast(forecast_periods))
+    });
+
+    Ok(AnalysisResult {
+        observations: data.len(),
+        stats: calculate_descriptive_stats(data).ok(),
+        trend: classify_trend_default(data).ok(),
+        metrics,
+        anomalies: remove_outliers_zscore(data, DEFAULT_ANOMALY_THRESHOLD).ok().map(|(_, removed)| removed),
+        forecast,
+        regression,
+    })
+}
+
+impl AnalysisResult {
+    /// Sem `options`, cada componente usa o próprio `Display`
+    fn write_formatted<W: fmt::Write>(&self, f: &mut W, options: Option<&FormatOptions>) -> fmt::Result {
+        const UNAVAILABLE: &str = "   Indisponivel";
+        writeln!(f, "=== ANALISE ===")?;
+        writeln!(f, "Observacoes: {}", self.observations)?;
+        match &self.stats {
+            Some(stats) => writeln!(f, "\n{}", display_or_format(stats, options))?,
+            None => writeln!(f, "\nEstatisticas Descritivas:\n{}", UNAVAILABLE)?,
+        }
+        match &self.regression {
+            Some(regression) => writeln!(f, "\n{}", display_or_format(regression, options))?,
+            None => writeln!(f, "\nRegressao Linear:\n{}", UNAVAILABLE)?,
+        }
+        match self.trend {
+            Some(trend) => writeln!(f, "\nClassificacao da Tendencia: {}", trend)?,
+            None => writeln!(f, "\nClassificacao da Tendencia:\n{}", UNAVAILABLE)?,
+        }
+        match &self.metrics {
+            Some(metrics) => writeln!(f, "\nMetricas de Erro:\n{}", display_or_format(metrics, options))?,
+            None => writeln!(f, "\nMetricas de Erro:\n{}", UNAVAILABLE)?,
+        }
+        writeln!(f, "\nAnomalias (|z| > {}):", DEFAULT_ANOMALY_THRESHOLD)?;
+        match &self.anomalies {
+            Some(indices) if indices.is_empty() => writeln!(f, "   Nenhuma")?,
+            Some(indices) => {
+                for i in indices {
+                    writeln!(f, "   Indice {}", i)?;
+                }
+            }
+            None => writeln!(f, "{}", UNAVAILABLE)?,
+        }
+        match &self.forecast {
+            Some(points) if !points.is_empty() => {
+                writeln!(f, "\nPrevisoes:")?;
+                for point in points {
+                    write!(f, "   Periodo {}: {}", point.period_number(), format_number(point.value, options))?;
+                    if point.lower != point.upper {
+                        let (lower, upper) = (format_number(point.lower, options), format_number(point.upper, options));
+                        write!(f, " [{}, {}]", lower, upper)?;
+                    }
+                    writeln!(f)?;
+                }
+            }
+            Some(_) => {}
+            None => writeln!(f, "\nPrevisoes:\n{}", UNAVAILABLE)?,
+        }
+        Ok(())
+    }
+}
+
+/// Com a precisão do formatador (`{:.6}`), todos os números a usam
+impl fmt::Display for AnalysisResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let options = f.precision().map(FormatOptions::with_decimals);
+        self.write_formatted(f, options.as_ref())
+    }
+}
+
+impl FormatWith for AnalysisResult {
+    fn format_with(&self, options: &FormatOptions) -> String {
+        let mut out = String::new();
+        let _ = self.write_formatted(&mut out, Some(options));
+        out
+    }
+}
+
+#[cfg(test)]
+mod testes {
+    use super::*;
+    use crate::testes::assert_approx_eq;
+
+    #[test]
+    fn test_relatorio_completo() {
+        let data = [10.0, 12.0, 11.0, 14.0, 13.0, 40.0, 16.0, 15.0, 18.0, 17.0];
+        let options = ReportOptions {
+            plot: false,
+            forecast_periods: 2,
+            anomaly_threshold: 2.5,
+            ..ReportOptions::default()
+        };
+        let report = generate_report(&data, &options).unwrap();
+        assert_eq!(report, generate_report(&data, &options).unwrap());
+        assert!(report.contains("Anomalias (|z| > 2.5):\n   Indice 5: 40.00 (z = 2.86)\n"));
+        assert!(!report.contains("Grafico:"));
+    }
+
+    #[test]
+    fn test_relatorio_degrada_sem_dados() {
+        let report = generate_report(&[5.0], &ReportOptions::default()).unwrap();
+        assert!(report.contains("Estatisticas Descritivas:\n   Observacoes: 1"));
+        assert!(report.contains("Regressao Linear:\n   Indisponivel: TimeSeriesError: Dados insuficientes"));
+        assert!(report.contains("Grafico:\n   Indisponivel:"));
+        assert!(report.contains("Previsoes para os proximos 3 periodos:\n   Indisponivel:"));
+
+        let options = ReportOptions { anomaly_threshold: 0.0, ..ReportOptions::default() };
+        assert!(generate_report(&[1.0, 2.0], &options).is_err());
+    }
+
+    #[test]
+    fn test_analyze_exemplo_de_vendas() {
+        // Mesmos dados de tests/fixtures/vendas.csv
+        let data = [100.0, 120.0, 130.0, 145.0, 160.0];
+        let result = analyze(&data, 2).unwrap();
+        assert_eq!(result.observations, 5);
+        assert_eq!(result.stats.as_ref().unwrap().mean, 131.0);
+        let regression = result.regression.as_ref().unwrap();
+        assert_approx_eq(regression.slope, 14.5, 1e-12);
+        assert_approx_eq(regression.intercept, 102.0, 1e-12);
+        // RSS = 17.5 e SQT = 2120
+        assert_approx_eq(regression.r_squared, 1.0 - 17.5 / 2120.0, 1e-12);
+        assert_eq!(result.trend, Some(TrendDirection::Increasing));
+        assert_approx_eq(result.metrics.as_ref().unwrap().mse, 3.5, 1e-12);
+        assert_eq!(result.anomalies, Some(vec![]));
+
+        let forecast = result.forecast.as_ref().unwrap();
+        assert_eq!(forecast.len(), 2);
+        assert_eq!(forecast[0].period_number(), 6);
+        assert_approx_eq(forecast[0].value, 174.5, 1e-9);
+        assert_approx_eq(forecast[1].value, 189.0, 1e-9);
+        assert!(forecast[0].lower < 174.5 && 174.5 < forecast[0].upper);
+
+        let text = result.to_string();
+        assert!(text.contains("Classificacao da Tendencia: Alta"));
+        assert!(text.contains("Periodo 6: 174.50 ["));
+        let json = result.to_json();
+        assert!(json.starts_with("{\"observations\":5,\"stats\":{\"count\":5,"));
+        assert!(json.contains("\"trend\":\"increasing\""));
+        assert!(json.contains("\"anomalies\":[]"));
+        assert!(json.contains("\"forecast\":[{\"period\":6,\"value\":174.5"));
+    }
+
+    #[test]
+    fn test_analyze_componentes_indisponiveis() {
+        assert!(matches!(analyze(&[1.0], 3), Err(TimeSeriesError::InsufficientData { required: 2, got: 1 })));
+
+        let constant = analyze(&[4.0, 4.0, 4.0, 4.0], 1).unwrap();
+        assert_eq!(constant.anomalies, Some(vec![]));
+        assert_eq!(constant.forecast.as_ref().unwrap()[0].value, 4.0);
+
+        // Dois pontos: previsão sem intervalo
+        let short = analyze(&[1.0, 3.0], 1).unwrap();
+        let point = short.forecast.unwrap()[0];
+        assert_eq!((point.value, point.lower, point.upper), (5.0, 5.0, 5.0));
+
+        // Valores não finitos: nada é calculável, mas a chamada não falha
+        let broken = analyze(&[1.0, f64::NAN, 3.0], 1).unwrap();
+        assert!(broken.regression.is_none() && broken.forecast.is_none() && broken.metrics.is_none());
+        assert!(broken.anomalies.is_none());
+        assert!(broken.to_string().contains("Regressao Linear:\n   Indisponivel"));
+        assert!(broken.to_json().contains("\"regression\":null"));
+    }
+
+    #[test]
+    fn test_format_options_scientific_e_relatorio() {
+        let options = FormatOptions {
+            decimals: 2,
+            thousands_separator: Some('.'),
+            scientific_threshold: Some(1e6),
+        };
+        assert_eq!(options.format(12345.678), "12.345.68");
+        assert_eq!(options.format(-987654.0), "-987.654.00");
+        assert_eq!(options.format(2.5e7), "2.50e7");
+        assert_eq!(options.format(3.0e-8), "3.00e-8");
+        assert_eq!(options.format(0.0), "0.00");
+        assert_eq!(options.format(f64::NAN), "NaN");
+        assert_eq!(options.format_json(f64::INFINITY), "null");
+        assert_eq!(options.format_json(1234.5), "1234.50");
+
+        let data = [10.0, 12.0, 11.0, 13.0, 12.5, 14.0];
+        let default = generate_report(&data, &ReportOptions { plot: false, ..ReportOptions::default() }).unwrap();
+        let explicit = generate_report(
+            &data,
+            &ReportOptions {
+                plot: false,
+                format: Some(FormatOptions::with_decimals(3)),
+                ..ReportOptions::default()
+            },
+        )
+        .unwrap();
+        assert_ne!(default, explicit);
+        assert!(explicit.contains("   Media: 12.083\n"));
+        assert!(default.contains("   Media: 12.08\n"));
+    }
+}
@@ -0,0 +1,393 @@
+//! Sazonalidade e decomposição
+//!
+//! ```
+//! use timeseries_analysis::seasonal::{seasonal_indices, seasonally_adjust, SeasonalModel};
+//!
+//! let data = [10.0, 20.0, 10.0, 20.0, 10.0, 20.0];
+//! let indices = seasonal_indices(&data, 2, 0, SeasonalModel::Additive).unwrap();
+//! let adjusted = seasonally_adjust(&data, &indices, 0, SeasonalModel::Additive).unwrap();
+//! assert!(adjusted.iter().all(|value| (value - 15.0).abs() < 1e-9));
+//! ```
+
+use crate::*;
+
+/// Forma como a sazonalidade se combina com o nível da série
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeasonalModel {
+    /// `y = tendência + sazonalidade`; índices somam zero
+    Additive,
+    /// `y = tendência · sazonalidade`; índices têm média 1 (exige dados positivos)
+    Multiplicative,
+}
+
+fn validate_seasonal_phase(period: usize, start_phase: usize) -> Result<(), TimeSeriesError> {
+    if period < 2 {
+        return Err(TimeSeriesError::invalid_parameter(
+            "period",
+            LocalizedText::new("deve ser ao menos 2", "must be at least 2"),
+        ));
+    }
+    if start_phase >= period {
+        return Err(TimeSeriesError::invalid_parameter(
+            "start_phase",
+            LocalizedText::new("deve ser menor que o período", "must be less than the period"),
+        ));
+    }
+    Ok(())
+}
+
+/// Média móvel centrada: janela ímpar simples ou 2×period para períodos pares
+///
+/// O elemento `j` do resultado corresponde a `data[j + period / 2]`; as `period / 2`
+/// pontas de cada lado ficam de fora. Exige `data.len() >= period + 1`.
+fn centered_moving_average(data: &[f64], period: usize) -> Vec<f64> {
+    let moving = rolling_welford(data, period, |mean, _| mean);
+    if period % 2 == 1 {
+        moving
+    } else {
+        moving.windows(2).map(|pair| (pair[0] + pair[1]) / 2.0).collect()
+    }
+}
+
+/// Índices sazonais pelo método clássico da razão (ou diferença) para a média móvel centrada
+///
+/// `data[i]` pertence à fase `(start_phase + i) % period`; o índice `k` do resultado é o da
+/// fase `k`. Os índices são normalizados para somar zero (aditivo) ou ter média 1
+/// (multiplicativo). Exige ao menos dois períodos completos.
+pub fn seasonal_indices(
+    data: &[f64],
+    period: usize,
+    start_phase: usize,
+    model: SeasonalModel,
+) -> Result<Vec<f64>, TimeSeriesError> {
+    validate_seasonal_phase(period, start_phase)?;
+    if data.len() < 2 * period {
+        return Err(TimeSeriesError::insufficient_data(2 * period, data.len()));
+    }
+    match model {
+        SeasonalModel::Additive => validate_finite(data)?,
+        SeasonalModel::Multiplicative => validate_positive(data)?,
+    }
+
+    let half = period / 2;
+    let trend = centered_moving_average(data, period);
+
+    let mut sums = vec![0.0; period];
+    let mut counts = vec![0usize; period];
+    for (i, (&y, &trend)) in data[half..].iter().zip(&trend).enumerate() {
+        let phase = (start_phase + half + i) % period;
+        sums[phase] += match model {
+            SeasonalModel::Additive => y - trend,
+            SeasonalModel::Multiplicative => y / trend,
+        };
+        counts[phase] += 1;
+    }
+
+    let mut indices: Vec<f64> = sums.iter().zip(&counts).map(|(s, &c)| s / c as f64).collect();
+    let mean = indices.iter().sum::<f64>() / period as f64;
+    for index in indices.iter_mut() {
+        match model {
+            SeasonalModel::Additive => *index -= mean,
+            SeasonalModel::Multiplicative => *index /= mean,
+        }
+    }
+    Ok(indices)
+}
+
+fn apply_seasonality(
+    data: &[f64],
+    indices: &[f64],
+    start_phase: usize,
+    f: impl Fn(f64, f64) -> f64,
+) -> Result<Vec<f64>, TimeSeriesError> {
+    validate_seasonal_phase(indices.len(), start_phase)?;
+    validate_finite(indices)?;
+    Ok(data
+        .iter()
+        .enumerate()
+        .map(|(i, &y)| f(y, indices[(start_phase + i) % indices.len()]))
+        .collect())
+}
+
+/// Remove a sazonalidade: subtrai (aditivo) ou divide (multiplicativo) pelo índice da fase
+/// de cada ponto, com `data[0]` na fase `start_phase`
+pub fn seasonally_adjust(
+    data: &[f64],
+    indices: &[f64],
+    start_phase: usize,
+    model: SeasonalModel,
+) -> Result<Vec<f64>, TimeSeriesError> {
+    if model == SeasonalModel::Multiplicative && indices.contains(&0.0) {
+        return Err(TimeSeriesError::invalid_parameter(
+            "indices",
+            LocalizedText::new("índice multiplicativo nulo", "zero multiplicative index"),
+        ));
+    }
+    apply_seasonality(data, indices, start_phase, |y, index| match model {
+        SeasonalModel::Additive => y - index,
+        SeasonalModel::Multiplicative => y / index,
+    })
+}
+
+/// Reaplica a sazonalidade a uma previsão de tendência, com `forecast[0]` na fase
+/// `start_phase` (inverso de [`seasonally_adjust`])
+pub fn reapply_seasonality(
+    forecast: &[f64],
+    indices: &[f64],
+    start_phase: usize,
+    model: SeasonalModel,
+) -> Result<Vec<f64>, TimeSeriesError> {
+    apply_seasonality(forecast, indices, start_phase, |y, index| match model {
+        SeasonalModel::Additive => y + index,
+        SeasonalModel::Multiplicative => y * index,
+    })
+}
+
+/// Previsão sazonal: tendência linear ajustada à média móvel centrada, mais os índices sazonais
+///
+/// Decompõe a série com [`seasonal_indices`] (fase 0 no primeiro ponto), ajusta
+/// [`linear_regression`] à média móvel centrada (sem as pontas indefinidas), prolonga a
+/// tendência por `periods` períodos e reaplica os índices com [`reapply_seasonality`]. Os
+/// erros da decomposição (dados insuficientes, valores não positivos no modelo
+/// multiplicativo) são propagados.
+pub fn forecast_decomposed(
+    data: &[f64],
+    period: usize,
+    periods: usize,
+    model: SeasonalModel,
+) -> Result<Vec<f64>, TimeSeriesError> {
+    let indices = seasonal_indices(data, period, 0, model)?;
+    let half = period / 2;
+    let trend = linear_regression(&centered_moving_average(data, period))?;
+    // A regressão usa x = 0 no ponto `half` da série original
+    let n = data.len();
+    let extended: Vec<f64> = trend.predict_range(n - half..n - half + periods).collect();
+    reapply_seasonality(&extended, &indices, n % period, model)
+}
+
+/// Decomposição clássica de uma série em tendência, sazonalidade e resíduo
+///
+/// No modelo aditivo `y = tendência + sazonal + resíduo`; no multiplicativo,
+/// `y = tendência · sazonal · resíduo`. A tendência é a média móvel centrada e fica
+/// indefinida (NaN) nas `period / 2` pontas de cada lado, assim como o resíduo.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Decomposition {
+    pub model: SeasonalModel,
+    pub period: usize,
+    pub trend: Vec<f64>,
+    pub seasonal: Vec<f64>,
+    pub residual: Vec<f64>,
+}
+
+/// Decompõe a série com [`seasonal_indices`] (fase 0 no primeiro ponto) e a média móvel centrada
+pub fn decompose(data: &[f64], period: usize, model: SeasonalModel) -> Result<Decomposition, TimeSeriesError> {
+    let indices = seasonal_indices(data, period, 0, model)?;
+    let half = period / 2;
+    let mut trend = vec![f64::NAN; data.len()];
+    for (slot, value) in trend[half..].iter_mut().zip(centered_moving_average(data, period)) {
+        *slot = value;
+    }
+    let seasonal: Vec<f64> = (0..data.len()).map(|i| indices[i % period]).collect();
+    let residual = data
+        .iter()
+        .zip(trend.iter().zip(&seasonal))
+        .map(|(&y, (&t, &s))| match model {
+            SeasonalModel::Additive => y - t - s,
+            SeasonalModel::Multiplicative => y / (t * s),
+        })
+        .collect();
+    Ok(Decomposition {
+        model,
+        period,
+        trend,
+        seasonal,
+        residual,
+    })
+}
+
+/// `max(0, 1 - Var(R) / Var(C + R))` nos pontos em que a tendência está definida
+///
+/// No modelo multiplicativo os componentes entram em logaritmo, o que torna a
+/// decomposição aditiva. Se o desvio de `C + R` for desprezível frente à escala da série
+/// de entrada (só arredondamento, como na sazonalidade de uma reta perfeita), não há
+/// componente a medir e o resultado é 0.
+fn component_strength(decomposition: &Decomposition, component: &[f64]) -> f64 {
+    let to_additive = |v: f64| match decomposition.model {
+        SeasonalModel::Additive => v,
+        SeasonalModel::Multiplicative => v.ln(),
+    };
+    // A série de entrada é a soma dos componentes aditivos; em logaritmo o arredondamento é
+    // relativo, daí a escala mínima 1 no modelo multiplicativo
+    let reconstructed: Vec<f64> = decomposition
+        .trend
+        .iter()
+        .zip(&decomposition.seasonal)
+        .zip(&decomposition.residual)
+        .map(|((&t, &s), &r)| to_additive(t) + to_additive(s) + to_additive(r))
+        .filter(|v| v.is_finite())
+        .collect();
+    let scale = match decomposition.model {
+        SeasonalModel::Additive => max_abs(&reconstructed),
+        SeasonalModel::Multiplicative => max_abs(&reconstructed).max(1.0),
+    };
+    let (residual, combined): (Vec<f64>, Vec<f64>) = decomposition
+        .residual
+        .iter()
+        .zip(component)
+        .filter(|(r, c)| r.is_finite() && c.is_finite())
+        .map(|(&r, &c)| (to_additive(r), to_additive(r) + to_additive(c)))
+        .unzip();
+    if combined.len() < 2 {
+        return 0.0;
+    }
+    let variance = |values: &[f64]| {
+        let mean = kahan_sum(values.iter().copied()) / values.len() as f64;
+        kahan_sum(values.iter().map(|v| (v - mean) * (v - mean))) / values.len() as f64
+    };
+    let combined_variance = variance(&combined);
+    if approx_zero(combined_variance.sqrt(), scale) {
+        return 0.0;
+    }
+    (1.0 - variance(&residual) / combined_variance).clamp(0.0, 1.0)
+}
+
+/// Força da tendência no estilo STL: `max(0, 1 - Var(resíduo) / Var(tendência + resíduo))`
+///
+/// Perto de 1 quando a tendência domina o ruído; 0 também no caso indefinido (variância
+/// nula de `tendência + resíduo`). Os valores ficam sempre em `[0, 1]`.
+pub fn trend_strength(decomposition: &Decomposition) -> f64 {
+    component_strength(decomposition, &decomposition.trend)
+}
+
+/// Força da sazonalidade no estilo STL: `max(0, 1 - Var(resíduo) / Var(sazonal + resíduo))`
+///
+/// Mesmas convenções de [`trend_strength`].
+pub fn seasonal_strength(decomposition: &Decomposition) -> f64 {
+    component_strength(decomposition, &decomposition.seasonal)
+}
+
+/// Resumo de [`series_characteristics`] para classificar muitas séries
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SeriesCharacteristics {
+    pub trend_strength: f64,
+    pub seasonal_strength: f64,
+    /// Período dominante de [`dominant_period`] na série sem a tendência, procurado até
+    /// `2·period` (limitado a metade dos pontos sem tendência definida)
+    pub dominant_period: Option<usize>,
+}
+
+/// Decompõe a série aditivamente com `period` e resume as forças de tendência e
+/// sazonalidade e o período dominante
+pub fn series_characteristics(data: &[f64], period: usize) -> Result<SeriesCharacteristics, TimeSeriesError> {
+    let decomposition = decompose(data, period, SeasonalModel::Additive)?;
+    let detrended: Vec<f64> = data
+        .iter()
+        .zip(&decomposition.trend)
+        .filter(|(_, t)| t.is_finite())
+        .map(|(y, t)| y - t)
+        .collect();
+    let max_lag = (2 * period).min(detrended.len() / 2);
+    let spread = detrended.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b))
+        - detrended.iter().fold(f64::INFINITY, |a, &b| a.min(b));
+    let dominant = if max_lag < 3 || approx_zero(spread, max_abs(data)) {
+        None
+    } else {
+        dominant_period(&detrended, max_lag)?
+    };
+    Ok(SeriesCharacteristics {
+        trend_strength: trend_strength(&decomposition),
+        seasonal_strength: seasonal_strength(&decomposition),
+        dominant_period: dominant,
+    })
+}
+
+#[cfg(test)]
+mod testes {
+    use super::*;
+    use crate::testes::assert_approx_eq;
+
+    #[test]
+    fn test_indices_sazonais() {
+        let pattern = [2.0, -1.0, 0.5, -1.5];
+        // a série começa na fase 1
+        let data: Vec<f64> = (0..16).map(|i| 10.0 + 0.5 * i as f64 + pattern[(1 + i) % 4]).collect();
+        let indices = seasonal_indices(&data, 4, 1, SeasonalModel::Additive).unwrap();
+        for (index, expected) in indices.iter().zip(&pattern) {
+            assert_approx_eq(*index, *expected, 1e-9);
+        }
+        let adjusted = seasonally_adjust(&data, &indices, 1, SeasonalModel::Additive).unwrap();
+        assert_approx_eq(linear_regression(&adjusted).unwrap().r_squared, 1.0, 1e-9);
+        let restored = reapply_seasonality(&adjusted, &indices, 1, SeasonalModel::Additive).unwrap();
+        for (r, d) in restored.iter().zip(&data) {
+            assert_approx_eq(*r, *d, 1e-12);
+        }
+
+        let factors = [1.2, 0.8, 1.1];
+        let data: Vec<f64> = (0..12).map(|i| (100.0 + 2.0 * i as f64) * factors[i % 3]).collect();
+        let indices = seasonal_indices(&data, 3, 0, SeasonalModel::Multiplicative).unwrap();
+        assert_approx_eq(indices.iter().sum::<f64>() / 3.0, 1.0, 1e-12);
+        let adjusted = seasonally_adjust(&data, &indices, 0, SeasonalModel::Multiplicative).unwrap();
+        let restored = reapply_seasonality(&adjusted, &indices, 0, SeasonalModel::Multiplicative).unwrap();
+        for (r, d) in restored.iter().zip(&data) {
+            assert_approx_eq(*r, *d, 1e-9);
+        }
+
+        assert!(seasonal_indices(&data[..5], 3, 0, SeasonalModel::Additive).is_err());
+        assert!(seasonal_indices(&data, 3, 3, SeasonalModel::Additive).is_err());
+    }
+
+    #[test]
+    fn test_forecast_decomposed() {
+        let data = generators::seasonal_series(96, 12, 10.0, 0.5, 0.5, 3).unwrap();
+        let test_size = 12;
+        let (train, test) = train_test_split(&data, test_size).unwrap();
+
+        let forecast = forecast_decomposed(train, 12, test_size, SeasonalModel::Additive).unwrap();
+        assert_eq!(forecast.len(), test_size);
+        let seasonal_mse = calculate_mse(test, &forecast).unwrap();
+        let linear_mse = backtest(&data, test_size).unwrap().mse;
+        assert!(seasonal_mse < linear_mse / 10.0, "sazonal {} vs linear {}", seasonal_mse, linear_mse);
+
+        // Sem ruído e sem sazonalidade, a tendência é reproduzida exatamente
+        let line: Vec<f64> = (0..24).map(|t| 5.0 + 2.0 * t as f64).collect();
+        let extended = forecast_decomposed(&line, 4, 2, SeasonalModel::Multiplicative).unwrap();
+        assert_approx_eq(extended[0], 53.0, 1e-9);
+        assert_approx_eq(extended[1], 55.0, 1e-9);
+
+        assert!(forecast_decomposed(&data[..20], 12, 3, SeasonalModel::Additive).is_err());
+        assert!(forecast_decomposed(&[-1.0; 30], 4, 3, SeasonalModel::Multiplicative).is_err());
+    }
+
+    #[test]
+    fn test_forca_de_tendencia_e_sazonalidade() {
+        let seasonal = generators::seasonal_series(120, 12, 10.0, 0.0, 0.5, 5).unwrap();
+        let characteristics = series_characteristics(&seasonal, 12).unwrap();
+        assert!(characteristics.seasonal_strength > 0.95, "{:?}", characteristics);
+        assert!(characteristics.trend_strength < 0.3, "{:?}", characteristics);
+        assert_eq!(characteristics.dominant_period, Some(12));
+
+        let trend = generators::linear_series(120, 0.5, 10.0, 0.5, 5).unwrap();
+        let characteristics = series_characteristics(&trend, 12).unwrap();
+        assert!(characteristics.trend_strength > 0.95, "{:?}", characteristics);
+        // Só ruído além da tendência: os 12 índices estimados em ~9 ciclos guardam cerca de
+        // 1/9 da variância do ruído, o que dá força sazonal esperada perto de 0.1
+        assert!(characteristics.seasonal_strength < 0.15, "{:?}", characteristics);
+
+        // Reta sem ruído: o resto da decomposição é só arredondamento
+        for (offset, slope) in [(100.0, 0.1), (1e-9, 1e-12), (1e12, 3e9)] {
+            let line: Vec<f64> = (0..48).map(|i| offset + slope * i as f64).collect();
+            let characteristics = series_characteristics(&line, 12).unwrap();
+            assert_eq!(characteristics.seasonal_strength, 0.0, "{:?}", characteristics);
+            assert_eq!(characteristics.dominant_period, None);
+            assert!(characteristics.trend_strength > 0.99, "{:?}", characteristics);
+        }
+
+        let decomposition = decompose(&trend, 12, SeasonalModel::Additive).unwrap();
+        assert!(decomposition.trend[..6].iter().all(|v| v.is_nan()));
+        assert!(decomposition.trend[6..114].iter().all(|v| v.is_finite()));
+        // Série constante: variância nula, caso indefinido tratado como 0
+        let flat = decompose(&[3.0; 24], 4, SeasonalModel::Additive).unwrap();
+        assert_eq!(trend_strength(&flat), 0.0);
+        assert_eq!(seasonal_strength(&flat), 0.0);
+    }
+}
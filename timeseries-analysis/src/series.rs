@@ -0,0 +1,882 @@
+//! Séries com datas, defasagens e dados faltantes
+//!
+//! Calendário ([`Date`]), séries com carimbo de tempo ([`TimeSeries`]), defasagens,
+//! alinhamento de séries de tamanhos diferentes, acumulados e preenchimento de lacunas.
+//!
+//! ```
+//! use timeseries_analysis::series::{fill_missing, lag, FillMethod};
+//!
+//! let filled = fill_missing(&[1.0, f64::NAN, 3.0], FillMethod::LinearInterpolate).unwrap();
+//! assert_eq!(filled, vec![1.0, 2.0, 3.0]);
+//! assert_eq!(lag(&filled, 1), vec![None, Some(1.0), Some(2.0)]);
+//! ```
+
+use crate::*;
+
+/// Data do calendário gregoriano (proléptico), sem fuso horário
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    pub year: i32,
+    /// 1 a 12
+    pub month: u32,
+    /// 1 ao último dia do mês
+    pub day: u32,
+}
+
+/// Ano bissexto no calendário gregoriano
+pub fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+/// Número de dias do mês (`month` de 1 a 12)
+pub fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl Date {
+    /// Cria uma data, validando mês e dia
+    pub fn new(year: i32, month: u32, day: u32) -> Result<Self, TimeSeriesError> {
+        if !(1..=12).contains(&month) {
+            return Err(TimeSeriesError::invalid_parameter(
+                "month",
+                LocalizedText::new("deve estar entre 1 e 12", "must be between 1 and 12"),
+            ));
+        }
+        if day == 0 || day > days_in_month(year, month) {
+            return Err(TimeSeriesError::invalid_parameter(
+                "day",
+                LocalizedText::new(
+                    format!("{}-{:02} tem {} dias", year, month, days_in_month(year, month)),
+                    format!("{}-{:02} has {} days", year, month, days_in_month(year, month)),
+                ),
+            ));
+        }
+        Ok(Date { year, month, day })
+    }
+
+    /// Dias desde 1970-01-01 (negativo antes dessa data)
+    fn days_from_epoch(&self) -> i64 {
+        // Algoritmo days_from_civil de Howard Hinnant, com anos começando em março
+        let year = i64::from(self.year) - i64::from(self.month <= 2);
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let month = i64::from(self.month);
+        let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(self.day) - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    fn from_days_from_epoch(days: i64) -> Self {
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let day_of_era = z - era * 146_097;
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let mp = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+        Date {
+            year: year as i32,
+            month,
+            day,
+        }
+    }
+
+    /// Data (UTC) de um instante em segundos desde 1970-01-01T00:00:00Z
+    pub fn from_timestamp(timestamp: i64) -> Self {
+        Date::from_days_from_epoch(timestamp.div_euclid(SECONDS_PER_DAY))
+    }
+
+    /// Instante, em segundos desde a época Unix, da meia-noite UTC desta data
+    pub fn to_timestamp(&self) -> i64 {
+        self.days_from_epoch() * SECONDS_PER_DAY
+    }
+
+    /// Soma `days` dias (aceita valores negativos)
+    pub fn add_days(&self, days: i64) -> Self {
+        Date::from_days_from_epoch(self.days_from_epoch() + days)
+    }
+
+    /// Soma `months` meses, limitando o dia ao último dia do mês de destino
+    /// (31/jan + 1 mês = 28 ou 29/fev)
+    pub fn add_months(&self, months: i64) -> Self {
+        let total = i64::from(self.year) * 12 + i64::from(self.month) - 1 + months;
+        let year = total.div_euclid(12) as i32;
+        let month = total.rem_euclid(12) as u32 + 1;
+        Date {
+            year,
+            month,
+            day: self.day.min(days_in_month(year, month)),
+        }
+    }
+
+    /// Data `steps` períodos de `freq` depois desta
+    ///
+    /// Cada passo é contado a partir desta data, então o dia limitado num mês curto não
+    /// se propaga: 31/jan, 29/fev, 31/mar...
+    pub fn step(&self, freq: Frequency, steps: i64) -> Self {
+        match freq {
+            Frequency::Daily => self.add_days(steps),
+            Frequency::Weekly => self.add_days(7 * steps),
+            Frequency::Monthly => self.add_months(steps),
+            Frequency::Quarterly => self.add_months(3 * steps),
+            Frequency::Yearly => self.add_months(12 * steps),
+        }
+    }
+}
+
+impl TryFrom<(i32, u32, u32)> for Date {
+    type Error = TimeSeriesError;
+
+    fn try_from((year, month, day): (i32, u32, u32)) -> Result<Self, Self::Error> {
+        Date::new(year, month, day)
+    }
+}
+
+/// Formato ISO 8601 (`AAAA-MM-DD`)
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// Segundos em um dia civil (sem segundos intercalares, como no tempo Unix)
+pub const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Intervalo entre observações consecutivas
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Quarterly,
+    Yearly,
+}
+
+/// Rótulos ISO dos `count` períodos a partir de `start` (inclusive)
+pub fn label_periods(start: Date, freq: Frequency, count: usize) -> Vec<String> {
+    (0..count).map(|i| start.step(freq, i as i64).to_string()).collect()
+}
+
+/// Período do calendário civil usado por [`TimeSeries::aggregate_by_period`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CalendarPeriod {
+    Day,
+    /// Semana ISO, de segunda a domingo
+    Week,
+    Month,
+    /// Trimestres começando em janeiro, abril, julho e outubro
+    Quarter,
+    Year,
+}
+
+impl CalendarPeriod {
+    /// Dia (desde a época) em que começa o período que contém `days`
+    fn start_of(self, days: i64) -> i64 {
+        let date = Date::from_days_from_epoch(days);
+        let first = |month: u32| Date { year: date.year, month, day: 1 }.days_from_epoch();
+        match self {
+            CalendarPeriod::Day => days,
+            // 1970-01-01 foi uma quinta-feira: (days + 3) mod 7 é 0 às segundas
+            CalendarPeriod::Week => days - (days + 3).rem_euclid(7),
+            CalendarPeriod::Month => first(date.month),
+            CalendarPeriod::Quarter => first(date.month - (date.month - 1) % 3),
+            CalendarPeriod::Year => first(1),
+        }
+    }
+
+    /// Início do período seguinte ao que começa em `start`
+    fn next_start(self, start: i64) -> i64 {
+        let date = Date::from_days_from_epoch(start);
+        match self {
+            CalendarPeriod::Day => start + 1,
+            CalendarPeriod::Week => start + 7,
+            CalendarPeriod::Month => date.add_months(1).days_from_epoch(),
+            CalendarPeriod::Quarter => date.add_months(3).days_from_epoch(),
+            CalendarPeriod::Year => date.add_months(12).days_from_epoch(),
+        }
+    }
+}
+
+/// Série com instantes explícitos: `timestamps[i]` é o instante de `values[i]`
+///
+/// Os instantes são inteiros (por exemplo, segundos desde a época Unix) em ordem
+/// estritamente crescente. Os valores podem conter NaN para observações ausentes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeSeries {
+    timestamps: Vec<i64>,
+    values: Vec<f64>,
+}
+
+impl TimeSeries {
+    /// Erro se os tamanhos diferirem ou os instantes não forem estritamente crescentes
+    pub fn new(timestamps: Vec<i64>, values: Vec<f64>) -> Result<Self, TimeSeriesError> {
+        if timestamps.len() != values.len() {
+            return Err(TimeSeriesError::LengthMismatch {
+                left: timestamps.len(),
+                right: values.len(),
+            });
+        }
+        if timestamps.windows(2).any(|pair| pair[1] <= pair[0]) {
+            return Err(TimeSeriesError::invalid_parameter(
+                "timestamps",
+                LocalizedText::new(
+                    "devem estar em ordem estritamente crescente",
+                    "must be in strictly increasing order",
+                ),
+            ));
+        }
+        Ok(TimeSeries { timestamps, values })
+    }
+
+    pub fn timestamps(&self) -> &[i64] {
+        &self.timestamps
+    }
+
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Lacunas `(início, fim)` entre observações consecutivas mais afastadas que
+    /// `expected_interval + tolerance`
+    ///
+    /// `início` e `fim` são os instantes observados que delimitam a lacuna. A tolerância
+    /// absorve pequenas irregularidades no espaçamento sem tratá-las como lacunas.
+    pub fn find_gaps(&self, expected_interval: i64, tolerance: i64) -> Vec<(i64, i64)> {
+        let limit = expected_interval.saturating_add(tolerance);
+        self.timestamps
+            .windows(2)
+            .filter(|pair| pair[1] - pair[0] > limit)
+            .map(|pair| (pair[0], pair[1]))
+            .collect()
+    }
+
+    /// Série regular com passo `interval` a partir do primeiro instante
+    ///
+    /// Cada observação vai para o ponto da grade mais próximo, desde que a distância não
+    /// passe de `tolerance`; os instantes sem observação recebem NaN e, com `fill`, são
+    /// preenchidos por [`fill_missing`] (que também preenche NaN já presentes nos valores).
+    /// Erro se uma observação ficar fora da tolerância ou duas caírem no mesmo ponto.
+    pub fn reindex(&self, interval: i64, tolerance: i64, fill: Option<FillMethod>) -> Result<TimeSeries, TimeSeriesError> {
+        if interval <= 0 {
+            return Err(TimeSeriesError::invalid_parameter(
+                "interval",
+                LocalizedText::new("deve ser positivo", "must be positive"),
+            ));
+        }
+        if !(0..=interval / 2).contains(&tolerance) {
+            return Err(TimeSeriesError::invalid_parameter(
+                "tolerance",
+                LocalizedText::new(
+                    "deve estar entre 0 e metade do intervalo",
+                    "must be between 0 and half the interval",
+                ),
+            ));
+        }
+        let (first, last) = match (self.timestamps.first(), self.timestamps.last()) {
+            (Some(&first), Some(&last)) => (first, last),
+            _ => return Err(TimeSeriesError::insufficient_data(1, 0)),
+        };
+
+        let slot_of = |timestamp: i64| ((timestamp - first) + interval / 2) / interval;
+        let slots = slot_of(last) as usize + 1;
+        let mut values = vec![f64::NAN; slots];
+        let mut occupied = vec![false; slots];
+        for (&timestamp, &value) in self.timestamps.iter().zip(&self.values) {
+            let slot = slot_of(timestamp);
+            if (timestamp - (first + slot * interval)).abs() > tolerance {
+                return Err(TimeSeriesError::invalid_parameter(
+                    "tolerance",
+                    LocalizedText::new(
+                        format!("instante {} fora da grade de passo {}", timestamp, interval),
+                        format!("timestamp {} off the grid with step {}", timestamp, interval),
+                    ),
+                ));
+            }
+            let slot = slot as usize;
+            if occupied[slot] {
+                return Err(TimeSeriesError::invalid_parameter(
+                    "interval",
+                    LocalizedText::new(
+                        format!("mais de uma observação no instante {}", first + slot as i64 * interval),
+                        format!("more than one observation at timestamp {}", first + slot as i64 * interval),
+                    ),
+                ));
+            }
+            occupied[slot] = true;
+            values[slot] = value;
+        }
+
+        if let Some(method) = fill {
+            values = fill_missing(&values, method)?;
+        }
+        let timestamps = (0..slots as i64).map(|slot| first + slot * interval).collect();
+        Ok(TimeSeries { timestamps, values })
+    }
+
+    /// Agrega as observações por período do calendário civil (UTC)
+    ///
+    /// Os instantes são interpretados como segundos desde 1970-01-01T00:00:00Z e cada
+    /// período vira um ponto cujo instante é a meia-noite do seu primeiro dia. Valores NaN
+    /// são ignorados; um período só com NaN fica com NaN. Períodos sem observações não
+    /// aparecem no resultado; para incluí-los com zero, veja
+    /// [`aggregate_by_period_filled`](Self::aggregate_by_period_filled).
+    pub fn aggregate_by_period(&self, period: CalendarPeriod, agg: Aggregation) -> Result<TimeSeries, TimeSeriesError> {
+        self.aggregate_calendar(period, agg, false)
+    }
+
+    /// Como [`aggregate_by_period`](Self::aggregate_by_period), mas os períodos sem
+    /// observações entre o primeiro e o último recebem o valor zero
+    pub fn aggregate_by_period_filled(
+        &self,
+        period: CalendarPeriod,
+        agg: Aggregation,
+    ) -> Result<TimeSeries, TimeSeriesError> {
+        self.aggregate_calendar(period, agg, true)
+    }
+
+    fn aggregate_calendar(
+        &self,
+        period: CalendarPeriod,
+        agg: Aggregation,
+        fill_empty: bool,
+    ) -> Result<TimeSeries, TimeSeriesError> {
+        if self.is_empty() {
+            return Err(TimeSeriesError::insufficient_data(1, 0));
+        }
+        let start_of = |timestamp: i64| period.start_of(timestamp.div_euclid(SECONDS_PER_DAY));
+
+        let mut timestamps = Vec::new();
+        let mut values = Vec::new();
+        let mut group = Vec::new();
+        let mut previous: Option<i64> = None;
+        let mut i = 0;
+        while i < self.len() {
+            let start = start_of(self.timestamps[i]);
+            if let (true, Some(previous)) = (fill_empty, previous) {
+                let mut empty = period.next_start(previous);
+                while empty < start {
+                    timestamps.push(empty * SECONDS_PER_DAY);
+                    values.push(0.0);
+                    empty = period.next_start(empty);
+                }
+            }
+
+            // Os instantes são crescentes, então cada período é um trecho contíguo
+            group.clear();
+            while i < self.len() && start_of(self.timestamps[i]) == start {
+                if !self.values[i].is_nan() {
+                    group.push(self.values[i]);
+                }
+                i += 1;
+            }
+            timestamps.push(start * SECONDS_PER_DAY);
+            values.push(if group.is_empty() { f64::NAN } else { agg.apply(&group) });
+            previous = Some(start);
+        }
+        Ok(TimeSeries { timestamps, values })
+    }
+}
+
+/// Soma acumulada: o i-ésimo valor é a soma de `data[..=i]`
+///
+/// Usa soma compensada, então prefixos longos não acumulam erro de arredondamento.
+/// Entrada vazia resulta em um vetor vazio.
+pub fn cumulative_sum(data: &[f64]) -> Vec<f64> {
+    let mut sum = KahanSum::new();
+    data.iter()
+        .map(|&v| {
+            sum.add(v);
+            sum.total()
+        })
+        .collect()
+}
+
+/// Média acumulada: o i-ésimo valor é a média de `data[..=i]`
+///
+/// Atualizada incrementalmente (`m += (x - m)/k`), em O(n).
+pub fn cumulative_mean(data: &[f64]) -> Vec<f64> {
+    let mut mean = 0.0;
+    data.iter()
+        .enumerate()
+        .map(|(i, &v)| {
+            mean += (v - mean) / (i + 1) as f64;
+            mean
+        })
+        .collect()
+}
+
+/// Máximo acumulado: o i-ésimo valor é o máximo de `data[..=i]`
+pub fn running_max(data: &[f64]) -> Vec<f64> {
+    data.iter()
+        .scan(f64::NEG_INFINITY, |max, &v| {
+            *max = max.max(v);
+            Some(*max)
+        })
+        .collect()
+}
+
+/// Mínimo acumulado: o i-ésimo valor é o mínimo de `data[..=i]`
+pub fn running_min(data: &[f64]) -> Vec<f64> {
+    data.iter()
+        .scan(f64::INFINITY, |min, &v| {
+            *min = min.min(v);
+            Some(*min)
+        })
+        .collect()
+}
+
+/// Desloca a série `k` períodos para frente: `lag[i] = data[i - k]`, `None` nos `k` primeiros
+pub fn lag(data: &[f64], k: usize) -> Vec<Option<f64>> {
+    (0..data.len()).map(|i| i.checked_sub(k).map(|j| data[j])).collect()
+}
+
+/// Antecipa a série em `k` períodos: `lead[i] = data[i + k]`, `None` nos `k` últimos
+pub fn lead(data: &[f64], k: usize) -> Vec<Option<f64>> {
+    (0..data.len()).map(|i| data.get(i + k).copied()).collect()
+}
+
+/// Pares alinhados `(x, y) = (data[..n-k], data[k..])`, em que `y[i]` ocorre `k`
+/// períodos depois de `x[i]`
+///
+/// Prontos para correlação ou regressão de `y` contra `x` (modelo autorregressivo de
+/// defasagem `k`). Exige `k < len`.
+pub fn lagged_pairs(data: &[f64], k: usize) -> Result<(Vec<f64>, Vec<f64>), TimeSeriesError> {
+    if k >= data.len() {
+        return Err(TimeSeriesError::insufficient_data(k + 1, data.len()));
+    }
+    Ok((data[..data.len() - k].to_vec(), data[k..].to_vec()))
+}
+
+/// Alinha as séries pelo início: todas começam no índice 0 e as mais curtas recebem
+/// `None` no final, até o tamanho da mais longa
+///
+/// Serve para a série original e a previsão estendida (ajuste seguido do horizonte).
+pub fn align_start(series: &[&[f64]]) -> Vec<Vec<Option<f64>>> {
+    let n = series.iter().map(|s| s.len()).max().unwrap_or(0);
+    series
+        .iter()
+        .map(|s| s.iter().copied().map(Some).chain(core::iter::repeat(None)).take(n).collect())
+        .collect()
+}
+
+/// Alinha as séries pelo fim: todas terminam no mesmo índice e as mais curtas recebem
+/// `None` no início, até o tamanho da mais longa
+///
+/// Serve para resultados de janelas móveis (como [`rolling_mean`]), que perdem os
+/// `window - 1` primeiros pontos.
+pub fn align_end(series: &[&[f64]]) -> Vec<Vec<Option<f64>>> {
+    let n = series.iter().map(|s| s.len()).max().unwrap_or(0);
+    series
+        .iter()
+        .map(|s| core::iter::repeat_n(None, n - s.len()).chain(s.iter().copied().map(Some)).collect())
+        .collect()
+}
+
+/// Faixa de índices em que todas as séries alinhadas têm valor
+///
+/// Considera o primeiro e o último índice em que todas estão definidas; vazia (`0..0`)
+/// se não houver nenhum.
+pub fn common_range(series: &[Vec<Option<f64>>]) -> Range<usize> {
+    let n = series.iter().map(|s| s.len()).min().unwrap_or(0);
+    let defined = |i: usize| series.iter().all(|s| s[i].is_some());
+    match (0..n).position(defined) {
+        Some(start) => start..(start..n).rposition(defined).map_or(start, |last| start + last + 1),
+        None => 0..0,
+    }
+}
+
+/// Valores das séries alinhadas dentro de [`common_range`], prontos para funções que
+/// esperam `&[f64]` de mesmo tamanho (como [`ascii_multi_plot`])
+///
+/// Retorna a faixa usada e uma série por entrada. Índices sem valor dentro da faixa
+/// ficam NaN.
+pub fn values_in_common_range(series: &[Vec<Option<f64>>]) -> (Range<usize>, Vec<Vec<f64>>) {
+    let range = common_range(series);
+    let values = series
+        .iter()
+        .map(|s| s[range.clone()].iter().map(|v| v.unwrap_or(f64::NAN)).collect())
+        .collect();
+    (range, values)
+}
+
+/// Como preencher valores ausentes (NaN) em [`fill_missing`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FillMethod {
+    /// Interpolação linear entre os vizinhos válidos; as pontas copiam o valor válido mais próximo
+    LinearInterpolate,
+    /// Repete o último valor válido; o início copia o primeiro valor válido
+    ForwardFill,
+    /// Repete o próximo valor válido; o final copia o último valor válido
+    BackwardFill,
+}
+
+impl fmt::Display for FillMethod {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            FillMethod::LinearInterpolate => "interpolação linear",
+            FillMethod::ForwardFill => "último valor",
+            FillMethod::BackwardFill => "próximo valor",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Preenche os valores ausentes (NaN) da série
+///
+/// Infinitos não são tratados como ausentes e geram erro. Exige ao menos um valor válido.
+pub fn fill_missing(data: &[f64], method: FillMethod) -> Result<Vec<f64>, TimeSeriesError> {
+    if let Some(index) = data.iter().position(|v| v.is_infinite()) {
+        return Err(TimeSeriesError::NonFiniteValue { index });
+    }
+    let valid: Vec<usize> = (0..data.len()).filter(|&i| !data[i].is_nan()).collect();
+    let (first, last) = match (valid.first(), valid.last()) {
+        (Some(&first), Some(&last)) => (first, last),
+        _ => return Err(TimeSeriesError::insufficient_data(1, 0)),
+    };
+
+    let mut filled = data.to_vec();
+    filled[..first].fill(data[first]);
+    filled[last + 1..].fill(data[last]);
+    for pair in valid.windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+        for (i, value) in filled.iter_mut().enumerate().take(b).skip(a + 1) {
+            *value = match method {
+                FillMethod::LinearInterpolate => {
+                    data[a] + (data[b] - data[a]) * (i - a) as f64 / (b - a) as f64
+                }
+                FillMethod::ForwardFill => data[a],
+                FillMethod::BackwardFill => data[b],
+            };
+        }
+    }
+    Ok(filled)
+}
+
+/// Rodadas de ajuste alternado de tendência e sazonalidade em [`impute_with_model`]
+const IMPUTATION_ITERATIONS: usize = 2;
+
+/// Preenche os valores ausentes (`None`) com o valor esperado de um modelo de tendência
+/// linear mais, com `period`, índices sazonais aditivos
+///
+/// O modelo é ajustado só nos pontos observados, com [`linear_regression_xy`] contra o
+/// índice de cada ponto. Com `period`, tendência e sazonalidade são reestimadas
+/// alternadamente (2 rodadas): a reta é ajustada aos dados sem
+/// a sazonalidade e cada índice é a média dos resíduos da sua fase (`i % period`),
+/// centrada para somar zero; fases sem nenhuma observação ficam com índice zero. Os
+/// valores observados são mantidos. Exige ao menos 3 valores observados, todos finitos.
+pub fn impute_with_model(data: &[Option<f64>], period: Option<usize>) -> Result<Vec<f64>, TimeSeriesError> {
+    if period.is_some_and(|p| p < 2) {
+        return Err(TimeSeriesError::invalid_parameter(
+            "period",
+            LocalizedText::new("deve ser ao menos 2", "must be at least 2"),
+        ));
+    }
+    let observed: Vec<(usize, f64)> = data.iter().enumerate().filter_map(|(i, v)| v.map(|v| (i, v))).collect();
+    if observed.len() < 3 {
+        return Err(TimeSeriesError::insufficient_data(3, observed.len()));
+    }
+    if let Some(&(index, _)) = observed.iter().find(|(_, v)| !v.is_finite()) {
+        return Err(TimeSeriesError::NonFiniteValue { index });
+    }
+
+    let x: Vec<f64> = observed.iter().map(|&(i, _)| i as f64).collect();
+    let mut seasonal = vec![0.0; period.unwrap_or(1)];
+    let mut trend = linear_regression_xy(&x, &observed.iter().map(|&(_, v)| v).collect::<Vec<_>>())?;
+    if let Some(period) = period {
+        for _ in 0..IMPUTATION_ITERATIONS {
+            let mut sums = vec![(0.0, 0usize); period];
+            for (&(i, value), fitted) in observed.iter().zip(&trend.predictions) {
+                sums[i % period].0 += value - fitted;
+                sums[i % period].1 += 1;
+            }
+            let means: Vec<f64> = sums.iter().map(|&(sum, count)| if count > 0 { sum / count as f64 } else { 0.0 }).collect();
+            let center = kahan_sum(means.iter().copied()) / period as f64;
+            seasonal = means.iter().map(|m| m - center).collect();
+            let adjusted: Vec<f64> = observed.iter().map(|&(i, v)| v - seasonal[i % period]).collect();
+            trend = linear_regression_xy(&x, &adjusted)?;
+        }
+    }
+
+    Ok(data
+        .iter()
+        .enumerate()
+        .map(|(i, value)| value.unwrap_or_else(|| trend.predict(i as f64) + seasonal[i % seasonal.len()]))
+        .collect())
+}
+
+/// Remove os pontos com |z-score| acima de `threshold` (média e desvio populacionais)
+///
+/// Retorna a série sem os outliers e os índices removidos. Série constante não tem outliers.
+pub fn remove_outliers_zscore(data: &[f64], threshold: f64) -> Result<(Vec<f64>, Vec<usize>), TimeSeriesError> {
+    if !(threshold.is_finite() && threshold > 0.0) {
+        return Err(TimeSeriesError::invalid_parameter(
+            "threshold",
+            LocalizedText::new("deve ser finito e positivo", "must be finite and positive"),
+        ));
+    }
+    let stats = calculate_descriptive_stats(data)?;
+    if stats.std_dev == 0.0 {
+        return Ok((data.to_vec(), Vec::new()));
+    }
+
+    let mut kept = Vec::with_capacity(data.len());
+    let mut removed = Vec::new();
+    for (i, &value) in data.iter().enumerate() {
+        if ((value - stats.mean) / stats.std_dev).abs() > threshold {
+            removed.push(i);
+        } else {
+            kept.push(value);
+        }
+    }
+    Ok((kept, removed))
+}
+
+#[cfg(test)]
+mod testes {
+    use super::*;
+    use crate::testes::assert_approx_eq;
+
+    #[test]
+    fn test_acumulados() {
+        let data = [3.0, 1.0, 4.0, 1.0, 5.0];
+        assert_eq!(cumulative_sum(&data), vec![3.0, 4.0, 8.0, 9.0, 14.0]);
+        assert_eq!(cumulative_mean(&data), vec![3.0, 2.0, 8.0 / 3.0, 2.25, 2.8]);
+        assert_eq!(running_max(&data), vec![3.0, 3.0, 4.0, 4.0, 5.0]);
+        assert_eq!(running_min(&data), vec![3.0, 1.0, 1.0, 1.0, 1.0]);
+
+        assert!(cumulative_sum(&[]).is_empty());
+        assert!(cumulative_mean(&[]).is_empty());
+        assert!(running_max(&[]).is_empty());
+        assert!(running_min(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_lag_lead_e_pares() {
+        let data = [1.0, 2.0, 3.0, 4.0];
+        assert_eq!(lag(&data, 1), vec![None, Some(1.0), Some(2.0), Some(3.0)]);
+        assert_eq!(lead(&data, 2), vec![Some(3.0), Some(4.0), None, None]);
+        assert_eq!(lag(&data, 5), vec![None; 4]);
+
+        let (x, y) = lagged_pairs(&data, 1).unwrap();
+        assert_eq!(x, vec![1.0, 2.0, 3.0]);
+        assert_eq!(y, vec![2.0, 3.0, 4.0]);
+        assert_eq!(lagged_pairs(&data, 4), Err(TimeSeriesError::InsufficientData { required: 5, got: 4 }));
+    }
+
+    #[test]
+    fn test_fill_missing_e_outliers() {
+        let data = [f64::NAN, 1.0, f64::NAN, f64::NAN, 4.0, f64::NAN];
+        assert_eq!(fill_missing(&data, FillMethod::LinearInterpolate).unwrap(), vec![1.0, 1.0, 2.0, 3.0, 4.0, 4.0]);
+        assert_eq!(fill_missing(&data, FillMethod::ForwardFill).unwrap(), vec![1.0, 1.0, 1.0, 1.0, 4.0, 4.0]);
+        assert_eq!(fill_missing(&data, FillMethod::BackwardFill).unwrap(), vec![1.0, 1.0, 4.0, 4.0, 4.0, 4.0]);
+        assert!(fill_missing(&[f64::NAN], FillMethod::ForwardFill).is_err());
+
+        let mut data = vec![10.0; 20];
+        data[7] = 100.0;
+        data[3] = 11.0;
+        let (kept, removed) = remove_outliers_zscore(&data, 3.0).unwrap();
+        assert_eq!(removed, vec![7]);
+        assert_eq!(kept.len(), 19);
+    }
+
+    #[test]
+    fn test_datas_calendario() {
+        let jan31 = Date::new(2024, 1, 31).unwrap();
+        assert_eq!(
+            label_periods(jan31, Frequency::Monthly, 4),
+            vec!["2024-01-31", "2024-02-29", "2024-03-31", "2024-04-30"]
+        );
+        assert_eq!(Date::new(2023, 1, 31).unwrap().add_months(1).to_string(), "2023-02-28");
+        assert_eq!(
+            label_periods(Date::new(2024, 2, 27).unwrap(), Frequency::Daily, 4),
+            vec!["2024-02-27", "2024-02-28", "2024-02-29", "2024-03-01"]
+        );
+        assert_eq!(
+            label_periods(Date::new(2023, 12, 25).unwrap(), Frequency::Weekly, 3),
+            vec!["2023-12-25", "2024-01-01", "2024-01-08"]
+        );
+        assert_eq!(
+            label_periods(Date::new(2023, 11, 30).unwrap(), Frequency::Quarterly, 2),
+            vec!["2023-11-30", "2024-02-29"]
+        );
+        assert_eq!(Date::new(2024, 2, 29).unwrap().step(Frequency::Yearly, 1).to_string(), "2025-02-28");
+        assert_eq!(Date::new(2000, 3, 1).unwrap().add_days(-1).to_string(), "2000-02-29");
+        assert!(Date::new(1900, 2, 29).is_err());
+        assert!(Date::try_from((2024, 13, 1)).is_err());
+    }
+
+    #[test]
+    fn test_time_series_lacunas_e_reindexacao() {
+        const HOUR: i64 = 3600;
+        // 12 horas com as horas 3, 7 e 8 ausentes e jitter de alguns segundos
+        let start = 1_700_000_000;
+        let missing = [3, 7, 8];
+        let (timestamps, values): (Vec<i64>, Vec<f64>) = (0..12)
+            .filter(|h| !missing.contains(h))
+            .map(|h| (start + h * HOUR + [0, 5, -4][h as usize % 3], 10.0 + h as f64))
+            .unzip();
+        let series = TimeSeries::new(timestamps, values).unwrap();
+
+        let gaps = series.find_gaps(HOUR, 60);
+        assert_eq!(gaps.len(), 2);
+        assert_eq!(gaps[0], (start + 2 * HOUR - 4, start + 4 * HOUR + 5));
+        assert_eq!(gaps[1], (start + 6 * HOUR, start + 9 * HOUR));
+        // Sem tolerância, o jitter já conta como lacuna
+        assert!(series.find_gaps(HOUR, 0).len() > 2);
+
+        let raw = series.reindex(HOUR, 60, None).unwrap();
+        assert_eq!(raw.len(), 12);
+        assert_eq!(raw.timestamps()[11], start + 11 * HOUR);
+        let nan_slots: Vec<usize> = (0..12).filter(|&i| raw.values()[i].is_nan()).collect();
+        assert_eq!(nan_slots, vec![3, 7, 8]);
+
+        let interpolated = series.reindex(HOUR, 60, Some(FillMethod::LinearInterpolate)).unwrap();
+        for h in 0..12 {
+            assert_approx_eq(interpolated.values()[h], 10.0 + h as f64, 1e-12);
+        }
+        let forward = series.reindex(HOUR, 60, Some(FillMethod::ForwardFill)).unwrap();
+        assert_eq!(&forward.values()[6..9], &[16.0, 16.0, 16.0]);
+
+        assert!(series.reindex(HOUR, 2, None).is_err());
+        assert!(TimeSeries::new(vec![2, 1], vec![0.0, 0.0]).is_err());
+    }
+
+    #[test]
+    fn test_alinhamento_de_series() {
+        let raw = [1.0, 2.0, 4.0, 5.0, 7.0, 8.0];
+        let smooth = rolling_mean(&raw, 3).unwrap();
+        let result = linear_regression(&raw).unwrap();
+        let mut extended = result.predictions.clone();
+        extended.extend(predict_future(&result, 2));
+
+        // Média móvel alinhada pelo fim com a série original
+        let by_end = align_end(&[&raw, &smooth]);
+        assert_eq!(by_end[1][..2], [None, None]);
+        assert_approx_eq(by_end[1][2].unwrap(), 7.0 / 3.0, 1e-12);
+        assert_eq!(common_range(&by_end), 2..6);
+
+        // Ajuste com o horizonte de previsão alinhado pelo início
+        let by_start = align_start(&[&raw, &extended]);
+        assert_eq!(by_start[0].len(), 8);
+        assert_eq!(by_start[0][6..], [None, None]);
+        assert_eq!(common_range(&by_start), 0..6);
+
+        let (report, skipped) = evaluate_aligned(&by_end[0], &by_end[1]).unwrap();
+        assert_eq!(skipped, 2);
+        assert_approx_eq(report.mse, calculate_mse(&raw[2..], &smooth).unwrap(), 1e-12);
+
+        let (range, values) = values_in_common_range(&by_end);
+        assert_eq!(range, 2..6);
+        assert_eq!(values[0], raw[2..].to_vec());
+        assert!(ascii_multi_plot(&[("Dados", &values[0]), ("Media movel", &values[1])], "x", &PlotConfig::default()).is_ok());
+
+        assert_eq!(common_range(&[vec![Some(1.0), None], vec![None, Some(2.0)]]), 0..0);
+        assert!(evaluate_aligned(&[None], &[Some(1.0)]).is_err());
+    }
+
+    #[test]
+    fn test_agregacao_por_mes_com_29_de_fevereiro() {
+        let at = |y, m, d, hour: i64| Date::new(y, m, d).unwrap().to_timestamp() + hour * 3600;
+        let series = TimeSeries::new(
+            vec![at(2024, 1, 31, 23), at(2024, 2, 28, 0), at(2024, 2, 29, 12), at(2024, 3, 1, 0), at(2024, 4, 15, 6)],
+            vec![1.0, 2.0, 4.0, 8.0, 16.0],
+        )
+        .unwrap();
+
+        let monthly = series.aggregate_by_period(CalendarPeriod::Month, Aggregation::Sum).unwrap();
+        let months: Vec<String> = monthly.timestamps().iter().map(|&t| Date::from_timestamp(t).to_string()).collect();
+        assert_eq!(months, ["2024-01-01", "2024-02-01", "2024-03-01", "2024-04-01"]);
+        assert_eq!(monthly.values(), &[1.0, 6.0, 8.0, 16.0]);
+
+        // 29/fev pertence ao 1º trimestre; abril abre o 2º
+        let quarterly = series.aggregate_by_period(CalendarPeriod::Quarter, Aggregation::Max).unwrap();
+        assert_eq!(quarterly.timestamps(), &[at(2024, 1, 1, 0), at(2024, 4, 1, 0)]);
+        assert_eq!(quarterly.values(), &[8.0, 16.0]);
+
+        let daily = series.aggregate_by_period(CalendarPeriod::Day, Aggregation::Mean).unwrap();
+        assert_eq!(daily.timestamps()[2], at(2024, 2, 29, 0));
+        assert_eq!(daily.len(), 5);
+        assert!(TimeSeries::new(vec![], vec![]).unwrap().aggregate_by_period(CalendarPeriod::Day, Aggregation::Sum).is_err());
+    }
+
+    #[test]
+    fn test_agregacao_na_virada_do_ano() {
+        let at = |y, m, d| Date::new(y, m, d).unwrap().to_timestamp();
+        // 2023-12-25 e 2024-01-01 são segundas-feiras; 2023-12-31 é domingo
+        let series = TimeSeries::new(
+            vec![at(2023, 11, 30), at(2023, 12, 25), at(2023, 12, 31), at(2024, 1, 1), at(2024, 3, 5)],
+            vec![1.0, 2.0, f64::NAN, 5.0, 7.0],
+        )
+        .unwrap();
+
+        let weekly = series.aggregate_by_period(CalendarPeriod::Week, Aggregation::Mean).unwrap();
+        assert_eq!(weekly.timestamps(), &[at(2023, 11, 27), at(2023, 12, 25), at(2024, 1, 1), at(2024, 3, 4)]);
+        assert_eq!(weekly.values(), &[1.0, 2.0, 5.0, 7.0]);
+
+        let yearly = series.aggregate_by_period(CalendarPeriod::Year, Aggregation::Sum).unwrap();
+        assert_eq!(yearly.timestamps(), &[at(2023, 1, 1), at(2024, 1, 1)]);
+        assert_eq!(yearly.values(), &[3.0, 12.0]);
+
+        // Fevereiro sem observações: ausente, ou zero com a variante preenchida
+        let monthly = series.aggregate_by_period(CalendarPeriod::Month, Aggregation::Sum).unwrap();
+        assert_eq!(monthly.len(), 4);
+        let filled = series.aggregate_by_period_filled(CalendarPeriod::Month, Aggregation::Sum).unwrap();
+        assert_eq!(filled.timestamps(), &[at(2023, 11, 1), at(2023, 12, 1), at(2024, 1, 1), at(2024, 2, 1), at(2024, 3, 1)]);
+        assert_eq!(filled.values(), &[1.0, 2.0, 5.0, 0.0, 7.0]);
+
+        // Período só com NaN continua presente, com NaN
+        let daily = series.aggregate_by_period(CalendarPeriod::Day, Aggregation::Sum).unwrap();
+        assert!(daily.values()[2].is_nan());
+        assert_eq!(Date::from_timestamp(-1).to_string(), "1969-12-31");
+    }
+
+    #[test]
+    fn test_imputacao_por_modelo_supera_interpolacao() {
+        let truth: Vec<f64> = (0..72)
+            .map(|t| 50.0 + 0.8 * t as f64 + 10.0 * (2.0 * std::f64::consts::PI * t as f64 / 12.0).sin())
+            .collect();
+        let missing = |i: usize| (30..42).contains(&i) || i == 5 || i == 61;
+        let data: Vec<Option<f64>> = truth.iter().enumerate().map(|(i, &v)| if missing(i) { None } else { Some(v) }).collect();
+        let imputed = impute_with_model(&data, Some(12)).unwrap();
+        let with_nan: Vec<f64> = data.iter().map(|v| v.unwrap_or(f64::NAN)).collect();
+        let interpolated = fill_missing(&with_nan, FillMethod::LinearInterpolate).unwrap();
+
+        let gap_error = |filled: &[f64]| (30..42).map(|i| (filled[i] - truth[i]).abs()).sum::<f64>() / 12.0;
+        assert!(gap_error(&imputed) < 0.1, "erro do modelo {}", gap_error(&imputed));
+        assert!(gap_error(&imputed) < gap_error(&interpolated) / 10.0);
+        assert_approx_eq(imputed[5], truth[5], 0.1);
+        // observados ficam intactos
+        assert_eq!(imputed[0], truth[0]);
+        assert_eq!(imputed.len(), truth.len());
+
+        // sem período, só a tendência
+        let linear = impute_with_model(&[Some(1.0), None, Some(3.0), None, Some(5.0)], None).unwrap();
+        assert_approx_eq(linear[3], 4.0, 1e-12);
+    }
+
+    #[test]
+    fn test_imputacao_por_modelo_erros() {
+        assert!(impute_with_model(&[None, None, None], None).is_err());
+        assert!(impute_with_model(&[Some(1.0), None, Some(2.0)], None).is_err());
+        assert!(matches!(
+            impute_with_model(&[Some(1.0), Some(f64::INFINITY), Some(2.0)], None),
+            Err(TimeSeriesError::NonFiniteValue { index: 1 })
+        ));
+        assert!(impute_with_model(&[Some(1.0), Some(2.0), Some(3.0)], Some(1)).is_err());
+    }
+}
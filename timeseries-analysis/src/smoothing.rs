@@ -0,0 +1,986 @@
+//! Suavização e janelas móveis
+//!
+//! Médias e desvios móveis, bandas, média exponencial, filtros (mediana, gaussiano,
+//! Savitzky-Golay, Hodrick–Prescott), LOESS e o filtro de Kalman de tendência local.
+//!
+//! ```
+//! use timeseries_analysis::smoothing::{exponential_moving_average, rolling_mean};
+//!
+//! assert_eq!(rolling_mean(&[1.0, 2.0, 3.0, 4.0], 2).unwrap(), vec![1.5, 2.5, 3.5]);
+//! assert_eq!(exponential_moving_average(&[2.0, 4.0], 0.5).unwrap(), vec![2.0, 3.0]);
+//! ```
+
+use crate::*;
+
+/// Suavização LOESS: regressão local ponderada (núcleo tricúbico) em cada índice
+///
+/// Cada ponto é estimado a partir dos `ceil(span·n)` vizinhos mais próximos, com peso
+/// `(1 - (d/h)³)³`, onde `h` é a distância ao vizinho mais distante mais um período (de
+/// modo que todos os vizinhos tenham peso positivo). `degree` 0 usa a média ponderada
+/// local e `degree` 1 a reta ponderada local. Exige `span` em (0, 1] e vizinhanças com
+/// ao menos `degree + 1` pontos.
+pub fn loess(data: &[f64], span: f64, degree: usize) -> Result<Vec<f64>, TimeSeriesError> {
+    if !(span > 0.0 && span <= 1.0) {
+        return Err(TimeSeriesError::invalid_parameter(
+            "span",
+            LocalizedText::new("deve estar em (0, 1]", "must be in (0, 1]"),
+        ));
+    }
+    if degree > 1 {
+        return Err(TimeSeriesError::invalid_parameter(
+            "degree",
+            LocalizedText::new("deve ser 0 ou 1", "must be 0 or 1"),
+        ));
+    }
+    validate_finite(data)?;
+    let n = data.len();
+    let neighbors = ((span * n as f64).ceil() as usize).min(n);
+    if neighbors < degree + 1 {
+        return Err(TimeSeriesError::insufficient_data(degree + 1, neighbors));
+    }
+
+    Ok((0..n)
+        .map(|i| {
+            // Janela de `neighbors` índices o mais centrada possível em i
+            let start = i.saturating_sub(neighbors / 2).min(n - neighbors);
+            let end = start + neighbors;
+            let h = (i - start).max(end - 1 - i) as f64 + 1.0;
+
+            let (mut sw, mut swx, mut swy, mut swxx, mut swxy) = (0.0, 0.0, 0.0, 0.0, 0.0);
+            for (j, &y) in data.iter().enumerate().take(end).skip(start) {
+                let x = j as f64 - i as f64;
+                let u = x.abs() / h;
+                let w = (1.0 - u * u * u).powi(3);
+                sw += w;
+                swx += w * x;
+                swy += w * y;
+                swxx += w * x * x;
+                swxy += w * x * y;
+            }
+
+            let denominator = sw * swxx - swx * swx;
+            if degree == 0 || denominator.abs() < f64::EPSILON * sw * swxx {
+                swy / sw
+            } else {
+                // Com x centrado em i, a estimativa é o intercepto da reta local
+                let slope = (sw * swxy - swx * swy) / denominator;
+                (swy - slope * swx) / sw
+            }
+        })
+        .collect())
+}
+
+/// Resultado de [`kalman_trend_filter`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct KalmanResult {
+    /// Nível filtrado (estimativa usando os dados até cada período)
+    pub level: Vec<f64>,
+    /// Inclinação filtrada
+    pub slope: Vec<f64>,
+    /// Nível suavizado (RTS: usa a série inteira)
+    pub smoothed_level: Vec<f64>,
+    /// Inclinação suavizada (RTS)
+    pub smoothed_slope: Vec<f64>,
+    /// Previsão um passo à frente de cada observação, feita antes de vê-la
+    pub predictions: Vec<f64>,
+    /// Variância da inovação de cada previsão um passo à frente
+    pub prediction_variance: Vec<f64>,
+    /// Covariância do estado final `[[nível, nível×inclinação], [.., inclinação]]`
+    pub final_covariance: [[f64; 2]; 2],
+}
+
+impl KalmanResult {
+    /// Previsões para os próximos `periods` períodos a partir do estado final filtrado
+    pub fn forecast(&self, periods: usize) -> Vec<f64> {
+        let level = self.level.last().copied().unwrap_or(0.0);
+        let slope = self.slope.last().copied().unwrap_or(0.0);
+        (1..=periods).map(|h| level + slope * h as f64).collect()
+    }
+}
+
+type Matrix2 = [[f64; 2]; 2];
+
+fn mat2_mul(a: &Matrix2, b: &Matrix2) -> Matrix2 {
+    let mut out = [[0.0; 2]; 2];
+    for (r, row) in out.iter_mut().enumerate() {
+        for (c, cell) in row.iter_mut().enumerate() {
+            *cell = a[r][0] * b[0][c] + a[r][1] * b[1][c];
+        }
+    }
+    out
+}
+
+/// Variância inicial do estado, praticamente sem informação a priori
+const KALMAN_DIFFUSE_VARIANCE: f64 = 1e7;
+
+/// Filtro de Kalman de nível e inclinação locais, com suavização RTS
+///
+/// Modelo: `nível[t] = nível[t-1] + inclinação[t-1] + η`, `inclinação[t] =
+/// inclinação[t-1] + ζ` e `y[t] = nível[t] + ε`, com `Var(η) = Var(ζ) = process_noise` e
+/// `Var(ε) = observation_noise`. O estado começa no primeiro valor com inclinação zero e
+/// variância difusa. Ruídos negativos são rejeitados, assim como ambos nulos (o filtro
+/// degeneraria).
+pub fn kalman_trend_filter(
+    data: &[f64],
+    process_noise: f64,
+    observation_noise: f64,
+) -> Result<KalmanResult, TimeSeriesError> {
+    if !process_noise.is_finite() || process_noise < 0.0 {
+        return Err(TimeSeriesError::invalid_parameter(
+            "process_noise",
+            LocalizedText::new("deve ser finito e não negativo", "must be finite and non-negative"),
+        ));
+    }
+    if !observation_noise.is_finite() || observation_noise < 0.0 {
+        return Err(TimeSeriesError::invalid_parameter(
+            "observation_noise",
+            LocalizedText::new("deve ser finito e não negativo", "must be finite and non-negative"),
+        ));
+    }
+    if process_noise == 0.0 && observation_noise == 0.0 {
+        return Err(TimeSeriesError::invalid_parameter(
+            "process_noise",
+            LocalizedText::new(
+                "ao menos um dos ruídos deve ser positivo",
+                "at least one of the noises must be positive",
+            ),
+        ));
+    }
+    if data.is_empty() {
+        return Err(TimeSeriesError::insufficient_data(1, 0));
+    }
+    validate_finite(data)?;
+
+    let n = data.len();
+    let transition: Matrix2 = [[1.0, 1.0], [0.0, 1.0]];
+    let transition_t: Matrix2 = [[1.0, 0.0], [1.0, 1.0]];
+
+    let mut state = [data[0], 0.0];
+    let mut cov: Matrix2 = [[KALMAN_DIFFUSE_VARIANCE, 0.0], [0.0, KALMAN_DIFFUSE_VARIANCE]];
+    let mut filtered = Vec::with_capacity(n);
+    let mut filtered_cov = Vec::with_capacity(n);
+    let mut predicted = Vec::with_capacity(n);
+    let mut predicted_cov = Vec::with_capacity(n);
+    let mut predictions = Vec::with_capacity(n);
+    let mut prediction_variance = Vec::with_capacity(n);
+
+    for (t, &y) in data.iter().enumerate() {
+        if t > 0 {
+            state = [state[0] + state[1], state[1]];
+            cov = mat2_mul(&mat2_mul(&transition, &cov), &transition_t);
+            cov[0][0] += process_noise;
+            cov[1][1] += process_noise;
+        }
+        predicted.push(state);
+        predicted_cov.push(cov);
+
+        let innovation_variance = cov[0][0] + observation_noise;
+        predictions.push(state[0]);
+        prediction_variance.push(innovation_variance);
+
+        let gain = [cov[0][0] / innovation_variance, cov[1][0] / innovation_variance];
+        let innovation = y - state[0];
+        state = [state[0] + gain[0] * innovation, state[1] + gain[1] * innovation];
+        cov = [
+            [cov[0][0] - gain[0] * cov[0][0], cov[0][1] - gain[0] * cov[0][1]],
+            [cov[1][0] - gain[1] * cov[0][0], cov[1][1] - gain[1] * cov[0][1]],
+        ];
+        filtered.push(state);
+        filtered_cov.push(cov);
+    }
+
+    // Suavizador de Rauch–Tung–Striebel, de trás para frente
+    let mut smoothed = filtered.clone();
+    for t in (0..n.saturating_sub(1)).rev() {
+        let next = &predicted_cov[t + 1];
+        let det = next[0][0] * next[1][1] - next[0][1] * next[1][0];
+        if det.abs() < f64::MIN_POSITIVE {
+            continue;
+        }
+        let inverse = [[next[1][1] / det, -next[0][1] / det], [-next[1][0] / det, next[0][0] / det]];
+        let gain = mat2_mul(&mat2_mul(&filtered_cov[t], &transition_t), &inverse);
+        let diff = [smoothed[t + 1][0] - predicted[t + 1][0], smoothed[t + 1][1] - predicted[t + 1][1]];
+        smoothed[t] = [
+            filtered[t][0] + gain[0][0] * diff[0] + gain[0][1] * diff[1],
+            filtered[t][1] + gain[1][0] * diff[0] + gain[1][1] * diff[1],
+        ];
+    }
+
+    Ok(KalmanResult {
+        level: filtered.iter().map(|s| s[0]).collect(),
+        slope: filtered.iter().map(|s| s[1]).collect(),
+        smoothed_level: smoothed.iter().map(|s| s[0]).collect(),
+        smoothed_slope: smoothed.iter().map(|s| s[1]).collect(),
+        predictions,
+        prediction_variance,
+        final_covariance: cov,
+    })
+}
+
+/// Resolve `A·x = rhs` para `A` simétrica pentadiagonal e definida positiva por fatoração LDLᵀ
+///
+/// `diag[i] = A[i][i]`, `off1[i] = A[i][i+1]` e `off2[i] = A[i][i+2]`; custo O(n).
+fn solve_pentadiagonal(diag: &[f64], off1: &[f64], off2: &[f64], rhs: &[f64]) -> Vec<f64> {
+    let n = diag.len();
+    // L unitária com l1[i] = L[i+1][i] e l2[i] = L[i+2][i]
+    let mut d = vec![0.0; n];
+    let mut l1 = vec![0.0; n];
+    let mut l2 = vec![0.0; n];
+    for i in 0..n {
+        let mut di = diag[i];
+        if i >= 1 {
+            di -= l1[i - 1] * l1[i - 1] * d[i - 1];
+        }
+        if i >= 2 {
+            di -= l2[i - 2] * l2[i - 2] * d[i - 2];
+        }
+        d[i] = di;
+        if i + 1 < n {
+            let mut a = off1[i];
+            if i >= 1 {
+                a -= l2[i - 1] * l1[i - 1] * d[i - 1];
+            }
+            l1[i] = a / di;
+        }
+        if i + 2 < n {
+            l2[i] = off2[i] / di;
+        }
+    }
+
+    let mut z = vec![0.0; n];
+    for i in 0..n {
+        let mut zi = rhs[i];
+        if i >= 1 {
+            zi -= l1[i - 1] * z[i - 1];
+        }
+        if i >= 2 {
+            zi -= l2[i - 2] * z[i - 2];
+        }
+        z[i] = zi;
+    }
+    let mut x = vec![0.0; n];
+    for i in (0..n).rev() {
+        let mut xi = z[i] / d[i];
+        if i + 1 < n {
+            xi -= l1[i] * x[i + 1];
+        }
+        if i + 2 < n {
+            xi -= l2[i] * x[i + 2];
+        }
+        x[i] = xi;
+    }
+    x
+}
+
+/// Filtro de Hodrick–Prescott: separa a série em tendência e ciclo, devolvendo `(trend, cycle)`
+///
+/// A tendência minimiza `Σ(y - τ)² + lambda·Σ(Δ²τ)²`, isto é, resolve `(I + lambda·DᵀD)·τ = y`
+/// com `D` a matriz das segundas diferenças; o sistema é pentadiagonal e resolvido em O(n).
+/// O ciclo é `y - τ`. Valores usuais de `lambda`: 1600 para dados trimestrais, 129600 para
+/// mensais e 6.25 para anuais. Exige `lambda > 0` e ao menos 4 pontos.
+pub fn hp_filter(data: &[f64], lambda: f64) -> Result<(Vec<f64>, Vec<f64>), TimeSeriesError> {
+    if !lambda.is_finite() || lambda <= 0.0 {
+        return Err(TimeSeriesError::invalid_parameter(
+            "lambda",
+            LocalizedText::new("deve ser positivo e finito", "must be positive and finite"),
+        ));
+    }
+    if data.len() < 4 {
+        return Err(TimeSeriesError::insufficient_data(4, data.len()));
+    }
+    validate_finite(data)?;
+
+    // Bandas de I + lambda·DᵀD, somando a contribuição de cada linha [1, -2, 1] de D
+    let n = data.len();
+    let mut diag = vec![1.0; n];
+    let mut off1 = vec![0.0; n];
+    let mut off2 = vec![0.0; n];
+    const SECOND_DIFFERENCE: [f64; 3] = [1.0, -2.0, 1.0];
+    for r in 0..n - 2 {
+        for a in 0..3 {
+            diag[r + a] += lambda * SECOND_DIFFERENCE[a] * SECOND_DIFFERENCE[a];
+            if a + 1 < 3 {
+                off1[r + a] += lambda * SECOND_DIFFERENCE[a] * SECOND_DIFFERENCE[a + 1];
+            }
+        }
+        off2[r] += lambda * SECOND_DIFFERENCE[0] * SECOND_DIFFERENCE[2];
+    }
+
+    let trend = solve_pentadiagonal(&diag, &off1, &off2, data);
+    let cycle = data.iter().zip(&trend).map(|(y, t)| y - t).collect();
+    Ok((trend, cycle))
+}
+
+/// Suavização por núcleo gaussiano (Nadaraya–Watson) sobre o eixo dos índices
+///
+/// Cada ponto é a média de todos os valores ponderada por `exp(-½·(d/bandwidth)²)`,
+/// onde `d` é a distância em períodos; pesos além de 4 desvios são desprezados. Nas
+/// bordas os pesos são renormalizados sobre os pontos existentes, sem preenchimento.
+pub fn gaussian_smooth(data: &[f64], bandwidth: f64) -> Result<Vec<f64>, TimeSeriesError> {
+    if !bandwidth.is_finite() || bandwidth <= 0.0 {
+        return Err(TimeSeriesError::invalid_parameter(
+            "bandwidth",
+            LocalizedText::new("deve ser positivo e finito", "must be positive and finite"),
+        ));
+    }
+    validate_finite(data)?;
+
+    let n = data.len();
+    let radius = ((4.0 * bandwidth).ceil() as usize).min(n);
+    let weights: Vec<f64> = (0..=radius)
+        .map(|d| {
+            let u = d as f64 / bandwidth;
+            (-0.5 * u * u).exp()
+        })
+        .collect();
+
+    Ok((0..n)
+        .map(|i| {
+            let start = i.saturating_sub(radius);
+            let end = (i + radius + 1).min(n);
+            let (mut total, mut weight_sum) = (0.0, 0.0);
+            for (j, &y) in data.iter().enumerate().take(end).skip(start) {
+                let w = weights[i.abs_diff(j)];
+                total += w * y;
+                weight_sum += w;
+            }
+            total / weight_sum
+        })
+        .collect())
+}
+
+/// Pesos de Savitzky–Golay: `weights[t][k]` multiplica o k-ésimo ponto da janela para
+/// avaliar, na posição `t` da janela, o polinômio ajustado por mínimos quadrados
+fn savitzky_golay_weights(window: usize, poly_order: usize) -> Result<Vec<Vec<f64>>, TimeSeriesError> {
+    let half = (window / 2) as f64;
+    let terms = poly_order + 1;
+    // Posições escaladas para [-1, 1] para manter o sistema bem condicionado
+    let positions: Vec<f64> = (0..window).map(|k| if half > 0.0 { (k as f64 - half) / half } else { 0.0 }).collect();
+
+    let mut ata = vec![vec![0.0; terms]; terms];
+    for &u in &positions {
+        for (r, row) in ata.iter_mut().enumerate() {
+            for (c, cell) in row.iter_mut().enumerate() {
+                *cell += u.powi((r + c) as i32);
+            }
+        }
+    }
+
+    // Colunas de (AᵀA)⁻¹Aᵀ: coeficientes do polinômio como combinação dos pontos
+    let mut projection = vec![vec![0.0; window]; terms];
+    for (k, &u) in positions.iter().enumerate() {
+        let column = solve_linear_system(ata.clone(), (0..terms).map(|p| u.powi(p as i32)).collect())?;
+        for (p, value) in column.into_iter().enumerate() {
+            projection[p][k] = value;
+        }
+    }
+
+    Ok(positions
+        .iter()
+        .map(|&t| {
+            (0..window)
+                .map(|k| (0..terms).map(|p| t.powi(p as i32) * projection[p][k]).sum())
+                .collect()
+        })
+        .collect())
+}
+
+/// Filtro de Savitzky–Golay: polinômio de grau `poly_order` ajustado em cada janela
+/// centrada de tamanho `window` e avaliado no centro
+///
+/// Suaviza preservando picos melhor que a média móvel. Os pesos são calculados uma
+/// única vez. Nas bordas, os `window / 2` primeiros e últimos pontos são avaliados no
+/// polinômio da primeira e da última janela completas (sem espelhamento). Exige
+/// `window` ímpar, maior que `poly_order` e no máximo `len`.
+pub fn savitzky_golay(data: &[f64], window: usize, poly_order: usize) -> Result<Vec<f64>, TimeSeriesError> {
+    if window.is_multiple_of(2) {
+        return Err(TimeSeriesError::invalid_parameter(
+            "window",
+            LocalizedText::new("deve ser ímpar", "must be odd"),
+        ));
+    }
+    if window <= poly_order {
+        return Err(TimeSeriesError::invalid_parameter(
+            "window",
+            LocalizedText::new("deve ser maior que poly_order", "must be greater than poly_order"),
+        ));
+    }
+    validate_window(data.len(), window)?;
+    validate_finite(data)?;
+
+    let weights = savitzky_golay_weights(window, poly_order)?;
+    let half = window / 2;
+    let n = data.len();
+    let apply = |start: usize, position: usize| -> f64 {
+        weights[position].iter().zip(&data[start..start + window]).map(|(w, y)| w * y).sum()
+    };
+
+    Ok((0..n)
+        .map(|i| {
+            if i < half {
+                apply(0, i)
+            } else if i + half >= n {
+                apply(n - window, i + window - n)
+            } else {
+                apply(i - half, half)
+            }
+        })
+        .collect())
+}
+
+/// Filtro de mediana móvel com janela centrada de tamanho ímpar `window`
+///
+/// Remove picos isolados preservando degraus. Nas bordas a janela encolhe
+/// simetricamente (o primeiro e o último pontos ficam inalterados). No interior, a
+/// janela é mantida ordenada e atualizada por busca binária a cada passo, sem reordenar
+/// do zero.
+pub fn median_filter(data: &[f64], window: usize) -> Result<Vec<f64>, TimeSeriesError> {
+    if window.is_multiple_of(2) {
+        return Err(TimeSeriesError::invalid_parameter(
+            "window",
+            LocalizedText::new("deve ser ímpar", "must be odd"),
+        ));
+    }
+    validate_window(data.len(), window)?;
+    validate_finite(data)?;
+
+    let n = data.len();
+    let half = window / 2;
+    let edge_median = |i: usize| {
+        let reach = i.min(n - 1 - i);
+        let mut slice = data[i - reach..=i + reach].to_vec();
+        slice.sort_by(|a, b| a.total_cmp(b));
+        slice[reach]
+    };
+
+    let mut out = Vec::with_capacity(n);
+    out.extend((0..half.min(n)).map(edge_median));
+
+    let mut sorted = sorted_copy(&data[..window]);
+    out.push(sorted[half]);
+    for i in (half + 1)..(n - half) {
+        let leaving = data[i - half - 1];
+        let position = sorted.partition_point(|v| v.total_cmp(&leaving).is_lt());
+        sorted.remove(position);
+        let entering = data[i + half];
+        let position = sorted.partition_point(|v| v.total_cmp(&entering).is_lt());
+        sorted.insert(position, entering);
+        out.push(sorted[half]);
+    }
+
+    out.extend((n - half..n).map(edge_median));
+    Ok(out)
+}
+
+/// Média móvel exponencial: `s[0] = y[0]`, `s[t] = α·y[t] + (1 - α)·s[t-1]`
+///
+/// `alpha` deve estar em (0, 1]; valores maiores reagem mais rápido às mudanças.
+pub fn exponential_moving_average(data: &[f64], alpha: f64) -> Result<Vec<f64>, TimeSeriesError> {
+    if !(alpha > 0.0 && alpha <= 1.0) {
+        return Err(TimeSeriesError::invalid_parameter(
+            "alpha",
+            LocalizedText::new("deve estar em (0, 1]", "must be in (0, 1]"),
+        ));
+    }
+    if data.is_empty() {
+        return Err(TimeSeriesError::insufficient_data(1, 0));
+    }
+    validate_finite(data)?;
+
+    Ok(data
+        .iter()
+        .scan(data[0], |level, &y| {
+            *level = alpha * y + (1.0 - alpha) * *level;
+            Some(*level)
+        })
+        .collect())
+}
+
+fn validate_window(len: usize, window: usize) -> Result<(), TimeSeriesError> {
+    if window == 0 {
+        return Err(TimeSeriesError::invalid_parameter(
+            "window",
+            LocalizedText::new("deve ser maior que zero", "must be greater than zero"),
+        ));
+    }
+    if window > len {
+        return Err(TimeSeriesError::insufficient_data(window, len));
+    }
+    Ok(())
+}
+
+/// Janela deslizante de Welford: aplica `f(média, m2)` a cada janela de `window` valores
+///
+/// Ao deslizar, o valor que sai é substituído pelo que entra em O(1), sem somar a
+/// janela novamente.
+pub(crate) fn rolling_welford<F: Fn(f64, f64) -> f64>(data: &[f64], window: usize, f: F) -> Vec<f64> {
+    let w = window as f64;
+    let mut mean = 0.0;
+    let mut m2 = 0.0;
+    for (i, &x) in data[..window].iter().enumerate() {
+        let delta = x - mean;
+        mean += delta / (i + 1) as f64;
+        m2 += delta * (x - mean);
+    }
+
+    let mut out = Vec::with_capacity(data.len() - window + 1);
+    out.push(f(mean, m2));
+    for (&old, &new) in data.iter().zip(&data[window..]) {
+        let old_mean = mean;
+        mean += (new - old) / w;
+        m2 += (new - old) * (new - mean + old - old_mean);
+        out.push(f(mean, m2));
+    }
+    out
+}
+
+/// Média móvel simples com janela `window`; o resultado tem `len - window + 1` valores
+pub fn rolling_mean(data: &[f64], window: usize) -> Result<Vec<f64>, TimeSeriesError> {
+    validate_window(data.len(), window)?;
+    validate_finite(data)?;
+    Ok(rolling_welford(data, window, |mean, _| mean))
+}
+
+/// Desvio padrão móvel (populacional) com janela `window`; `len - window + 1` valores
+///
+/// Calculado em O(n) com Welford deslizante. Cancelamentos numéricos que levariam a
+/// uma variância levemente negativa são truncados em zero.
+pub fn rolling_std(data: &[f64], window: usize) -> Result<Vec<f64>, TimeSeriesError> {
+    validate_window(data.len(), window)?;
+    validate_finite(data)?;
+    let w = window as f64;
+    Ok(rolling_welford(data, window, |_, m2| (m2 / w).max(0.0).sqrt()))
+}
+
+/// Extremo móvel com deque monotônica: `keep(a, b)` diz se `a` domina `b`
+///
+/// Cada índice entra e sai da deque uma única vez, então o custo é O(n) para qualquer
+/// janela.
+fn rolling_extreme<F: Fn(f64, f64) -> bool>(data: &[f64], window: usize, keep: F) -> Vec<f64> {
+    let mut deque: VecDeque<usize> = VecDeque::with_capacity(window);
+    let mut out = Vec::with_capacity(data.len() - window + 1);
+    for (i, &value) in data.iter().enumerate() {
+        while deque.back().is_some_and(|&j| !keep(data[j], value)) {
+            deque.pop_back();
+        }
+        deque.push_back(i);
+        if deque.front().is_some_and(|&j| j + window <= i) {
+            deque.pop_front();
+        }
+        if i + 1 >= window {
+            out.push(data[deque[0]]);
+        }
+    }
+    out
+}
+
+/// Mínimo móvel com janela `window`; o resultado tem `len - window + 1` valores
+pub fn rolling_min(data: &[f64], window: usize) -> Result<Vec<f64>, TimeSeriesError> {
+    validate_window(data.len(), window)?;
+    validate_finite(data)?;
+    Ok(rolling_extreme(data, window, |kept, new| kept < new))
+}
+
+/// Máximo móvel com janela `window`; o resultado tem `len - window + 1` valores
+pub fn rolling_max(data: &[f64], window: usize) -> Result<Vec<f64>, TimeSeriesError> {
+    validate_window(data.len(), window)?;
+    validate_finite(data)?;
+    Ok(rolling_extreme(data, window, |kept, new| kept > new))
+}
+
+/// Aplica `f` à versão ordenada de cada janela de `window` valores
+///
+/// O buffer ordenado é mantido entre janelas: o valor que sai e o que entra são localizados
+/// por busca binária, então cada passo custa O(window) em deslocamentos de memória, sem
+/// reordenar a janela inteira.
+fn rolling_sorted<T, F: FnMut(&[f64]) -> T>(data: &[f64], window: usize, mut f: F) -> Vec<T> {
+    if window == 0 || window > data.len() {
+        return Vec::new();
+    }
+    let mut sorted = sorted_copy(&data[..window]);
+    let mut out = Vec::with_capacity(data.len() - window + 1);
+    out.push(f(&sorted));
+    for i in window..data.len() {
+        let leaving = data[i - window];
+        let position = sorted.partition_point(|v| v.total_cmp(&leaving).is_lt());
+        sorted.remove(position);
+        let entering = data[i];
+        let position = sorted.partition_point(|v| v.total_cmp(&entering).is_lt());
+        sorted.insert(position, entering);
+        out.push(f(&sorted));
+    }
+    out
+}
+
+fn validate_quantile(name: &'static str, q: f64) -> Result<(), TimeSeriesError> {
+    if !(q > 0.0 && q < 1.0) {
+        return Err(TimeSeriesError::invalid_parameter(
+            name,
+            LocalizedText::new("deve estar em (0, 1)", "must be in (0, 1)"),
+        ));
+    }
+    Ok(())
+}
+
+/// Quantil `q` móvel com janela `window`; o resultado tem `len - window + 1` valores
+///
+/// Usa a mesma interpolação de [`percentile`] (com `p = 100·q`). Exige `q` em (0, 1) e
+/// `window >= 2`.
+pub fn rolling_quantile(data: &[f64], window: usize, q: f64) -> Result<Vec<f64>, TimeSeriesError> {
+    validate_quantile("q", q)?;
+    if window < 2 {
+        return Err(TimeSeriesError::invalid_parameter(
+            "window",
+            LocalizedText::new("deve ser pelo menos 2", "must be at least 2"),
+        ));
+    }
+    validate_window(data.len(), window)?;
+    validate_finite(data)?;
+    Ok(rolling_sorted(data, window, |sorted| percentile_sorted(sorted, 100.0 * q)))
+}
+
+/// Índices em que o valor sai do canal formado pelos quantis `q_low` e `q_high` da janela
+/// anterior
+///
+/// O canal do índice `i` usa apenas `data[i - window..i]`, sem incluir o próprio valor, e
+/// o índice é marcado se `data[i]` ficar estritamente abaixo do quantil inferior ou acima
+/// do superior; os `window` primeiros índices nunca são marcados. Exige
+/// `0 < q_low < q_high < 1` e `window >= 2`.
+pub fn breakouts(data: &[f64], window: usize, q_low: f64, q_high: f64) -> Result<Vec<usize>, TimeSeriesError> {
+    validate_quantile("q_low", q_low)?;
+    validate_quantile("q_high", q_high)?;
+    if q_low >= q_high {
+        return Err(TimeSeriesError::invalid_parameter(
+            "q_low",
+            LocalizedText::new("deve ser menor que q_high", "must be less than q_high"),
+        ));
+    }
+    if window < 2 {
+        return Err(TimeSeriesError::invalid_parameter(
+            "window",
+            LocalizedText::new("deve ser pelo menos 2", "must be at least 2"),
+        ));
+    }
+    validate_finite(data)?;
+    if data.len() <= window {
+        return Ok(Vec::new());
+    }
+
+    // O canal da janela que termina em `j` vale para o índice `j + 1`
+    let channels = rolling_sorted(&data[..data.len() - 1], window, |sorted| {
+        (percentile_sorted(sorted, 100.0 * q_low), percentile_sorted(sorted, 100.0 * q_high))
+    });
+    Ok(channels
+        .into_iter()
+        .zip(window..)
+        .filter(|&((low, high), i)| data[i] < low || data[i] > high)
+        .map(|(_, i)| i)
+        .collect())
+}
+
+/// Bandas de volatilidade `média ± k·desvio` calculadas em janela móvel
+///
+/// O valor `i` de cada série corresponde à janela que termina em `data[i + offset]`;
+/// `&data[bands.offset..]` tem o mesmo tamanho das bandas e pode ser plotado junto
+/// com elas em [`ascii_multi_plot`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bands {
+    /// Índice em `data` do primeiro valor das bandas (`window - 1`)
+    pub offset: usize,
+    /// Média móvel
+    pub mean: Vec<f64>,
+    /// Banda superior: média + k·desvio
+    pub upper: Vec<f64>,
+    /// Banda inferior: média - k·desvio
+    pub lower: Vec<f64>,
+}
+
+/// Calcula as bandas `média ± k·desvio` com [`rolling_mean`] e [`rolling_std`]
+pub fn bands(data: &[f64], window: usize, k: f64) -> Result<Bands, TimeSeriesError> {
+    if !k.is_finite() || k <= 0.0 {
+        return Err(TimeSeriesError::invalid_parameter(
+            "k",
+            LocalizedText::new("deve ser positivo", "must be positive"),
+        ));
+    }
+    let mean = rolling_mean(data, window)?;
+    let std_dev = rolling_std(data, window)?;
+    let upper = mean.iter().zip(&std_dev).map(|(m, s)| m + k * s).collect();
+    let lower = mean.iter().zip(&std_dev).map(|(m, s)| m - k * s).collect();
+    Ok(Bands {
+        offset: window - 1,
+        mean,
+        upper,
+        lower,
+    })
+}
+
+/// Índices de `data` cujo valor fica estritamente fora das bandas
+pub fn points_outside_bands(data: &[f64], bands: &Bands) -> Vec<usize> {
+    data.iter()
+        .enumerate()
+        .skip(bands.offset)
+        .zip(bands.lower.iter().zip(&bands.upper))
+        .filter(|((_, &v), (&lo, &hi))| v < lo || v > hi)
+        .map(|((i, _), _)| i)
+        .collect()
+}
+
+#[cfg(test)]
+mod testes {
+    use super::*;
+    use crate::testes::{assert_approx_eq, pseudo_random};
+
+    #[test]
+    fn test_rolling_mean_e_std_contra_referencia() {
+        let data: Vec<f64> = pseudo_random(42, 500).iter().map(|v| 1e6 + v * 100.0).collect();
+        for window in [1, 7, 50, 500] {
+            let means = rolling_mean(&data, window).unwrap();
+            let stds = rolling_std(&data, window).unwrap();
+            assert_eq!(means.len(), data.len() - window + 1);
+            for (i, slice) in data.windows(window).enumerate() {
+                let mean = slice.iter().sum::<f64>() / window as f64;
+                let var = slice.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / window as f64;
+                assert_approx_eq(means[i], mean, 1e-6);
+                assert_approx_eq(stds[i], var.sqrt(), 1e-6);
+            }
+        }
+
+        // Janela constante: variância exatamente zero, nunca NaN
+        assert!(rolling_std(&[5.0; 10], 3).unwrap().iter().all(|&s| s == 0.0));
+        assert!(rolling_mean(&data, 0).is_err());
+        assert_eq!(rolling_std(&[1.0, 2.0], 3), Err(TimeSeriesError::InsufficientData { required: 3, got: 2 }));
+    }
+
+    #[test]
+    fn test_rolling_min_max_contra_ingenuo() {
+        // Valores arredondados para gerar sequências de empates
+        let data: Vec<f64> = pseudo_random(7, 300).iter().map(|v| (v * 5.0).floor()).collect();
+        for window in [1, 2, 5, 17, 300] {
+            let mins = rolling_min(&data, window).unwrap();
+            let maxs = rolling_max(&data, window).unwrap();
+            assert_eq!(mins.len(), data.len() - window + 1);
+            for (i, slice) in data.windows(window).enumerate() {
+                assert_eq!(mins[i], slice.iter().cloned().fold(f64::INFINITY, f64::min));
+                assert_eq!(maxs[i], slice.iter().cloned().fold(f64::NEG_INFINITY, f64::max));
+            }
+        }
+        assert!(rolling_max(&data, 0).is_err());
+        assert!(rolling_min(&data, 301).is_err());
+    }
+
+    #[test]
+    fn test_bands_e_rompimentos() {
+        let data = [10.0, 10.5, 9.5, 10.0, 10.2, 9.8, 10.0, 20.0, 10.1, 9.9];
+        let envelope = bands(&data, 4, 1.0).unwrap();
+        assert_eq!(envelope.offset, 3);
+        assert_eq!(envelope.mean.len(), data.len() - 3);
+        assert_eq!(data[envelope.offset..].len(), envelope.upper.len());
+        assert_approx_eq(envelope.mean[0], 10.0, 1e-12);
+        assert_approx_eq(envelope.upper[0] - envelope.mean[0], envelope.mean[0] - envelope.lower[0], 1e-12);
+
+        // O salto para 20 rompe a banda superior
+        assert_eq!(points_outside_bands(&data, &envelope), vec![7]);
+        assert!(bands(&data, 4, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_loess_dados_lineares() {
+        let data: Vec<f64> = (0..30).map(|i| 5.0 - 0.7 * i as f64).collect();
+        let smooth = loess(&data, 0.3, 1).unwrap();
+        for (a, b) in data.iter().zip(&smooth) {
+            assert_approx_eq(*a, *b, 1e-9);
+        }
+        assert!(loess(&data, 0.0, 1).is_err());
+        assert!(loess(&data, 1.5, 1).is_err());
+        assert!(loess(&data, 0.3, 2).is_err());
+        assert!(loess(&data[..3], 0.2, 1).is_err());
+    }
+
+    #[test]
+    fn test_loess_acompanha_seno() {
+        let noise = pseudo_random(9, 200);
+        let truth: Vec<f64> = (0..200).map(|i| (2.0 * core::f64::consts::PI * i as f64 / 50.0).sin()).collect();
+        let data: Vec<f64> = truth.iter().zip(&noise).map(|(t, e)| t + 0.6 * (e - 0.5)).collect();
+
+        let smooth = loess(&data, 0.1, 1).unwrap();
+        let line = linear_regression(&data).unwrap();
+        let loess_mse = calculate_mse(&truth, &smooth).unwrap();
+        let linear_mse = calculate_mse(&truth, &line.predictions).unwrap();
+        assert!(loess_mse < 0.02, "{}", loess_mse);
+        assert!(loess_mse * 10.0 < linear_mse, "{} vs {}", loess_mse, linear_mse);
+    }
+
+    #[test]
+    fn test_kalman_converge_para_inclinacao() {
+        let data: Vec<f64> = (0..60).map(|i| 3.0 + 2.0 * i as f64).collect();
+        let result = kalman_trend_filter(&data, 1e-4, 1.0).unwrap();
+        assert_eq!(result.level.len(), data.len());
+        assert_approx_eq(*result.slope.last().unwrap(), 2.0, 1e-3);
+        assert_approx_eq(result.smoothed_slope[10], 2.0, 1e-3);
+        assert!(result.prediction_variance.iter().all(|&v| v > 0.0));
+
+        let forecast = result.forecast(2);
+        assert_approx_eq(forecast[0], 3.0 + 2.0 * 60.0, 1e-2);
+        assert_approx_eq(forecast[1], 3.0 + 2.0 * 61.0, 1e-2);
+    }
+
+    #[test]
+    fn test_kalman_parametros_invalidos() {
+        assert!(kalman_trend_filter(&[1.0, 2.0], -1.0, 1.0).is_err());
+        assert!(kalman_trend_filter(&[1.0, 2.0], 1.0, -1.0).is_err());
+        assert!(kalman_trend_filter(&[1.0, 2.0], 0.0, 0.0).is_err());
+        assert!(kalman_trend_filter(&[], 1.0, 1.0).is_err());
+    }
+
+    #[test]
+    fn test_gaussian_smooth_limites() {
+        let data = [1.0, 4.0, 2.0, 8.0, 5.0, 7.0];
+        let narrow = gaussian_smooth(&data, 0.1).unwrap();
+        for (a, b) in data.iter().zip(&narrow) {
+            assert_approx_eq(*a, *b, 1e-9);
+        }
+
+        let mean = data.iter().sum::<f64>() / data.len() as f64;
+        let wide = gaussian_smooth(&data, 1e6).unwrap();
+        assert!(wide.iter().all(|&v| (v - mean).abs() < 1e-6));
+
+        // Bordas renormalizadas: série constante permanece constante
+        let flat = gaussian_smooth(&[3.0; 10], 2.0).unwrap();
+        assert!(flat.iter().all(|&v| (v - 3.0).abs() < 1e-12));
+        assert!(gaussian_smooth(&data, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_savitzky_golay_preserva_cubica() {
+        let data: Vec<f64> = (0..25)
+            .map(|i| {
+                let x = i as f64;
+                0.01 * x * x * x - 0.3 * x * x + 2.0 * x - 5.0
+            })
+            .collect();
+        let smooth = savitzky_golay(&data, 7, 3).unwrap();
+        for (a, b) in data.iter().zip(&smooth) {
+            assert_approx_eq(*a, *b, 1e-9);
+        }
+
+        // Grau 0 no interior é a média móvel centrada
+        let flat = savitzky_golay(&[1.0, 2.0, 6.0, 2.0, 1.0], 3, 0).unwrap();
+        assert_approx_eq(flat[2], 10.0 / 3.0, 1e-12);
+
+        assert!(savitzky_golay(&data, 6, 3).is_err());
+        assert!(savitzky_golay(&data, 3, 3).is_err());
+        assert!(savitzky_golay(&data[..5], 7, 3).is_err());
+    }
+
+    #[test]
+    fn test_median_filter_remove_picos() {
+        let mut data: Vec<f64> = (0..20).map(|i| i as f64 * 0.5).collect();
+        data[10] = 1e6;
+        let filtered = median_filter(&data, 5).unwrap();
+        assert_eq!(filtered.len(), data.len());
+        // O pico some; no máximo os vizinhos deslocam a rampa em um passo
+        for (i, &v) in filtered.iter().enumerate() {
+            assert!((v - i as f64 * 0.5).abs() <= 0.5, "índice {}: {}", i, v);
+        }
+
+        // Degraus são preservados, ao contrário da média móvel
+        let step = [0.0, 0.0, 0.0, 0.0, 10.0, 10.0, 10.0, 10.0];
+        assert_eq!(median_filter(&step, 3).unwrap(), step.to_vec());
+        assert!(rolling_mean(&step, 3).unwrap().iter().any(|&v| v != 0.0 && v != 10.0));
+
+        // Equivale a ordenar cada janela do zero
+        let noisy = pseudo_random(21, 60);
+        let fast = median_filter(&noisy, 9).unwrap();
+        for i in 4..56 {
+            assert_eq!(fast[i], median(&noisy[i - 4..=i + 4]).unwrap());
+        }
+        assert!(median_filter(&noisy, 4).is_err());
+    }
+
+    #[test]
+    fn test_filtro_hp_em_reta_e_senoide() {
+        // Numa reta as segundas diferenças já são nulas: tendência = dados, ciclo = 0
+        let line: Vec<f64> = (0..30).map(|i| 2.0 + 0.5 * i as f64).collect();
+        let (trend, cycle) = hp_filter(&line, 1600.0).unwrap();
+        for (t, y) in trend.iter().zip(&line) {
+            assert_approx_eq(*t, *y, 1e-8);
+        }
+        assert!(cycle.iter().all(|c| c.abs() < 1e-8));
+
+        // Com lambda = 1600 a senoide de período 12 passa quase inteira para o ciclo (ganho
+        // ≈ 0.99); o efeito de borda decai numa escala de lambda^¼ ≈ 6 períodos
+        let n = 120;
+        let sine: Vec<f64> = (0..n).map(|i| (2.0 * core::f64::consts::PI * i as f64 / 12.0).sin()).collect();
+        let data: Vec<f64> = (0..n).map(|i| 10.0 + 0.3 * i as f64 + sine[i]).collect();
+        let (trend, cycle) = hp_filter(&data, 1600.0).unwrap();
+        for i in 30..n - 30 {
+            assert_approx_eq(cycle[i], sine[i], 0.02);
+            assert_approx_eq(trend[i] + cycle[i], data[i], 1e-9);
+        }
+
+        // Simetria: inverter a série inverte tendência e ciclo
+        let reversed: Vec<f64> = data.iter().rev().copied().collect();
+        let (trend_rev, cycle_rev) = hp_filter(&reversed, 1600.0).unwrap();
+        for i in 0..n {
+            assert_approx_eq(trend_rev[i], trend[n - 1 - i], 1e-8);
+            assert_approx_eq(cycle_rev[i], cycle[n - 1 - i], 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_filtro_hp_parametros_invalidos() {
+        let data = [1.0, 2.0, 3.0, 5.0];
+        assert!(matches!(hp_filter(&data, 0.0), Err(TimeSeriesError::InvalidParameter { name: "lambda", .. })));
+        assert!(matches!(hp_filter(&data, -1.0), Err(TimeSeriesError::InvalidParameter { name: "lambda", .. })));
+        assert!(matches!(
+            hp_filter(&data[..3], 100.0),
+            Err(TimeSeriesError::InsufficientData { required: 4, got: 3 })
+        ));
+        // lambda pequeno mantém a tendência próxima dos dados
+        let (trend, _) = hp_filter(&data, 1e-6).unwrap();
+        for (t, y) in trend.iter().zip(&data) {
+            assert_approx_eq(*t, *y, 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_quantil_movel_igual_a_forca_bruta() {
+        // Valores repetidos exercitam a remoção do buffer ordenado
+        let data: Vec<f64> = pseudo_random(33, 300).iter().map(|v| (v * 20.0).floor()).collect();
+        for (window, q) in [(2, 0.5), (7, 0.1), (50, 0.95)] {
+            let rolling = rolling_quantile(&data, window, q).unwrap();
+            assert_eq!(rolling.len(), data.len() - window + 1);
+            for (start, value) in rolling.iter().enumerate() {
+                let expected = percentile(&data[start..start + window], 100.0 * q).unwrap();
+                assert_approx_eq(*value, expected, 1e-12);
+            }
+        }
+
+        assert!(rolling_quantile(&data, 1, 0.5).is_err());
+        assert!(rolling_quantile(&data, 10, 0.0).is_err());
+        assert!(rolling_quantile(&data, 10, 1.0).is_err());
+        assert!(matches!(
+            rolling_quantile(&data[..5], 10, 0.5),
+            Err(TimeSeriesError::InsufficientData { required: 10, got: 5 })
+        ));
+    }
+
+    #[test]
+    fn test_breakouts_marcam_apenas_o_pico() {
+        // Variação normal: um padrão de período 10 que se repete, sempre dentro do canal
+        let pattern = pseudo_random(8, 10);
+        let mut data: Vec<f64> = (0..200).map(|i| 10.0 + pattern[i % 10]).collect();
+        assert!(breakouts(&data, 100, 0.01, 0.99).unwrap().is_empty());
+        data[150] = 25.0;
+        assert_eq!(breakouts(&data, 100, 0.01, 0.99).unwrap(), vec![150]);
+
+        // Sem lookahead: o pico no último índice também é detectado
+        let mut tail = data[..120].to_vec();
+        tail.push(-5.0);
+        assert_eq!(breakouts(&tail, 100, 0.01, 0.99).unwrap(), vec![120]);
+
+        assert!(breakouts(&data, 100, 0.9, 0.1).is_err());
+        assert!(breakouts(&data[..50], 100, 0.01, 0.99).unwrap().is_empty());
+    }
+}
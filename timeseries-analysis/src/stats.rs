@@ -0,0 +1,1943 @@
+//! Estatística descritiva e testes
+//!
+//! Medidas de posição e dispersão (também em streaming e por blocos), estatísticas robustas,
+//! agregação entre séries, crescimento, drawdowns, detecção de mudança por CUSUM,
+//! autocorrelação e comparação entre segmentos.
+//!
+//! ```
+//! use timeseries_analysis::stats::{calculate_descriptive_stats, median};
+//!
+//! let data = [3.0, 1.0, 4.0, 1.0, 5.0];
+//! assert_eq!(median(&data).unwrap(), 3.0);
+//! let stats = calculate_descriptive_stats(&data).unwrap();
+//! assert_eq!((stats.min, stats.max, stats.mean), (1.0, 5.0, 2.8));
+//! ```
+
+use crate::*;
+
+/// Logaritmo da função gama para `x > 0` (aproximação de Lanczos, g = 7)
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    if x < 0.5 {
+        // Γ(x) = Γ(x + 1) / x mantém o argumento na região precisa da aproximação
+        return ln_gamma(x + 1.0) - x.ln();
+    }
+    let x = x - 1.0;
+    let t = x + 7.5;
+    let series = COEFFICIENTS[1..]
+        .iter()
+        .enumerate()
+        .fold(COEFFICIENTS[0], |acc, (i, c)| acc + c / (x + (i + 1) as f64));
+    0.5 * (2.0 * core::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + series.ln()
+}
+
+/// Função beta incompleta regularizada `I_x(a, b)` (fração contínua de Lentz)
+fn incomplete_beta(x: f64, a: f64, b: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+    // A fração converge rápido para x < (a + 1) / (a + b + 2); do contrário usa a simetria
+    if x > (a + 1.0) / (a + b + 2.0) {
+        return 1.0 - incomplete_beta(1.0 - x, b, a);
+    }
+
+    const TINY: f64 = 1e-300;
+    let front = (ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1.0 - x).ln()).exp() / a;
+    let mut c = 1.0;
+    let mut d = 1.0 - (a + b) * x / (a + 1.0);
+    d = if d.abs() < TINY { 1.0 / TINY } else { 1.0 / d };
+    let mut fraction = d;
+    for m in 1..300 {
+        let m = m as f64;
+        for numerator in [
+            m * (b - m) * x / ((a + 2.0 * m - 1.0) * (a + 2.0 * m)),
+            -(a + m) * (a + b + m) * x / ((a + 2.0 * m) * (a + 2.0 * m + 1.0)),
+        ] {
+            d = 1.0 + numerator * d;
+            d = if d.abs() < TINY { 1.0 / TINY } else { 1.0 / d };
+            c = 1.0 + numerator / c;
+            if c.abs() < TINY {
+                c = TINY;
+            }
+            fraction *= c * d;
+        }
+        if (c * d - 1.0).abs() < 1e-15 {
+            break;
+        }
+    }
+    front * fraction
+}
+
+/// Função de distribuição acumulada da t de Student com `df` graus de liberdade
+pub(crate) fn student_t_cdf(t: f64, df: f64) -> f64 {
+    let tail = 0.5 * incomplete_beta(df / (df + t * t), df / 2.0, 0.5);
+    if t > 0.0 {
+        1.0 - tail
+    } else {
+        tail
+    }
+}
+
+/// Quantil `p` (entre 0 e 1, exclusivos) da t de Student, por bisseção sobre a CDF
+pub(crate) fn student_t_quantile(p: f64, df: f64) -> f64 {
+    if p == 0.5 {
+        return 0.0;
+    }
+    if p < 0.5 {
+        return -student_t_quantile(1.0 - p, df);
+    }
+    let mut hi = 1.0;
+    while student_t_cdf(hi, df) < p && hi < 1e12 {
+        hi *= 2.0;
+    }
+    let mut lo = 0.0;
+    for _ in 0..200 {
+        let mid = 0.5 * (lo + hi);
+        if student_t_cdf(mid, df) < p {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+        if hi - lo <= 1e-12 * hi {
+            break;
+        }
+    }
+    0.5 * (lo + hi)
+}
+
+/// Resultado de [`compare_segments`]; as diferenças são sempre `after - before`
+#[derive(Debug, Clone, PartialEq)]
+pub struct SegmentComparison {
+    pub mean_before: f64,
+    pub mean_after: f64,
+    pub mean_difference: f64,
+    /// Estatística t de Welch (variâncias não supostas iguais)
+    pub welch_t: f64,
+    /// Graus de liberdade de Welch–Satterthwaite (não inteiros)
+    pub welch_degrees_of_freedom: f64,
+    /// p-valor bilateral do teste t de Welch
+    pub welch_p_value: f64,
+    pub median_difference: f64,
+    /// `var(after) / var(before)`, com variâncias amostrais (divisor n - 1)
+    pub variance_ratio: f64,
+    /// p-valor bilateral do teste F para a razão de variâncias
+    pub f_p_value: f64,
+    /// Maior distância entre as distribuições empíricas (Kolmogorov–Smirnov)
+    pub ks_statistic: f64,
+    /// p-valor assintótico do teste de Kolmogorov–Smirnov
+    pub ks_p_value: f64,
+}
+
+impl fmt::Display for SegmentComparison {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Comparacao de Segmentos:")?;
+        writeln!(f, "   Medias: {:.4} -> {:.4} (diferenca {:.4})", self.mean_before, self.mean_after, self.mean_difference)?;
+        writeln!(
+            f,
+            "   Welch t: {:.4} (gl = {:.2}, p = {:.4})",
+            self.welch_t, self.welch_degrees_of_freedom, self.welch_p_value
+        )?;
+        writeln!(f, "   Diferenca das Medianas: {:.4}", self.median_difference)?;
+        writeln!(f, "   Razao das Variancias: {:.4} (p = {:.4})", self.variance_ratio, self.f_p_value)?;
+        write!(f, "   Kolmogorov-Smirnov: {:.4} (p = {:.4})", self.ks_statistic, self.ks_p_value)
+    }
+}
+
+/// Média e variância amostral (divisor n - 1) de um segmento de [`compare_segments`]
+fn segment_moments(data: &[f64], name: &'static str) -> Result<(f64, f64), TimeSeriesError> {
+    if data.len() < 3 {
+        return Err(TimeSeriesError::invalid_parameter(
+            name,
+            LocalizedText::new(
+                format!("necessário(s) 3 ponto(s), recebido(s) {}", data.len()),
+                format!("3 point(s) required, {} received", data.len()),
+            ),
+        ));
+    }
+    validate_finite(data)?;
+    if data.iter().all(|&v| v == data[0]) {
+        return Err(TimeSeriesError::invalid_parameter(
+            name,
+            LocalizedText::new("segmento constante", "constant segment"),
+        ));
+    }
+    let n = data.len() as f64;
+    let mean = kahan_sum(data.iter().copied()) / n;
+    let variance = kahan_sum(data.iter().map(|&v| (v - mean) * (v - mean))) / (n - 1.0);
+    Ok((mean, variance))
+}
+
+/// Maior diferença entre as distribuições empíricas de dois conjuntos já ordenados
+fn ks_statistic(a: &[f64], b: &[f64]) -> f64 {
+    let (n_a, n_b) = (a.len() as f64, b.len() as f64);
+    let (mut i, mut j) = (0, 0);
+    let mut max_distance: f64 = 0.0;
+    while i < a.len() && j < b.len() {
+        // Avança os dois lados sobre valores empatados antes de comparar
+        let value = a[i].min(b[j]);
+        while i < a.len() && a[i] <= value {
+            i += 1;
+        }
+        while j < b.len() && b[j] <= value {
+            j += 1;
+        }
+        max_distance = max_distance.max((i as f64 / n_a - j as f64 / n_b).abs());
+    }
+    max_distance
+}
+
+/// Cauda da distribuição de Kolmogorov: `Q(λ) = 2 Σ (-1)^(k-1) e^(-2k²λ²)`
+fn kolmogorov_tail(lambda: f64) -> f64 {
+    let mut sum = 0.0;
+    let mut sign = 1.0;
+    let mut previous_term: f64 = 0.0;
+    for k in 1..=100 {
+        let k = k as f64;
+        let term = sign * 2.0 * (-2.0 * k * k * lambda * lambda).exp();
+        sum += term;
+        if term.abs() <= 1e-3 * previous_term.abs() || term.abs() <= 1e-8 * sum.abs() {
+            return sum.clamp(0.0, 1.0);
+        }
+        sign = -sign;
+        previous_term = term;
+    }
+    // Sem convergência só acontece com λ próximo de zero, onde a cauda vale 1
+    1.0
+}
+
+/// Compara a distribuição de dois trechos de uma série (por exemplo, antes e depois de um evento)
+///
+/// Calcula a diferença das médias com o teste t de Welch, a diferença das medianas, a razão
+/// das variâncias com o teste F e a estatística de Kolmogorov–Smirnov com o p-valor
+/// assintótico (correção de Stephens). Cada segmento precisa de ao menos 3 pontos e não pode
+/// ser constante; o erro indica qual entrada falhou.
+pub fn compare_segments(before: &[f64], after: &[f64]) -> Result<SegmentComparison, TimeSeriesError> {
+    let (mean_before, var_before) = segment_moments(before, "before")?;
+    let (mean_after, var_after) = segment_moments(after, "after")?;
+    let (n_before, n_after) = (before.len() as f64, after.len() as f64);
+
+    let (se_before, se_after) = (var_before / n_before, var_after / n_after);
+    let mean_difference = mean_after - mean_before;
+    let welch_t = mean_difference / (se_before + se_after).sqrt();
+    let welch_degrees_of_freedom = (se_before + se_after).powi(2)
+        / (se_before * se_before / (n_before - 1.0) + se_after * se_after / (n_after - 1.0));
+    let welch_p_value = incomplete_beta(
+        welch_degrees_of_freedom / (welch_degrees_of_freedom + welch_t * welch_t),
+        welch_degrees_of_freedom / 2.0,
+        0.5,
+    );
+
+    let variance_ratio = var_after / var_before;
+    let (d1, d2) = (n_after - 1.0, n_before - 1.0);
+    let f_cdf = incomplete_beta(d1 * variance_ratio / (d1 * variance_ratio + d2), d1 / 2.0, d2 / 2.0);
+    let f_p_value = (2.0 * f_cdf.min(1.0 - f_cdf)).min(1.0);
+
+    let sorted_before = sorted_copy(before);
+    let sorted_after = sorted_copy(after);
+    let ks = ks_statistic(&sorted_before, &sorted_after);
+    let effective_n = (n_before * n_after / (n_before + n_after)).sqrt();
+    let ks_p_value = kolmogorov_tail((effective_n + 0.12 + 0.11 / effective_n) * ks);
+
+    Ok(SegmentComparison {
+        mean_before,
+        mean_after,
+        mean_difference,
+        welch_t,
+        welch_degrees_of_freedom,
+        welch_p_value,
+        median_difference: percentile_sorted(&sorted_after, 50.0) - percentile_sorted(&sorted_before, 50.0),
+        variance_ratio,
+        f_p_value,
+        ks_statistic: ks,
+        ks_p_value,
+    })
+}
+
+/// Função complementar de erro (aproximação de Chebyshev, erro relativo < 1.2e-7)
+pub(crate) fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let poly = -z * z - 1.265_512_23
+        + t * (1.000_023_68
+            + t * (0.374_091_96
+                + t * (0.096_784_18
+                    + t * (-0.186_288_06
+                        + t * (0.278_868_07
+                            + t * (-1.135_203_98 + t * (1.488_515_87 + t * (-0.822_152_23 + t * 0.170_872_77))))))));
+    let value = t * poly.exp();
+    if x >= 0.0 {
+        value
+    } else {
+        2.0 - value
+    }
+}
+
+/// Função de distribuição acumulada da normal padrão
+pub(crate) fn normal_cdf(z: f64) -> f64 {
+    0.5 * erfc(-z / core::f64::consts::SQRT_2)
+}
+
+/// Percentil de dados já ordenados, com interpolação linear entre as posições vizinhas
+///
+/// Convenção: posição `h = (n - 1)·p/100`, interpolando entre `sorted[⌊h⌋]` e `sorted[⌈h⌉]`
+/// (o mesmo método padrão do NumPy).
+pub(crate) fn percentile_sorted(sorted: &[f64], p: f64) -> f64 {
+    let h = (sorted.len() - 1) as f64 * p / 100.0;
+    let lower = h.floor() as usize;
+    let upper = h.ceil() as usize;
+    sorted[lower] + (h - lower as f64) * (sorted[upper] - sorted[lower])
+}
+
+pub(crate) fn sorted_copy(data: &[f64]) -> Vec<f64> {
+    let mut sorted = data.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    sorted
+}
+
+/// Calcula o percentil `p` (entre 0 e 100) com interpolação linear
+pub fn percentile(data: &[f64], p: f64) -> Result<f64, TimeSeriesError> {
+    if data.is_empty() {
+        return Err(TimeSeriesError::insufficient_data(1, 0));
+    }
+    if !(0.0..=100.0).contains(&p) {
+        return Err(TimeSeriesError::invalid_parameter(
+            "p",
+            LocalizedText::new("deve estar entre 0 e 100", "must be between 0 and 100"),
+        ));
+    }
+    validate_finite(data)?;
+    Ok(percentile_sorted(&sorted_copy(data), p))
+}
+
+/// Calcula a mediana (média dos dois valores centrais quando n é par)
+pub fn median(data: &[f64]) -> Result<f64, TimeSeriesError> {
+    if data.is_empty() {
+        return Err(TimeSeriesError::insufficient_data(1, 0));
+    }
+    validate_finite(data)?;
+    Ok(percentile_sorted(&sorted_copy(data), 50.0))
+}
+
+/// Momentos centrais (m2, m3, m4) com divisor n
+pub(crate) fn central_moments(data: &[f64]) -> (f64, f64, f64) {
+    let n = data.len() as f64;
+    let mean = data.iter().sum::<f64>() / n;
+    let (mut m2, mut m3, mut m4) = (0.0, 0.0, 0.0);
+    for &x in data {
+        let d = x - mean;
+        let d2 = d * d;
+        m2 += d2;
+        m3 += d2 * d;
+        m4 += d2 * d2;
+    }
+    (m2 / n, m3 / n, m4 / n)
+}
+
+/// Calcula a assimetria amostral corrigida (G1)
+///
+/// `G1 = √(n(n-1))/(n-2) · m3/m2^(3/2)`, onde `mk` são os momentos centrais com divisor n.
+/// Exige ao menos 3 pontos e variância não nula.
+pub fn skewness(data: &[f64]) -> Result<f64, TimeSeriesError> {
+    if data.len() < 3 {
+        return Err(TimeSeriesError::insufficient_data(3, data.len()));
+    }
+    validate_finite(data)?;
+    let (m2, m3, _) = central_moments(data);
+    if m2 <= 0.0 {
+        return Err(TimeSeriesError::new(
+            LocalizedText::new(
+                "Assimetria indefinida para série com variância zero",
+                "Skewness undefined for a series with zero variance",
+            ),
+        ));
+    }
+
+    let n = data.len() as f64;
+    let g1 = m3 / m2.powf(1.5);
+    Ok((n * (n - 1.0)).sqrt() / (n - 2.0) * g1)
+}
+
+/// Calcula a curtose em excesso amostral corrigida (G2; distribuição normal ≈ 0)
+///
+/// `G2 = (n-1)/((n-2)(n-3)) · ((n+1)·g2 + 6)`, com `g2 = m4/m2² - 3`.
+/// Exige ao menos 4 pontos e variância não nula.
+pub fn kurtosis(data: &[f64]) -> Result<f64, TimeSeriesError> {
+    if data.len() < 4 {
+        return Err(TimeSeriesError::insufficient_data(4, data.len()));
+    }
+    validate_finite(data)?;
+    let (m2, _, m4) = central_moments(data);
+    if m2 <= 0.0 {
+        return Err(TimeSeriesError::new(
+            LocalizedText::new(
+                "Curtose indefinida para série com variância zero",
+                "Kurtosis undefined for a series with zero variance",
+            ),
+        ));
+    }
+
+    let n = data.len() as f64;
+    let g2 = m4 / (m2 * m2) - 3.0;
+    Ok((n - 1.0) / ((n - 2.0) * (n - 3.0)) * ((n + 1.0) * g2 + 6.0))
+}
+
+/// Acumulador de estatísticas em passagem única (algoritmo de Welford)
+///
+/// Numericamente estável para séries longas com valores grandes; acumuladores de
+/// blocos diferentes podem ser combinados com [`RunningStats::merge`].
+///
+/// Serve para alimentar estatísticas a partir de várias threads ou arquivos: cada fonte
+/// usa o próprio acumulador e os resultados são combinados no final, com o mesmo
+/// resultado (a menos de arredondamento) de um único acumulador. [`RunningStats::finish`]
+/// devolve um [`RunningSummary`] e não um [`DescriptiveStats`] porque mediana e quartis
+/// exigem os dados completos; sem valores, o erro é o mesmo de
+/// [`calculate_descriptive_stats`] para entrada vazia.
+///
+/// ```
+/// use timeseries_analysis::stats::{calculate_descriptive_stats, RunningStats};
+///
+/// let (mut left, mut right) = (RunningStats::new(), RunningStats::new());
+/// [1.0, 2.0].iter().for_each(|&v| left.push(v));
+/// right.extend([3.0, 4.0, 5.0]);
+/// left.merge(&right);
+///
+/// let summary = left.finish().unwrap();
+/// assert_eq!(summary.mean, calculate_descriptive_stats(&[1.0, 2.0, 3.0, 4.0, 5.0]).unwrap().mean);
+/// assert!(RunningStats::new().finish().is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RunningStats {
+    count: usize,
+    mean: f64,
+    m2: f64,
+    min: f64,
+    max: f64,
+}
+
+/// Resumo produzido por [`RunningStats::finish`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RunningSummary {
+    pub count: usize,
+    pub mean: f64,
+    /// Variância populacional (divisor n)
+    pub variance: f64,
+    pub std_dev: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl Default for RunningStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RunningStats {
+    /// Cria um acumulador vazio
+    pub fn new() -> Self {
+        RunningStats {
+            count: 0,
+            mean: 0.0,
+            m2: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+
+    /// Adiciona um valor ao acumulador
+    pub fn push(&mut self, value: f64) {
+        self.count += 1;
+        let delta = value - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (value - self.mean);
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+    }
+
+    /// Combina com outro acumulador (fórmula de Chan para variâncias paralelas)
+    pub fn merge(&mut self, other: &RunningStats) {
+        if other.count == 0 {
+            return;
+        }
+        if self.count == 0 {
+            *self = *other;
+            return;
+        }
+
+        let total = (self.count + other.count) as f64;
+        let delta = other.mean - self.mean;
+        let weight = other.count as f64 / total;
+
+        self.mean += delta * weight;
+        self.m2 += other.m2 + delta * delta * self.count as f64 * weight;
+        self.count += other.count;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+    }
+
+    /// Número de valores acumulados
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Produz o resumo; erro se nenhum valor foi acumulado
+    pub fn finish(&self) -> Result<RunningSummary, TimeSeriesError> {
+        if self.count == 0 {
+            return Err(TimeSeriesError::insufficient_data(1, 0));
+        }
+        let variance = (self.m2 / self.count as f64).max(0.0);
+        Ok(RunningSummary {
+            count: self.count,
+            mean: self.mean,
+            variance,
+            std_dev: variance.sqrt(),
+            min: self.min,
+            max: self.max,
+        })
+    }
+}
+
+impl Extend<f64> for RunningStats {
+    fn extend<I: IntoIterator<Item = f64>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+/// Estatísticas de momentos em passagem única sobre um iterador, sem armazenar a entrada
+///
+/// Mediana e quartis exigem os dados ordenados e por isso só estão disponíveis em
+/// [`calculate_descriptive_stats`], que usa esta função para os demais campos.
+pub fn descriptive_stats_iter<I: IntoIterator<Item = f64>>(data: I) -> Result<RunningSummary, TimeSeriesError> {
+    let mut running = RunningStats::new();
+    for (index, value) in data.into_iter().enumerate() {
+        if !value.is_finite() {
+            return Err(TimeSeriesError::NonFiniteValue { index });
+        }
+        running.push(value);
+    }
+    running.finish()
+}
+
+/// Estatísticas de momentos sobre uma série lida em blocos, mantendo apenas um bloco por vez
+///
+/// Equivale a [`descriptive_stats_iter`] sobre a concatenação dos blocos: cada bloco é
+/// resumido em um [`RunningStats`] próprio e combinado ao total com [`RunningStats::merge`].
+pub fn descriptive_stats_chunked<I>(chunks: I) -> Result<RunningSummary, TimeSeriesError>
+where
+    I: IntoIterator,
+    I::Item: AsRef<[f64]>,
+{
+    let mut total = RunningStats::new();
+    for chunk in chunks {
+        let chunk = chunk.as_ref();
+        if let Err(TimeSeriesError::NonFiniteValue { index }) = validate_finite(chunk) {
+            return Err(TimeSeriesError::NonFiniteValue { index: total.count + index });
+        }
+        let mut running = RunningStats::new();
+        running.extend(chunk.iter().copied());
+        total.merge(&running);
+    }
+    total.finish()
+}
+
+/// Estatísticas descritivas de uma série temporal
+#[derive(Debug, Clone, PartialEq)]
+pub struct DescriptiveStats {
+    pub count: usize,
+    pub mean: f64,
+    /// Desvio padrão populacional (divisor n)
+    pub std_dev: f64,
+    /// Variância populacional (divisor n)
+    pub variance: f64,
+    pub min: f64,
+    pub max: f64,
+    pub median: f64,
+    /// Primeiro quartil (percentil 25)
+    pub q1: f64,
+    /// Terceiro quartil (percentil 75)
+    pub q3: f64,
+}
+
+impl DescriptiveStats {
+    fn write_formatted<W: fmt::Write>(&self, f: &mut W, options: &FormatOptions) -> fmt::Result {
+        let num = |value: f64| options.format(value);
+        writeln!(f, "Estatisticas Descritivas:")?;
+        writeln!(f, "   Observacoes: {}", self.count)?;
+        writeln!(f, "   Media: {}", num(self.mean))?;
+        writeln!(f, "   Desvio Padrao: {}", num(self.std_dev))?;
+        writeln!(f, "   Variancia: {}", num(self.variance))?;
+        writeln!(f, "   Minimo: {}", num(self.min))?;
+        writeln!(f, "   Maximo: {}", num(self.max))?;
+        writeln!(f, "   Mediana: {}", num(self.median))?;
+        writeln!(f, "   Q1: {}", num(self.q1))?;
+        write!(f, "   Q3: {}", num(self.q3))
+    }
+}
+
+/// Valores com 2 casas decimais, ou com a precisão do formatador (`{:.4}`)
+impl fmt::Display for DescriptiveStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write_formatted(f, &FormatOptions::for_formatter(f, 2))
+    }
+}
+
+impl FormatWith for DescriptiveStats {
+    fn format_with(&self, options: &FormatOptions) -> String {
+        let mut out = String::new();
+        let _ = self.write_formatted(&mut out, options);
+        out
+    }
+}
+
+/// Calcula estatísticas descritivas básicas para uma série temporal
+pub fn calculate_descriptive_stats(data: &[f64]) -> Result<DescriptiveStats, TimeSeriesError> {
+    if data.is_empty() {
+        return Err(TimeSeriesError::insufficient_data(1, 0));
+    }
+    validate_finite(data)?;
+    let summary = fold_ranges(
+        data.len(),
+        |range| {
+            let mut running = RunningStats::new();
+            running.extend(data[range].iter().copied());
+            running
+        },
+        |mut a, b| {
+            a.merge(&b);
+            a
+        },
+    )
+    .finish()?;
+
+    let sorted = sorted_copy(data);
+
+    Ok(DescriptiveStats {
+        count: summary.count,
+        mean: summary.mean,
+        std_dev: summary.std_dev,
+        variance: summary.variance,
+        min: summary.min,
+        max: summary.max,
+        median: percentile_sorted(&sorted, 50.0),
+        q1: percentile_sorted(&sorted, 25.0),
+        q3: percentile_sorted(&sorted, 75.0),
+    })
+}
+
+/// Estatísticas descritivas na ordem `(média, desvio padrão, mínimo, máximo, mediana, Q1, Q3)`
+pub type DescriptiveStatsTuple = (f64, f64, f64, f64, f64, f64, f64);
+
+/// Versão em tupla de [`calculate_descriptive_stats`], mantida por compatibilidade
+#[deprecated(note = "use calculate_descriptive_stats, que retorna DescriptiveStats")]
+pub fn calculate_descriptive_stats_tuple(data: &[f64]) -> Result<DescriptiveStatsTuple, TimeSeriesError> {
+    let stats = calculate_descriptive_stats(data)?;
+    Ok((stats.mean, stats.std_dev, stats.min, stats.max, stats.median, stats.q1, stats.q3))
+}
+
+/// Média aparada: descarta `⌊n·trim_fraction⌋` pontos em cada extremo antes de tirar a média
+///
+/// `trim_fraction` deve estar em `[0, 0.5)`; com 0 o resultado é a média comum. Como a
+/// fração fica abaixo de metade, sempre resta ao menos um ponto. Empates nos extremos são
+/// descartados pela posição na ordenação, não pelo valor.
+pub fn trimmed_mean(data: &[f64], trim_fraction: f64) -> Result<f64, TimeSeriesError> {
+    if !(0.0..0.5).contains(&trim_fraction) {
+        return Err(TimeSeriesError::invalid_parameter(
+            "trim_fraction",
+            LocalizedText::new("deve estar em [0, 0.5)", "must be in [0, 0.5)"),
+        ));
+    }
+    if data.is_empty() {
+        return Err(TimeSeriesError::insufficient_data(1, 0));
+    }
+    validate_finite(data)?;
+
+    let sorted = sorted_copy(data);
+    let cut = (sorted.len() as f64 * trim_fraction).floor() as usize;
+    let kept = &sorted[cut..sorted.len() - cut];
+    Ok(kahan_sum(kept.iter().copied()) / kept.len() as f64)
+}
+
+/// Desvio absoluto mediano: mediana de `|x - mediana|`, sem fator de escala
+///
+/// Para comparar com o desvio padrão de dados normais, multiplique por 1,4826.
+pub fn median_absolute_deviation(data: &[f64]) -> Result<f64, TimeSeriesError> {
+    let center = median(data)?;
+    let deviations: Vec<f64> = data.iter().map(|&v| (v - center).abs()).collect();
+    Ok(percentile_sorted(&sorted_copy(&deviations), 50.0))
+}
+
+/// Fração aparada em cada extremo por [`robust_stats`]
+pub const DEFAULT_TRIM_FRACTION: f64 = 0.1;
+
+/// Estimativas de posição e escala pouco sensíveis a outliers
+#[derive(Debug, Clone, PartialEq)]
+pub struct RobustStats {
+    pub median: f64,
+    /// Média aparada com `trim_fraction` em cada extremo
+    pub trimmed_mean: f64,
+    pub trim_fraction: f64,
+    /// Desvio absoluto mediano (sem fator de escala)
+    pub mad: f64,
+}
+
+impl fmt::Display for RobustStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Estatisticas Robustas:")?;
+        writeln!(f, "   Mediana: {:.2}", self.median)?;
+        writeln!(f, "   Media Aparada ({:.0}%): {:.2}", self.trim_fraction * 100.0, self.trimmed_mean)?;
+        write!(f, "   MAD: {:.2}", self.mad)
+    }
+}
+
+/// Complemento de [`calculate_descriptive_stats`] com mediana, média aparada e MAD
+///
+/// Usa [`DEFAULT_TRIM_FRACTION`]; para outra fração, chame [`trimmed_mean`] diretamente.
+pub fn robust_stats(data: &[f64]) -> Result<RobustStats, TimeSeriesError> {
+    Ok(RobustStats {
+        median: median(data)?,
+        trimmed_mean: trimmed_mean(data, DEFAULT_TRIM_FRACTION)?,
+        trim_fraction: DEFAULT_TRIM_FRACTION,
+        mad: median_absolute_deviation(data)?,
+    })
+}
+
+/// Como combinar vários valores em um só
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregation {
+    Mean,
+    Median,
+    Min,
+    Max,
+    Sum,
+}
+
+impl Aggregation {
+    /// Aplica a agregação a valores finitos, não vazios
+    pub(crate) fn apply(self, values: &[f64]) -> f64 {
+        match self {
+            Aggregation::Mean => kahan_sum(values.iter().copied()) / values.len() as f64,
+            Aggregation::Median => percentile_sorted(&sorted_copy(values), 50.0),
+            Aggregation::Min => values.iter().copied().fold(f64::INFINITY, f64::min),
+            Aggregation::Max => values.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            Aggregation::Sum => kahan_sum(values.iter().copied()),
+        }
+    }
+}
+
+/// Exige ao menos uma série, nenhuma vazia e todas com valores finitos
+pub(crate) fn validate_series_set(series: &[&[f64]]) -> Result<(), TimeSeriesError> {
+    if series.is_empty() {
+        return Err(TimeSeriesError::insufficient_data(1, 0));
+    }
+    if let Some(index) = series.iter().position(|s| s.is_empty()) {
+        return Err(TimeSeriesError::invalid_parameter(
+            "series",
+            LocalizedText::new(format!("a série {} está vazia", index), format!("series {} is empty", index)),
+        ));
+    }
+    series.iter().try_for_each(|s| validate_finite(s))
+}
+
+/// Exige que todas as séries tenham o tamanho da primeira
+pub(crate) fn validate_equal_lengths(series: &[&[f64]]) -> Result<usize, TimeSeriesError> {
+    let n = series[0].len();
+    match series.iter().find(|s| s.len() != n) {
+        Some(other) => Err(TimeSeriesError::LengthMismatch { left: n, right: other.len() }),
+        None => Ok(n),
+    }
+}
+
+/// Agrega, em cada índice de tempo, os valores de todas as séries (por exemplo, a média
+/// de várias lojas por período)
+///
+/// As séries devem ter o mesmo tamanho; para tamanhos diferentes, veja
+/// [`aggregate_series_partial`].
+pub fn aggregate_series(series: &[&[f64]], agg: Aggregation) -> Result<Vec<f64>, TimeSeriesError> {
+    validate_series_set(series)?;
+    let n = validate_equal_lengths(series)?;
+    let mut column = Vec::with_capacity(series.len());
+    Ok((0..n)
+        .map(|i| {
+            column.clear();
+            column.extend(series.iter().map(|s| s[i]));
+            agg.apply(&column)
+        })
+        .collect())
+}
+
+/// Como [`aggregate_series`], mas aceita tamanhos diferentes: cada índice agrega apenas
+/// as séries que o possuem
+///
+/// Retorna os valores agregados (tantos quanto a série mais longa) e, para cada índice,
+/// quantas séries contribuíram.
+pub fn aggregate_series_partial(
+    series: &[&[f64]],
+    agg: Aggregation,
+) -> Result<(Vec<f64>, Vec<usize>), TimeSeriesError> {
+    validate_series_set(series)?;
+    let n = series.iter().map(|s| s.len()).max().unwrap_or(0);
+    let mut column = Vec::with_capacity(series.len());
+    Ok((0..n)
+        .map(|i| {
+            column.clear();
+            column.extend(series.iter().filter_map(|s| s.get(i)));
+            (agg.apply(&column), column.len())
+        })
+        .unzip())
+}
+
+/// Estatísticas descritivas entre as séries em cada índice de tempo
+///
+/// As séries devem ter o mesmo tamanho.
+pub fn aggregate_stats(series: &[&[f64]]) -> Result<Vec<DescriptiveStats>, TimeSeriesError> {
+    validate_series_set(series)?;
+    let n = validate_equal_lengths(series)?;
+    (0..n)
+        .map(|i| {
+            let column: Vec<f64> = series.iter().map(|s| s[i]).collect();
+            calculate_descriptive_stats(&column)
+        })
+        .collect()
+}
+
+/// Resumo de crescimento de [`growth_stats`]; taxas em porcentagem
+#[derive(Debug, Clone, PartialEq)]
+pub struct GrowthStats {
+    /// `último - primeiro`
+    pub total_change: f64,
+    /// Variação total relativa ao primeiro valor; `None` se o primeiro valor é zero
+    pub total_percent_change: Option<f64>,
+    /// Média das variações percentuais período a período; `None` se algum valor base é zero
+    pub average_percent_change: Option<f64>,
+    /// Crescimento composto por período `((último/primeiro)^(1/(n-1)) - 1)·100`; `None` se o
+    /// primeiro valor é zero ou se primeiro e último têm sinais opostos
+    pub compound_growth_rate: Option<f64>,
+    /// Períodos com alta, queda e sem variação
+    pub up_periods: usize,
+    pub down_periods: usize,
+    pub flat_periods: usize,
+}
+
+impl GrowthStats {
+    fn write_formatted<W: fmt::Write>(&self, f: &mut W, options: &FormatOptions) -> fmt::Result {
+        let pct = |value: Option<f64>| value.map_or_else(|| "n/d".to_string(), |v| format!("{}%", options.format(v)));
+        writeln!(f, "Crescimento:")?;
+        writeln!(f, "   Variacao Total: {}", options.format(self.total_change))?;
+        writeln!(f, "   Variacao Total (%): {}", pct(self.total_percent_change))?;
+        writeln!(f, "   Variacao Media por Periodo: {}", pct(self.average_percent_change))?;
+        writeln!(f, "   Crescimento Composto por Periodo: {}", pct(self.compound_growth_rate))?;
+        write!(
+            f,
+            "   Periodos em Alta/Queda/Estaveis: {}/{}/{}",
+            self.up_periods, self.down_periods, self.flat_periods
+        )
+    }
+}
+
+/// Valores com 2 casas decimais, ou com a precisão do formatador
+impl fmt::Display for GrowthStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write_formatted(f, &FormatOptions::for_formatter(f, 2))
+    }
+}
+
+impl FormatWith for GrowthStats {
+    fn format_with(&self, options: &FormatOptions) -> String {
+        let mut out = String::new();
+        let _ = self.write_formatted(&mut out, options);
+        out
+    }
+}
+
+/// Resume o crescimento da série: variação total, média por período e taxa composta (CAGR)
+///
+/// As taxas indefinidas (base zero, troca de sinal) ficam como `None` em vez de gerar erro.
+/// Exige ao menos 2 pontos.
+pub fn growth_stats(data: &[f64]) -> Result<GrowthStats, TimeSeriesError> {
+    if data.len() < 2 {
+        return Err(TimeSeriesError::insufficient_data(2, data.len()));
+    }
+    validate_finite(data)?;
+
+    let (first, last) = (data[0], data[data.len() - 1]);
+    let ratio = last / first;
+    let compound_growth_rate = if first != 0.0 && ratio >= 0.0 {
+        Some((ratio.powf(1.0 / (data.len() - 1) as f64) - 1.0) * 100.0)
+    } else {
+        None
+    };
+    let average_percent_change = percent_change(data, 1)
+        .ok()
+        .map(|changes| changes.iter().sum::<f64>() / changes.len() as f64);
+
+    let (mut up_periods, mut down_periods, mut flat_periods) = (0, 0, 0);
+    for w in data.windows(2) {
+        if w[1] > w[0] {
+            up_periods += 1;
+        } else if w[1] < w[0] {
+            down_periods += 1;
+        } else {
+            flat_periods += 1;
+        }
+    }
+
+    Ok(GrowthStats {
+        total_change: last - first,
+        total_percent_change: (first != 0.0).then(|| (last - first) / first * 100.0),
+        average_percent_change,
+        compound_growth_rate,
+        up_periods,
+        down_periods,
+        flat_periods,
+    })
+}
+
+/// Resultado de [`drawdowns`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DrawdownReport {
+    /// Distância até o máximo acumulado, em unidades da série (sempre ≥ 0)
+    pub drawdown: Vec<f64>,
+    /// Drawdown em percentual do máximo acumulado; `None` quando o pico não é positivo
+    pub drawdown_pct: Vec<Option<f64>>,
+    /// Maior drawdown absoluto
+    pub max_drawdown: f64,
+    /// Maior drawdown em percentual do seu pico, se o pico for positivo
+    pub max_drawdown_pct: Option<f64>,
+    /// Índice do pico que antecede o maior drawdown
+    pub peak_index: usize,
+    /// Índice do vale do maior drawdown
+    pub trough_index: usize,
+    /// Primeiro índice após o vale em que a série volta ao valor do pico
+    pub recovery_index: Option<usize>,
+}
+
+/// Analisa as quedas da série em relação ao seu máximo acumulado
+///
+/// Em séries que nunca caem o maior drawdown é zero, com pico e vale no índice 0.
+pub fn drawdowns(data: &[f64]) -> Result<DrawdownReport, TimeSeriesError> {
+    if data.is_empty() {
+        return Err(TimeSeriesError::insufficient_data(1, 0));
+    }
+    validate_finite(data)?;
+
+    let peaks = running_max(data);
+    let drawdown: Vec<f64> = data.iter().zip(&peaks).map(|(v, p)| p - v).collect();
+    let drawdown_pct = drawdown
+        .iter()
+        .zip(&peaks)
+        .map(|(&d, &p)| if p > 0.0 { Some(d / p * 100.0) } else { None })
+        .collect();
+
+    let mut trough_index = 0;
+    for (i, &d) in drawdown.iter().enumerate() {
+        if d > drawdown[trough_index] {
+            trough_index = i;
+        }
+    }
+    let max_drawdown = drawdown[trough_index];
+    let peak_value = peaks[trough_index];
+    let peak_index = data[..=trough_index].iter().position(|&v| v == peak_value).unwrap_or(0);
+    let recovery_index = if max_drawdown > 0.0 {
+        data[trough_index..].iter().position(|&v| v >= peak_value).map(|i| i + trough_index)
+    } else {
+        None
+    };
+
+    Ok(DrawdownReport {
+        drawdown,
+        drawdown_pct,
+        max_drawdown,
+        max_drawdown_pct: if peak_value > 0.0 { Some(max_drawdown / peak_value * 100.0) } else { None },
+        peak_index,
+        trough_index,
+        recovery_index,
+    })
+}
+
+/// Sentido de uma sequência monótona (não estrita: valores repetidos não quebram a sequência)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Não decrescente
+    Increasing,
+    /// Não crescente
+    Decreasing,
+}
+
+impl Direction {
+    /// `next` segue o sentido em relação a `previous`, com folga `tolerance`
+    fn follows(&self, previous: f64, next: f64, tolerance: f64) -> bool {
+        match self {
+            Direction::Increasing => next >= previous - tolerance,
+            Direction::Decreasing => next <= previous + tolerance,
+        }
+    }
+}
+
+fn validate_tolerance(tolerance: f64) -> Result<(), TimeSeriesError> {
+    if !(tolerance.is_finite() && tolerance >= 0.0) {
+        return Err(TimeSeriesError::invalid_parameter(
+            "tolerance",
+            LocalizedText::new("deve ser finito e não negativo", "must be finite and non-negative"),
+        ));
+    }
+    Ok(())
+}
+
+/// Maior trecho monótono da série: `(início, comprimento, sentido)`
+///
+/// Trechos constantes contam nos dois sentidos. Em caso de empate no comprimento vence o
+/// trecho que começa primeiro e, com o mesmo início, [`Direction::Increasing`]; uma série
+/// de um ponto devolve `(0, 1, Increasing)`.
+pub fn longest_monotonic_run(data: &[f64]) -> Result<(usize, usize, Direction), TimeSeriesError> {
+    if data.is_empty() {
+        return Err(TimeSeriesError::insufficient_data(1, 0));
+    }
+    validate_finite(data)?;
+
+    let mut best = (0, 1, Direction::Increasing);
+    for direction in [Direction::Increasing, Direction::Decreasing] {
+        let mut start = 0;
+        for i in 1..=data.len() {
+            if i < data.len() && direction.follows(data[i - 1], data[i], 0.0) {
+                continue;
+            }
+            let length = i - start;
+            if length > best.1 || (length == best.1 && start < best.0) {
+                best = (start, length, direction);
+            }
+            start = i;
+        }
+    }
+    Ok(best)
+}
+
+/// Indica se a série é monótona no sentido `direction`, tolerando recuos de até `tolerance`
+///
+/// A folga é medida contra o extremo acumulado (máximo para `Increasing`, mínimo para
+/// `Decreasing`), e não entre pontos vizinhos, para que várias pequenas quedas seguidas
+/// não somem um recuo maior que `tolerance`.
+pub fn is_monotonic(data: &[f64], direction: Direction, tolerance: f64) -> Result<bool, TimeSeriesError> {
+    if data.is_empty() {
+        return Err(TimeSeriesError::insufficient_data(1, 0));
+    }
+    validate_tolerance(tolerance)?;
+    validate_finite(data)?;
+
+    let mut extreme = data[0];
+    for &value in &data[1..] {
+        if !direction.follows(extreme, value, tolerance) {
+            return Ok(false);
+        }
+        extreme = match direction {
+            Direction::Increasing => extreme.max(value),
+            Direction::Decreasing => extreme.min(value),
+        };
+    }
+    Ok(true)
+}
+
+/// Patamares da série: trechos de ao menos `min_length` pontos com `max - min <= tolerance`,
+/// como `(início, comprimento)`
+///
+/// A busca é gulosa da esquerda para a direita: cada patamar é estendido ao máximo e o
+/// seguinte só começa depois dele, de modo que os trechos não se sobrepõem.
+pub fn detect_plateaus(data: &[f64], tolerance: f64, min_length: usize) -> Result<Vec<(usize, usize)>, TimeSeriesError> {
+    if data.is_empty() {
+        return Err(TimeSeriesError::insufficient_data(1, 0));
+    }
+    validate_tolerance(tolerance)?;
+    if min_length < 2 {
+        return Err(TimeSeriesError::invalid_parameter(
+            "min_length",
+            LocalizedText::new("deve ser pelo menos 2", "must be at least 2"),
+        ));
+    }
+    validate_finite(data)?;
+
+    let mut plateaus = Vec::new();
+    let mut start = 0;
+    while start < data.len() {
+        let (mut lo, mut hi) = (data[start], data[start]);
+        let mut end = start + 1;
+        while end < data.len() && data[end].max(hi) - data[end].min(lo) <= tolerance {
+            lo = lo.min(data[end]);
+            hi = hi.max(data[end]);
+            end += 1;
+        }
+        if end - start >= min_length {
+            plateaus.push((start, end - start));
+            start = end;
+        } else {
+            start += 1;
+        }
+    }
+    Ok(plateaus)
+}
+
+/// Direção do desvio sinalizado por [`CusumDetector`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DriftDirection {
+    /// Média acima da referência
+    Up,
+    /// Média abaixo da referência
+    Down,
+}
+
+/// Alarme emitido por [`CusumDetector::push`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DriftSignal {
+    pub direction: DriftDirection,
+    /// Valor da estatística CUSUM que cruzou o limiar
+    pub statistic: f64,
+    /// Posição do valor que disparou o alarme, contando desde a criação do detector
+    pub index: usize,
+}
+
+/// Detector de mudança de média em tempo real pelo CUSUM bilateral
+///
+/// Mantém `S⁺ = max(0, S⁺ + x - target - k)` e `S⁻ = max(0, S⁻ + target - k - x)`; quando
+/// uma delas passa de `h`, [`push`](CusumDetector::push) devolve um [`DriftSignal`] e as
+/// duas estatísticas voltam a zero. `k` (folga) costuma ser metade do desvio que se quer
+/// detectar e `h` algumas vezes o desvio padrão do ruído.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CusumDetector {
+    target: f64,
+    slack: f64,
+    threshold: f64,
+    upper: f64,
+    lower: f64,
+    count: usize,
+}
+
+impl CusumDetector {
+    /// Erro se `target` não for finito, `slack` for negativo ou `threshold` não for positivo
+    pub fn new(target: f64, slack: f64, threshold: f64) -> Result<Self, TimeSeriesError> {
+        if !target.is_finite() {
+            return Err(TimeSeriesError::invalid_parameter(
+                "target",
+                LocalizedText::new("deve ser finito", "must be finite"),
+            ));
+        }
+        if !(slack.is_finite() && slack >= 0.0) {
+            return Err(TimeSeriesError::invalid_parameter(
+                "k",
+                LocalizedText::new("deve ser finito e não negativo", "must be finite and non-negative"),
+            ));
+        }
+        if !(threshold.is_finite() && threshold > 0.0) {
+            return Err(TimeSeriesError::invalid_parameter(
+                "h",
+                LocalizedText::new("deve ser finito e positivo", "must be finite and positive"),
+            ));
+        }
+        Ok(CusumDetector {
+            target,
+            slack,
+            threshold,
+            upper: 0.0,
+            lower: 0.0,
+            count: 0,
+        })
+    }
+
+    /// Processa um valor; valores não finitos contam na posição, mas não alteram as estatísticas
+    pub fn push(&mut self, value: f64) -> Option<DriftSignal> {
+        let index = self.count;
+        self.count += 1;
+        if !value.is_finite() {
+            return None;
+        }
+        self.upper = (self.upper + value - self.target - self.slack).max(0.0);
+        self.lower = (self.lower + self.target - self.slack - value).max(0.0);
+
+        let (direction, statistic) = if self.upper > self.threshold {
+            (DriftDirection::Up, self.upper)
+        } else if self.lower > self.threshold {
+            (DriftDirection::Down, self.lower)
+        } else {
+            return None;
+        };
+        self.reset();
+        Some(DriftSignal {
+            direction,
+            statistic,
+            index,
+        })
+    }
+
+    /// Zera as estatísticas, mantendo a contagem de posições
+    pub fn reset(&mut self) {
+        self.upper = 0.0;
+        self.lower = 0.0;
+    }
+
+    /// Estatística de alta `S⁺` atual
+    pub fn upper(&self) -> f64 {
+        self.upper
+    }
+
+    /// Estatística de queda `S⁻` atual
+    pub fn lower(&self) -> f64 {
+        self.lower
+    }
+}
+
+/// Índices em que [`CusumDetector`] dispara ao percorrer `data` com referência `target`
+pub fn cusum_scan(data: &[f64], target: f64, k: f64, h: f64) -> Result<Vec<usize>, TimeSeriesError> {
+    validate_finite(data)?;
+    let mut detector = CusumDetector::new(target, k, h)?;
+    Ok(data.iter().filter_map(|&value| detector.push(value)).map(|signal| signal.index).collect())
+}
+
+/// Autocorrelação amostral para os lags `0..=max_lag`
+///
+/// `r_k = Σ (x_t - x̄)(x_{t+k} - x̄) / Σ (x_t - x̄)²`, com o mesmo denominador em todos os
+/// lags (estimador padrão, sempre entre -1 e 1). Exige `max_lag < n` e série não constante.
+pub fn autocorrelation(data: &[f64], max_lag: usize) -> Result<Vec<f64>, TimeSeriesError> {
+    if max_lag >= data.len() {
+        return Err(TimeSeriesError::insufficient_data(max_lag + 1, data.len()));
+    }
+    validate_finite(data)?;
+    let mean = kahan_sum(data.iter().copied()) / data.len() as f64;
+    let centered: Vec<f64> = data.iter().map(|v| v - mean).collect();
+    let denominator = kahan_sum(centered.iter().map(|d| d * d));
+    if approx_zero((denominator / data.len() as f64).sqrt(), max_abs(data)) {
+        return Err(TimeSeriesError::new(
+            LocalizedText::new(
+                "Autocorrelação indefinida para série constante",
+                "Autocorrelation undefined for a constant series",
+            ),
+        ));
+    }
+    Ok((0..=max_lag)
+        .map(|k| kahan_sum(centered.iter().zip(&centered[k..]).map(|(a, b)| a * b)) / denominator)
+        .collect())
+}
+
+/// Período dominante: o lag entre 2 e `max_lag` com o maior pico local positivo da autocorrelação
+///
+/// Remova a tendência antes (por exemplo, com os resíduos de [`linear_regression`] ou com
+/// [`decompose`]); uma
+/// tendência forte mantém a autocorrelação decrescente e sem picos. `None` se não houver pico.
+pub fn dominant_period(data: &[f64], max_lag: usize) -> Result<Option<usize>, TimeSeriesError> {
+    let acf = autocorrelation(data, max_lag)?;
+    Ok((2..max_lag)
+        .filter(|&k| acf[k] > 0.0 && acf[k] > acf[k - 1] && acf[k] >= acf[k + 1])
+        .max_by(|&a, &b| acf[a].total_cmp(&acf[b]).then(b.cmp(&a))))
+}
+
+/// Ajuste de `y[t]` contra `x[t - lag]` em [`lead_lag_analysis`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct LagFit {
+    pub lag: usize,
+    pub slope: f64,
+    pub intercept: f64,
+    pub r_squared: f64,
+    /// Pares usados no ajuste (`n - lag`)
+    pub pairs: usize,
+}
+
+/// Resultado de [`lead_lag_analysis`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct LeadLagReport {
+    /// Um ajuste por defasagem, em ordem crescente de `lag`
+    pub fits: Vec<LagFit>,
+    /// Defasagens ignoradas (ex.: `x` constante na janela), com o motivo
+    pub skipped: Vec<(usize, String)>,
+    /// Defasagem com maior R² (empates ficam com a menor)
+    pub best_lag: usize,
+}
+
+impl fmt::Display for LeadLagReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{:>5} {:>12} {:>8} {:>6}", "Lag", "Slope", "R²", "Pares")?;
+        for fit in &self.fits {
+            let marker = if fit.lag == self.best_lag { " *" } else { "" };
+            writeln!(
+                f,
+                "{:>5} {:>12.4} {:>8.4} {:>6}{}",
+                fit.lag, fit.slope, fit.r_squared, fit.pairs, marker
+            )?;
+        }
+        for (lag, reason) in &self.skipped {
+            writeln!(f, "{:>5} ignorado: {}", lag, reason)?;
+        }
+        write!(f, "Melhor defasagem: {}", self.best_lag)
+    }
+}
+
+/// Regride `y[t]` contra `x[t - k]` para cada `k` em `0..=max_lag` e aponta a defasagem
+/// com maior R² — uma análise de Granger simplificada ("x ajuda a prever y?")
+///
+/// As séries devem ter o mesmo tamanho e cada defasagem precisa de ao menos 3 pares.
+/// Defasagens em que o ajuste falha (como `x` constante na janela) são registradas em
+/// `skipped` sem interromper o restante.
+pub fn lead_lag_analysis(x: &[f64], y: &[f64], max_lag: usize) -> Result<LeadLagReport, TimeSeriesError> {
+    if x.len() != y.len() {
+        return Err(TimeSeriesError::LengthMismatch {
+            left: x.len(),
+            right: y.len(),
+        });
+    }
+    if x.len() < max_lag + 3 {
+        return Err(TimeSeriesError::insufficient_data(max_lag + 3, x.len()));
+    }
+
+    let n = x.len();
+    let mut fits = Vec::with_capacity(max_lag + 1);
+    let mut skipped = Vec::new();
+    for lag in 0..=max_lag {
+        match linear_regression_xy(&x[..n - lag], &y[lag..]) {
+            Ok(result) => fits.push(LagFit {
+                lag,
+                slope: result.slope,
+                intercept: result.intercept,
+                r_squared: result.r_squared,
+                pairs: n - lag,
+            }),
+            Err(err @ TimeSeriesError::NonFiniteValue { .. }) => return Err(err),
+            Err(err) => skipped.push((lag, err.to_string())),
+        }
+    }
+
+    let best_lag = fits
+        .iter()
+        .fold(None::<&LagFit>, |best, fit| match best {
+            Some(current) if current.r_squared >= fit.r_squared => Some(current),
+            _ => Some(fit),
+        })
+        .map(|fit| fit.lag)
+        .ok_or_else(|| TimeSeriesError::new(
+            LocalizedText::new("Nenhuma defasagem pôde ser ajustada", "No lag could be fitted"),
+        ))?;
+
+    Ok(LeadLagReport {
+        fits,
+        skipped,
+        best_lag,
+    })
+}
+
+/// Medida de distância entre séries usada por [`distance_matrix`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DistanceMetric {
+    /// [`euclidean_distance`]
+    Euclidean,
+    /// [`correlation_distance`]
+    Correlation,
+    /// [`dynamic_time_warping`] com a faixa de Sakoe–Chiba `window`
+    Dtw { window: Option<usize> },
+}
+
+impl DistanceMetric {
+    /// Distância entre `a` e `b` segundo a medida
+    pub fn distance(&self, a: &[f64], b: &[f64]) -> Result<f64, TimeSeriesError> {
+        match *self {
+            DistanceMetric::Euclidean => euclidean_distance(a, b),
+            DistanceMetric::Correlation => correlation_distance(a, b),
+            DistanceMetric::Dtw { window } => dynamic_time_warping(a, b, window),
+        }
+    }
+}
+
+fn validate_distance_pair(a: &[f64], b: &[f64], min_len: usize) -> Result<(), TimeSeriesError> {
+    if a.len() != b.len() {
+        return Err(TimeSeriesError::LengthMismatch {
+            left: a.len(),
+            right: b.len(),
+        });
+    }
+    if a.len() < min_len {
+        return Err(TimeSeriesError::insufficient_data(min_len, a.len()));
+    }
+    validate_finite(a)?;
+    validate_finite(b)
+}
+
+/// Distância euclidiana `√Σ(a[i] - b[i])²`; as séries devem ter o mesmo tamanho
+pub fn euclidean_distance(a: &[f64], b: &[f64]) -> Result<f64, TimeSeriesError> {
+    validate_distance_pair(a, b, 1)?;
+    Ok(kahan_sum(a.iter().zip(b).map(|(x, y)| (x - y) * (x - y))).sqrt())
+}
+
+/// Distância de correlação `1 - r` (Pearson), entre 0 (mesma forma) e 2 (formas opostas)
+///
+/// Ignora nível e escala das séries. Exige o mesmo tamanho, ao menos 2 pontos e séries
+/// não constantes, para as quais a correlação é indefinida.
+pub fn correlation_distance(a: &[f64], b: &[f64]) -> Result<f64, TimeSeriesError> {
+    validate_distance_pair(a, b, 2)?;
+    let n = a.len() as f64;
+    let (mean_a, mean_b) = (kahan_sum(a.iter().copied()) / n, kahan_sum(b.iter().copied()) / n);
+    let covariance = kahan_sum(a.iter().zip(b).map(|(x, y)| (x - mean_a) * (y - mean_b)));
+    let var_a = kahan_sum(a.iter().map(|x| (x - mean_a) * (x - mean_a)));
+    let var_b = kahan_sum(b.iter().map(|y| (y - mean_b) * (y - mean_b)));
+    if approx_zero((var_a / n).sqrt(), max_abs(a)) || approx_zero((var_b / n).sqrt(), max_abs(b)) {
+        return Err(TimeSeriesError::new(
+            LocalizedText::new(
+                "Correlação indefinida: série constante",
+                "Correlation undefined: constant series",
+            ),
+        ));
+    }
+    let r = (covariance / (var_a * var_b).sqrt()).clamp(-1.0, 1.0);
+    Ok(1.0 - r)
+}
+
+/// Distância DTW (dynamic time warping): `√` do menor custo `Σ(a[i] - b[j])²` entre os
+/// alinhamentos monótonos das duas séries
+///
+/// Aceita séries de tamanhos diferentes. Com `window`, só são alinhados pares com
+/// `|i - j| <= window` (faixa de Sakoe–Chiba), em O(n·w) em vez de O(n·m); a faixa é
+/// alargada para `|n - m|` quando menor, já que sem isso não haveria alinhamento
+/// possível. Com `window = Some(0)` e mesmo tamanho, coincide com
+/// [`euclidean_distance`], que é sempre um limite superior para séries de mesmo tamanho.
+pub fn dynamic_time_warping(a: &[f64], b: &[f64], window: Option<usize>) -> Result<f64, TimeSeriesError> {
+    if a.is_empty() || b.is_empty() {
+        return Err(TimeSeriesError::insufficient_data(1, 0));
+    }
+    validate_finite(a)?;
+    validate_finite(b)?;
+
+    let (n, m) = (a.len(), b.len());
+    let window = window.map_or(n.max(m), |w| w.max(n.abs_diff(m)));
+    // duas linhas da matriz de custo acumulado; a coluna 0 é a borda infinita
+    let mut previous = vec![f64::INFINITY; m + 1];
+    let mut current = vec![f64::INFINITY; m + 1];
+    previous[0] = 0.0;
+    for i in 1..=n {
+        let (lo, hi) = (i.saturating_sub(window).max(1), (i + window).min(m));
+        // `current` guarda a linha i - 2: só as bordas da faixa, lidas nesta linha e na
+        // próxima, precisam voltar a ser infinitas, o que mantém cada linha em O(w)
+        current[lo - 1] = f64::INFINITY;
+        if hi < m {
+            current[hi + 1] = f64::INFINITY;
+        }
+        for j in lo..=hi {
+            let cost = (a[i - 1] - b[j - 1]) * (a[i - 1] - b[j - 1]);
+            current[j] = cost + previous[j - 1].min(previous[j]).min(current[j - 1]);
+        }
+        core::mem::swap(&mut previous, &mut current);
+    }
+    Ok(previous[m].sqrt())
+}
+
+/// Matriz simétrica de distâncias entre todas as séries, com zeros na diagonal
+///
+/// Falha com o erro do primeiro par inválido (ex.: tamanhos diferentes fora do DTW).
+pub fn distance_matrix(series: &[&[f64]], metric: DistanceMetric) -> Result<Vec<Vec<f64>>, TimeSeriesError> {
+    if series.is_empty() {
+        return Err(TimeSeriesError::invalid_parameter(
+            "series",
+            LocalizedText::new("informe pelo menos uma série", "give at least one series"),
+        ));
+    }
+    let k = series.len();
+    let mut matrix = vec![vec![0.0; k]; k];
+    for i in 0..k {
+        for j in i + 1..k {
+            let distance = metric.distance(series[i], series[j])?;
+            matrix[i][j] = distance;
+            matrix[j][i] = distance;
+        }
+    }
+    Ok(matrix)
+}
+
+#[cfg(test)]
+mod testes {
+    use super::*;
+    use crate::testes::{assert_approx_eq, pseudo_random};
+
+    #[test]
+    fn test_estatisticas_descritivas() {
+        let data = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+        let stats = calculate_descriptive_stats(&data).unwrap();
+
+        assert_eq!(stats.count, 5);
+        assert_approx_eq(stats.mean, 3.0, 1e-10);
+        assert_approx_eq(stats.std_dev, (2.0_f64).sqrt(), 1e-10);
+        assert_approx_eq(stats.variance, 2.0, 1e-10);
+        assert_approx_eq(stats.min, 1.0, 1e-10);
+        assert_approx_eq(stats.max, 5.0, 1e-10);
+        assert_approx_eq(stats.median, 3.0, 1e-10);
+        assert_approx_eq(stats.q1, 2.0, 1e-10);
+        assert_approx_eq(stats.q3, 4.0, 1e-10);
+    }
+
+    #[test]
+    fn test_mediana() {
+        assert_approx_eq(median(&[3.0, 1.0, 2.0]).unwrap(), 2.0, 1e-12);
+        assert_approx_eq(median(&[4.0, 1.0, 3.0, 2.0]).unwrap(), 2.5, 1e-12);
+        assert_approx_eq(median(&[7.0]).unwrap(), 7.0, 1e-12);
+        assert_approx_eq(median(&[1.0, 4.0]).unwrap(), 2.5, 1e-12);
+        assert!(median(&[]).is_err());
+    }
+
+    #[test]
+    fn test_percentil_interpolacao_linear() {
+        // Convenção fixada: h = (n - 1)·p/100 com interpolação linear
+        let data = vec![10.0, 20.0, 30.0, 40.0];
+        assert_approx_eq(percentile(&data, 0.0).unwrap(), 10.0, 1e-12);
+        assert_approx_eq(percentile(&data, 25.0).unwrap(), 17.5, 1e-12);
+        assert_approx_eq(percentile(&data, 90.0).unwrap(), 37.0, 1e-12);
+        assert_approx_eq(percentile(&data, 100.0).unwrap(), 40.0, 1e-12);
+        assert_approx_eq(percentile(&[1.0, 2.0], 75.0).unwrap(), 1.75, 1e-12);
+        assert_approx_eq(percentile(&[5.0], 33.0).unwrap(), 5.0, 1e-12);
+
+        assert!(percentile(&data, -0.1).is_err());
+        assert!(percentile(&data, 100.1).is_err());
+        assert!(percentile(&[], 50.0).is_err());
+    }
+
+    #[test]
+    fn test_assimetria_e_curtose() {
+        let data = vec![1.0, 2.0, 3.0, 4.0, 10.0];
+        assert_approx_eq(skewness(&data).unwrap(), 1.6970562748477143, 1e-12);
+        assert_approx_eq(kurtosis(&data).unwrap(), 3.152, 1e-12);
+
+        let symmetric = vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0];
+        assert_approx_eq(skewness(&symmetric).unwrap(), 0.0, 1e-12);
+    }
+
+    #[test]
+    fn test_assimetria_e_curtose_casos_invalidos() {
+        assert!(skewness(&[3.0, 3.0, 3.0, 3.0]).is_err());
+        assert!(kurtosis(&[3.0, 3.0, 3.0, 3.0]).is_err());
+        assert!(skewness(&[1.0, 2.0]).is_err());
+        assert!(kurtosis(&[1.0, 2.0, 3.0]).is_err());
+    }
+
+    #[test]
+    fn test_estatisticas_descritivas_display() {
+        let stats = calculate_descriptive_stats(&[100.0, 120.0, 130.0, 145.0, 160.0]).unwrap();
+        let text = stats.to_string();
+        assert!(text.starts_with("Estatisticas Descritivas:\n   Observacoes: 5\n   Media: 131.00\n"));
+        assert!(text.ends_with("   Q3: 145.00"));
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_estatisticas_descritivas_tupla_obsoleta() {
+        let (mean, std_dev, ..) = calculate_descriptive_stats_tuple(&[1.0, 2.0, 3.0, 4.0, 5.0]).unwrap();
+        assert_approx_eq(mean, 3.0, 1e-10);
+        assert_approx_eq(std_dev, (2.0_f64).sqrt(), 1e-10);
+    }
+
+    #[test]
+    fn test_welford_igual_a_duas_passagens() {
+        let data: Vec<f64> = (0..500).map(|i| ((i * 37) % 101) as f64 * 0.75 - 20.0).collect();
+        let n = data.len() as f64;
+        let mean = data.iter().sum::<f64>() / n;
+        let variance = data.iter().map(|&x| (x - mean).powi(2)).sum::<f64>() / n;
+
+        let stats = calculate_descriptive_stats(&data).unwrap();
+        assert_approx_eq(stats.mean, mean, 1e-9);
+        assert_approx_eq(stats.variance, variance, 1e-9);
+        assert_approx_eq(stats.std_dev, variance.sqrt(), 1e-9);
+    }
+
+    #[test]
+    fn test_welford_valores_grandes() {
+        // Em torno de 1e9 a soma dos quadrados perde precisão; Welford não
+        let data: Vec<f64> = (0..10_000).map(|i| 1e9 + (i % 4) as f64).collect();
+        let stats = calculate_descriptive_stats(&data).unwrap();
+        assert_approx_eq(stats.mean, 1e9 + 1.5, 1e-5);
+        assert_approx_eq(stats.variance, 1.25, 1e-6);
+    }
+
+    #[test]
+    fn test_running_stats_merge() {
+        let data: Vec<f64> = (0..97).map(|i| (i as f64 * 0.37).sin() * 10.0 + i as f64).collect();
+
+        let mut whole = RunningStats::new();
+        whole.extend(data.iter().copied());
+
+        let mut merged = RunningStats::new();
+        for chunk in data.chunks(13) {
+            let mut partial = RunningStats::new();
+            partial.extend(chunk.iter().copied());
+            merged.merge(&partial);
+        }
+        merged.merge(&RunningStats::new());
+
+        let a = whole.finish().unwrap();
+        let b = merged.finish().unwrap();
+        assert_eq!(a.count, b.count);
+        assert_approx_eq(a.mean, b.mean, 1e-9);
+        assert_approx_eq(a.variance, b.variance, 1e-9);
+        assert_eq!(a.min, b.min);
+        assert_eq!(a.max, b.max);
+
+        assert!(RunningStats::new().finish().is_err());
+    }
+
+    #[test]
+    fn test_acumulador_dividido_igual_ao_lote() {
+        let data: Vec<f64> = pseudo_random(360, 500).iter().enumerate().map(|(i, e)| 1e6 + i as f64 * 0.5 + e).collect();
+        let batch = calculate_descriptive_stats(&data).unwrap();
+
+        let mut whole = RunningStats::new();
+        data.iter().for_each(|&v| whole.push(v));
+        let (mut left, mut right) = (RunningStats::new(), RunningStats::new());
+        data[..173].iter().for_each(|&v| left.push(v));
+        right.extend(data[173..].iter().copied());
+        left.merge(&right);
+
+        for summary in [whole.finish().unwrap(), left.finish().unwrap()] {
+            assert_eq!(summary.count, batch.count);
+            assert_approx_eq(summary.mean, batch.mean, 1e-9);
+            assert_approx_eq(summary.variance / batch.variance, 1.0, 1e-9);
+            assert_approx_eq(summary.std_dev / batch.std_dev, 1.0, 1e-9);
+            assert_eq!((summary.min, summary.max), (batch.min, batch.max));
+        }
+        assert_eq!(
+            RunningStats::new().finish().unwrap_err(),
+            calculate_descriptive_stats(&[]).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_estatisticas_iterador_igual_a_slice() {
+        let data: Vec<f64> = (0..50).map(|i| (i as f64 * 0.7).cos() * 4.0).collect();
+        let stats = calculate_descriptive_stats(&data).unwrap();
+        let streamed = descriptive_stats_iter(data.iter().copied()).unwrap();
+
+        assert_eq!(streamed.count, stats.count);
+        assert_eq!(streamed.mean, stats.mean);
+        assert_eq!(streamed.variance, stats.variance);
+        assert_eq!(streamed.min, stats.min);
+        assert_eq!(streamed.max, stats.max);
+        assert!(descriptive_stats_iter(std::iter::empty()).is_err());
+    }
+
+    #[test]
+    fn test_drawdowns() {
+        let rising = drawdowns(&[1.0, 2.0, 3.0, 4.0]).unwrap();
+        assert_eq!(rising.max_drawdown, 0.0);
+        assert_eq!(rising.max_drawdown_pct, Some(0.0));
+        assert_eq!((rising.peak_index, rising.trough_index, rising.recovery_index), (0, 0, None));
+
+        let data = [100.0, 120.0, 110.0, 90.0, 105.0, 125.0, 118.0];
+        let report = drawdowns(&data).unwrap();
+        assert_eq!(report.max_drawdown, 30.0);
+        assert_approx_eq(report.max_drawdown_pct.unwrap(), 25.0, 1e-12);
+        assert_eq!((report.peak_index, report.trough_index, report.recovery_index), (1, 3, Some(5)));
+        assert_eq!(report.drawdown[6], 7.0);
+
+        // Picos não positivos não têm percentual
+        let negative = drawdowns(&[-5.0, -8.0]).unwrap();
+        assert_eq!(negative.max_drawdown, 3.0);
+        assert_eq!(negative.max_drawdown_pct, None);
+        assert_eq!(negative.drawdown_pct, vec![None, None]);
+    }
+
+    #[test]
+    fn test_quantil_t_student() {
+        assert_approx_eq(student_t_quantile(0.975, 1.0), 12.706_204_736, 1e-6);
+        assert_approx_eq(student_t_quantile(0.975, 10.0), 2.228_138_852, 1e-8);
+        assert_approx_eq(student_t_quantile(0.95, 30.0), 1.697_260_887, 1e-8);
+        assert_approx_eq(student_t_quantile(0.025, 10.0), -2.228_138_852, 1e-8);
+        assert_approx_eq(student_t_cdf(0.0, 5.0), 0.5, 1e-12);
+    }
+
+    #[test]
+    fn test_lead_lag() {
+        let x: Vec<f64> = pseudo_random(3, 40).iter().map(|u| 10.0 * u).collect();
+        // y[t] = 2·x[t-3] + 1
+        let y: Vec<f64> = (0..40).map(|t| if t >= 3 { 2.0 * x[t - 3] + 1.0 } else { 0.0 }).collect();
+        let report = lead_lag_analysis(&x, &y, 5).unwrap();
+        assert_eq!(report.fits.len(), 6);
+        assert_eq!(report.best_lag, 3);
+        assert_approx_eq(report.fits[3].slope, 2.0, 1e-9);
+        assert_approx_eq(report.fits[3].r_squared, 1.0, 1e-9);
+        assert!(report.fits[0].r_squared < 0.5);
+        assert!(report.to_string().contains("Melhor defasagem: 3"));
+        assert!(lead_lag_analysis(&x, &y, 38).is_err());
+
+        // x constante nas primeiras janelas só invalida as defasagens afetadas
+        let x = [1.0, 1.0, 1.0, 1.0, 2.0];
+        let y = [1.0, 1.0, 1.0, 1.0, 1.0];
+        let report = lead_lag_analysis(&x, &y, 2).unwrap();
+        assert_eq!(report.fits.len(), 1);
+        assert_eq!(report.skipped.iter().map(|(lag, _)| *lag).collect::<Vec<_>>(), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_growth_stats() {
+        let growth = growth_stats(&[100.0, 110.0, 99.0, 121.0]).unwrap();
+        assert_approx_eq(growth.total_change, 21.0, 1e-12);
+        assert_approx_eq(growth.total_percent_change.unwrap(), 21.0, 1e-12);
+        assert_approx_eq(growth.compound_growth_rate.unwrap(), (1.21_f64.powf(1.0 / 3.0) - 1.0) * 100.0, 1e-12);
+        assert_approx_eq(growth.average_percent_change.unwrap(), (10.0 - 10.0 + 22.0 / 0.99) / 3.0, 1e-9);
+        assert_eq!((growth.up_periods, growth.down_periods, growth.flat_periods), (2, 1, 0));
+        assert!(growth.to_string().contains("Crescimento Composto por Periodo: 6.56%"));
+
+        let undefined = growth_stats(&[0.0, 5.0, 5.0]).unwrap();
+        assert_eq!(undefined.compound_growth_rate, None);
+        assert_eq!(undefined.total_percent_change, None);
+        assert_eq!(undefined.flat_periods, 1);
+        assert!(undefined.to_string().contains("Crescimento Composto por Periodo: n/d"));
+        assert_eq!(growth_stats(&[-10.0, 5.0]).unwrap().compound_growth_rate, None);
+        assert!(growth_stats(&[1.0]).is_err());
+    }
+
+    #[test]
+    fn test_media_aparada() {
+        assert_approx_eq(trimmed_mean(&[1.0, 2.0, 3.0, 4.0, 100.0], 0.2).unwrap(), 3.0, 1e-12);
+        // Empates: descarta um 5 e o 1 embaixo, o 9 em cima
+        assert_approx_eq(trimmed_mean(&[5.0, 5.0, 5.0, 1.0, 9.0, 5.0], 0.2).unwrap(), 5.0, 1e-12);
+        assert_approx_eq(trimmed_mean(&[1.0, 2.0, 6.0], 0.0).unwrap(), 3.0, 1e-12);
+        // n pequeno: ⌊1·0.49⌋ = 0, o único ponto permanece
+        assert_approx_eq(trimmed_mean(&[7.0], 0.49).unwrap(), 7.0, 1e-12);
+
+        assert!(trimmed_mean(&[1.0, 2.0], 0.5).is_err());
+        assert!(trimmed_mean(&[1.0, 2.0], -0.1).is_err());
+        assert!(trimmed_mean(&[], 0.1).is_err());
+    }
+
+    #[test]
+    fn test_desvio_absoluto_mediano() {
+        // Mediana 3.5; desvios ordenados 0.5, 0.5, 1.5, 2.5, 6.5, 16.5 → MAD 2.0
+        let data = [1.0, 2.0, 3.0, 4.0, 10.0, 20.0];
+        assert_approx_eq(median_absolute_deviation(&data).unwrap(), 2.0, 1e-12);
+        assert_approx_eq(median_absolute_deviation(&[1.0, 1.0, 2.0, 2.0, 4.0, 6.0, 9.0]).unwrap(), 1.0, 1e-12);
+
+        let robust = robust_stats(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 1000.0]).unwrap();
+        assert_approx_eq(robust.median, 5.5, 1e-12);
+        assert_approx_eq(robust.trimmed_mean, 5.5, 1e-12);
+        assert_approx_eq(robust.mad, 2.5, 1e-12);
+        assert!(median_absolute_deviation(&[]).is_err());
+    }
+
+    #[test]
+    fn test_comparar_segmentos_iguais() {
+        let data = pseudo_random(7, 40);
+        let comparison = compare_segments(&data, &data).unwrap();
+        assert_approx_eq(comparison.mean_difference, 0.0, 1e-12);
+        assert_approx_eq(comparison.welch_t, 0.0, 1e-12);
+        assert_approx_eq(comparison.welch_p_value, 1.0, 1e-9);
+        assert_approx_eq(comparison.median_difference, 0.0, 1e-12);
+        assert_approx_eq(comparison.variance_ratio, 1.0, 1e-12);
+        assert_approx_eq(comparison.f_p_value, 1.0, 1e-6);
+        assert_approx_eq(comparison.ks_statistic, 0.0, 1e-12);
+        assert_approx_eq(comparison.ks_p_value, 1.0, 1e-12);
+        assert!(comparison.to_string().starts_with("Comparacao de Segmentos:"));
+    }
+
+    #[test]
+    fn test_comparar_segmentos_deslocados() {
+        let before = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0];
+        let after: Vec<f64> = before.iter().map(|v| v + 20.0).collect();
+        let comparison = compare_segments(&before, &after).unwrap();
+
+        assert_approx_eq(comparison.mean_difference, 20.0, 1e-12);
+        assert_approx_eq(comparison.median_difference, 20.0, 1e-12);
+        // Variâncias iguais (6): t = 20 / √(6/8 + 6/8), gl = 14
+        assert_approx_eq(comparison.welch_t, 20.0 / 1.5f64.sqrt(), 1e-9);
+        assert_approx_eq(comparison.welch_degrees_of_freedom, 14.0, 1e-9);
+        assert!(comparison.welch_p_value < 1e-9);
+        assert_approx_eq(comparison.variance_ratio, 1.0, 1e-12);
+        // Sem sobreposição: D = 1
+        assert_approx_eq(comparison.ks_statistic, 1.0, 1e-12);
+        assert!(comparison.ks_p_value < 0.01);
+
+        assert!(matches!(
+            compare_segments(&before, &[1.0, 2.0]),
+            Err(TimeSeriesError::InvalidParameter { name: "after", .. })
+        ));
+    }
+
+    #[test]
+    fn test_cusum_detecta_mudanca_de_media() {
+        // Ruído em torno de 0 com desvio ~0.29; a média sobe 1.0 a partir do índice 100
+        let noise: Vec<f64> = pseudo_random(11, 200).iter().map(|v| v - 0.5).collect();
+        let shifted: Vec<f64> = noise.iter().enumerate().map(|(i, v)| if i >= 100 { v + 1.0 } else { *v }).collect();
+
+        let alarms = cusum_scan(&shifted, 0.0, 0.5, 2.0).unwrap();
+        assert!(!alarms.is_empty());
+        assert!(alarms[0] >= 100 && alarms[0] < 110, "alarme em {}", alarms[0]);
+        assert!(cusum_scan(&noise, 0.0, 0.5, 2.0).unwrap().is_empty());
+
+        let mut detector = CusumDetector::new(0.0, 0.5, 2.0).unwrap();
+        let signal = shifted.iter().find_map(|&v| detector.push(v)).unwrap();
+        assert_eq!(signal.direction, DriftDirection::Up);
+        assert_eq!(signal.index, alarms[0]);
+        assert!(signal.statistic > 2.0);
+        assert_eq!(detector.upper(), 0.0);
+
+        let down = [0.0, -1.0, -1.0, -1.0];
+        let mut detector = CusumDetector::new(0.0, 0.5, 1.0).unwrap();
+        let signals: Vec<DriftSignal> = down.iter().filter_map(|&v| detector.push(v)).collect();
+        assert_eq!(signals.len(), 1);
+        assert_eq!((signals[0].direction, signals[0].index), (DriftDirection::Down, 3));
+        assert_approx_eq(signals[0].statistic, 1.5, 1e-12);
+
+        assert!(CusumDetector::new(0.0, 0.5, 0.0).is_err());
+        assert!(CusumDetector::new(0.0, -0.1, 1.0).is_err());
+    }
+
+    #[test]
+    fn test_agregar_series() {
+        let a = [1.0, 2.0, 3.0];
+        let b = [4.0, 6.0, 0.0];
+        let c = [7.0, 1.0, 3.0];
+        let series: [&[f64]; 3] = [&a, &b, &c];
+
+        assert_eq!(aggregate_series(&series, Aggregation::Mean).unwrap(), vec![4.0, 3.0, 2.0]);
+        assert_eq!(aggregate_series(&series, Aggregation::Median).unwrap(), vec![4.0, 2.0, 3.0]);
+        assert_eq!(aggregate_series(&series, Aggregation::Min).unwrap(), vec![1.0, 1.0, 0.0]);
+        assert_eq!(aggregate_series(&series, Aggregation::Max).unwrap(), vec![7.0, 6.0, 3.0]);
+        assert_eq!(aggregate_series(&series, Aggregation::Sum).unwrap(), vec![12.0, 9.0, 6.0]);
+
+        let stats = aggregate_stats(&series).unwrap();
+        assert_eq!(stats.len(), 3);
+        assert_approx_eq(stats[1].mean, 3.0, 1e-12);
+        // Variância populacional de (3, 0, 3): média 2, desvios 1, -2, 1 → 6/3
+        assert_approx_eq(stats[2].variance, 2.0, 1e-12);
+
+        assert_eq!(
+            aggregate_series(&[&a, &b[..2]], Aggregation::Mean),
+            Err(TimeSeriesError::LengthMismatch { left: 3, right: 2 })
+        );
+        assert!(aggregate_series(&[], Aggregation::Mean).is_err());
+        assert!(aggregate_series(&[&a, &[]], Aggregation::Mean).is_err());
+    }
+
+    #[test]
+    fn test_agregar_series_parcial() {
+        let a = [1.0, 2.0, 3.0, 10.0];
+        let b = [3.0, 4.0];
+        let (values, counts) = aggregate_series_partial(&[&a, &b], Aggregation::Mean).unwrap();
+        assert_eq!(values, vec![2.0, 3.0, 3.0, 10.0]);
+        assert_eq!(counts, vec![2, 2, 1, 1]);
+    }
+
+    #[test]
+    fn test_autocorrelacao() {
+        let data = [1.0, 2.0, 3.0, 4.0];
+        // Centrada: -1.5, -0.5, 0.5, 1.5; Σd² = 5; lag 1: 0.75 - 0.25 + 0.75 = 1.25
+        let acf = autocorrelation(&data, 2).unwrap();
+        assert_approx_eq(acf[0], 1.0, 1e-12);
+        assert_approx_eq(acf[1], 0.25, 1e-12);
+        assert_approx_eq(acf[2], (-0.75 - 0.75) / 5.0, 1e-12);
+        assert!(autocorrelation(&data, 4).is_err());
+        assert!(autocorrelation(&[2.0; 5], 1).is_err());
+    }
+
+    #[test]
+    fn test_plateaus_em_escada() {
+        // degraus de 4, 3 e 5 pontos com pequeno ruído
+        let data = [1.0, 1.01, 0.99, 1.0, 2.0, 2.02, 2.0, 3.0, 3.01, 2.99, 3.0, 3.02];
+        assert_eq!(detect_plateaus(&data, 0.05, 3).unwrap(), vec![(0, 4), (4, 3), (7, 5)]);
+        assert_eq!(detect_plateaus(&data, 0.05, 4).unwrap(), vec![(0, 4), (7, 5)]);
+        assert!(detect_plateaus(&data, 0.0, 2).unwrap().is_empty());
+        assert_eq!(detect_plateaus(&[5.0, 5.0, 5.0], 0.0, 2).unwrap(), vec![(0, 3)]);
+
+        assert!(is_monotonic(&data, Direction::Increasing, 0.02).unwrap());
+        assert!(!is_monotonic(&data, Direction::Increasing, 0.0).unwrap());
+        assert!(!is_monotonic(&data, Direction::Decreasing, 0.5).unwrap());
+        assert_eq!(longest_monotonic_run(&[2.0, 2.0, 2.0]).unwrap(), (0, 3, Direction::Increasing));
+        assert_eq!(longest_monotonic_run(&[7.0]).unwrap(), (0, 1, Direction::Increasing));
+
+        assert!(detect_plateaus(&[], 0.1, 2).is_err());
+        assert!(detect_plateaus(&data, -0.1, 2).is_err());
+        assert!(detect_plateaus(&data, 0.1, 1).is_err());
+        assert!(is_monotonic(&data, Direction::Increasing, f64::NAN).is_err());
+        assert!(longest_monotonic_run(&[]).is_err());
+    }
+
+    #[test]
+    fn test_monotonia_em_rampa_ruidosa() {
+        let noise = pseudo_random(17, 60);
+        let ramp: Vec<f64> = noise.iter().enumerate().map(|(i, e)| i as f64 + 0.4 * (e - 0.5)).collect();
+        // ruído de amplitude 0.4 nunca recua mais de 0.4 abaixo do máximo acumulado
+        assert!(is_monotonic(&ramp, Direction::Increasing, 0.4).unwrap());
+        let falling: Vec<f64> = ramp.iter().map(|v| -v).collect();
+        assert!(is_monotonic(&falling, Direction::Decreasing, 0.4).unwrap());
+        assert!(!is_monotonic(&falling, Direction::Increasing, 0.4).unwrap());
+
+        let data = [3.0, 1.0, 2.0, 2.0, 4.0, 3.0, 2.0, 1.0, 0.0];
+        assert_eq!(longest_monotonic_run(&data).unwrap(), (4, 5, Direction::Decreasing));
+        // empate: o trecho de alta começa antes
+        assert_eq!(longest_monotonic_run(&[1.0, 2.0, 3.0, 2.0, 1.0]).unwrap(), (0, 3, Direction::Increasing));
+        assert!(detect_plateaus(&ramp, 0.5, 3).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_dtw_serie_defasada() {
+        let base: Vec<f64> = (0..80).map(|i| (i as f64 * 0.3).sin() + if (30..40).contains(&i) { 2.0 } else { 0.0 }).collect();
+        let shifted: Vec<f64> = (0..80usize).map(|i| base[i.saturating_sub(3)]).collect();
+        let euclidean = euclidean_distance(&base, &shifted).unwrap();
+        let dtw = dynamic_time_warping(&base, &shifted, None).unwrap();
+        assert!(dtw < euclidean / 5.0, "dtw {} vs euclidiana {}", dtw, euclidean);
+        // faixa estreita demais para absorver a defasagem; ainda assim limitada pela euclidiana
+        let banded = dynamic_time_warping(&base, &shifted, Some(3)).unwrap();
+        assert_approx_eq(banded, dtw, 1e-12);
+        let narrow = dynamic_time_warping(&base, &shifted, Some(1)).unwrap();
+        assert!(narrow > dtw && narrow <= euclidean);
+        assert_approx_eq(dynamic_time_warping(&base, &shifted, Some(0)).unwrap(), euclidean, 1e-12);
+
+        // tamanhos diferentes só no DTW
+        assert_approx_eq(dynamic_time_warping(&[1.0, 2.0, 3.0], &[1.0, 2.0, 2.0, 3.0], Some(0)).unwrap(), 0.0, 1e-12);
+        assert!(matches!(euclidean_distance(&[1.0, 2.0], &[1.0]), Err(TimeSeriesError::LengthMismatch { .. })));
+        assert!(dynamic_time_warping(&[], &[1.0], None).is_err());
+
+        // faixa contra a matriz completa, com as células fora da faixa infinitas
+        let brute_force = |a: &[f64], b: &[f64], window: usize| {
+            let mut cost = vec![vec![f64::INFINITY; b.len() + 1]; a.len() + 1];
+            cost[0][0] = 0.0;
+            for i in 1..=a.len() {
+                for j in 1..=b.len() {
+                    if i.abs_diff(j) <= window {
+                        let d = (a[i - 1] - b[j - 1]) * (a[i - 1] - b[j - 1]);
+                        cost[i][j] = d + cost[i - 1][j - 1].min(cost[i - 1][j]).min(cost[i][j - 1]);
+                    }
+                }
+            }
+            cost[a.len()][b.len()].sqrt()
+        };
+        let x = pseudo_random(392, 37);
+        let y = pseudo_random(393, 29);
+        for window in [8, 9, 12, 20, 40] {
+            let expected = brute_force(&x, &y, window);
+            assert_approx_eq(dynamic_time_warping(&x, &y, Some(window)).unwrap(), expected, 1e-12);
+            assert_approx_eq(dynamic_time_warping(&y, &x, Some(window)).unwrap(), expected, 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_matriz_de_distancias() {
+        let a = [1.0, 2.0, 3.0, 4.0];
+        let b = [10.0, 20.0, 30.0, 40.0];
+        let c = [4.0, 3.0, 2.0, 1.0];
+        assert_approx_eq(correlation_distance(&a, &b).unwrap(), 0.0, 1e-12);
+        assert_approx_eq(correlation_distance(&a, &c).unwrap(), 2.0, 1e-12);
+        assert!(correlation_distance(&a, &[5.0; 4]).is_err());
+
+        let matrix = distance_matrix(&[&a, &b, &c], DistanceMetric::Euclidean).unwrap();
+        assert_eq!(matrix.len(), 3);
+        for (i, row) in matrix.iter().enumerate() {
+            assert_eq!(row[i], 0.0);
+            assert!(row.iter().enumerate().all(|(j, &d)| d == matrix[j][i]));
+        }
+        assert_approx_eq(matrix[0][2], 20f64.sqrt(), 1e-12);
+        assert!(distance_matrix(&[&a, &[1.0, 2.0]], DistanceMetric::Correlation).is_err());
+        assert!(distance_matrix(&[&a, &[1.0, 2.0]], DistanceMetric::Dtw { window: Some(1) }).is_ok());
+        assert!(distance_matrix(&[], DistanceMetric::Euclidean).is_err());
+    }
+}
@@ -0,0 +1,597 @@
+//! Transformações de escala e diferenciação
+//!
+//! Padronização, normalização, log e Box-Cox (com as inversas), variações percentuais e o
+//! [`TransformPipeline`], que encadeia transformações e desfaz todas de uma vez.
+//!
+//! ```
+//! use timeseries_analysis::transform::{destandardize, standardize};
+//!
+//! let (z, mean, std_dev) = standardize(&[2.0, 4.0, 6.0]).unwrap();
+//! assert_eq!(mean, 4.0);
+//! assert_eq!(destandardize(&z, mean, std_dev), vec![2.0, 4.0, 6.0]);
+//! ```
+
+use crate::*;
+
+/// Padroniza a série (z-score), retornando `(z, média, desvio padrão)`
+///
+/// Usa o desvio padrão populacional, como [`calculate_descriptive_stats`]. A média e o
+/// desvio retornados permitem voltar à escala original com [`destandardize`].
+pub fn standardize(data: &[f64]) -> Result<(Vec<f64>, f64, f64), TimeSeriesError> {
+    if data.is_empty() {
+        return Err(TimeSeriesError::insufficient_data(1, 0));
+    }
+    validate_finite(data)?;
+    let summary = descriptive_stats_iter(data.iter().copied())?;
+    if approx_zero(summary.std_dev, summary.min.abs().max(summary.max.abs())) {
+        return Err(TimeSeriesError::new(
+            LocalizedText::new(
+                "Padronização indefinida para série com variância zero",
+                "Standardization undefined for a series with zero variance",
+            ),
+        ));
+    }
+
+    let z = data.iter().map(|&v| (v - summary.mean) / summary.std_dev).collect();
+    Ok((z, summary.mean, summary.std_dev))
+}
+
+/// Inversa de [`standardize`]: `v = z · desvio + média`
+pub fn destandardize(data: &[f64], mean: f64, std_dev: f64) -> Vec<f64> {
+    data.iter().map(|&z| z * std_dev + mean).collect()
+}
+
+/// Mapeia a série linearmente para o intervalo `[lo, hi]`, retornando `(valores, mínimo, máximo)`
+///
+/// O mínimo e o máximo originais permitem voltar à escala original com
+/// [`min_max_denormalize`]. Exige `lo < hi` e dados com amplitude não nula.
+pub fn min_max_normalize(data: &[f64], lo: f64, hi: f64) -> Result<(Vec<f64>, f64, f64), TimeSeriesError> {
+    if !lo.is_finite() || !hi.is_finite() || lo >= hi {
+        return Err(TimeSeriesError::invalid_parameter(
+            "lo/hi",
+            LocalizedText::new("o intervalo deve satisfazer lo < hi", "the interval must satisfy lo < hi"),
+        ));
+    }
+    if data.is_empty() {
+        return Err(TimeSeriesError::insufficient_data(1, 0));
+    }
+    validate_finite(data)?;
+    let min_val = data.iter().fold(f64::INFINITY, |a, &b| a.min(b));
+    let max_val = data.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
+    let range = max_val - min_val;
+    if approx_zero(range, min_val.abs().max(max_val.abs())) {
+        return Err(TimeSeriesError::new(
+            LocalizedText::new(
+                "Normalização indefinida para série com amplitude zero",
+                "Normalization undefined for a series with zero range",
+            ),
+        ));
+    }
+
+    let scale = (hi - lo) / range;
+    let normalized = data.iter().map(|&v| lo + (v - min_val) * scale).collect();
+    Ok((normalized, min_val, max_val))
+}
+
+/// Inversa de [`min_max_normalize`], levando valores de `[lo, hi]` de volta a `[min, max]`
+pub fn min_max_denormalize(data: &[f64], lo: f64, hi: f64, min: f64, max: f64) -> Vec<f64> {
+    let scale = (max - min) / (hi - lo);
+    data.iter().map(|&v| min + (v - lo) * scale).collect()
+}
+
+/// Garante que todos os valores são finitos e estritamente positivos
+pub(crate) fn validate_positive(data: &[f64]) -> Result<(), TimeSeriesError> {
+    validate_finite(data)?;
+    match data.iter().position(|&v| v <= 0.0) {
+        Some(index) => Err(TimeSeriesError::invalid_parameter(
+            "data",
+            LocalizedText::new(
+                format!("exige valores positivos, mas o índice {} vale {}", index, data[index]),
+                format!("requires positive values, but index {} is {}", index, data[index]),
+            ),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Aplica o logaritmo natural a cada valor; erro se algum valor não for positivo
+pub fn log_transform(data: &[f64]) -> Result<Vec<f64>, TimeSeriesError> {
+    validate_positive(data)?;
+    Ok(data.iter().map(|v| v.ln()).collect())
+}
+
+/// Inversa de [`log_transform`]
+pub fn inverse_log_transform(data: &[f64]) -> Vec<f64> {
+    data.iter().map(|v| v.exp()).collect()
+}
+
+/// Transformação de Box-Cox: `(y^λ - 1)/λ`, ou `ln(y)` quando `λ = 0`
+///
+/// Exige valores estritamente positivos.
+pub fn box_cox(data: &[f64], lambda: f64) -> Result<Vec<f64>, TimeSeriesError> {
+    validate_positive(data)?;
+    if !lambda.is_finite() {
+        return Err(TimeSeriesError::invalid_parameter(
+            "lambda",
+            LocalizedText::new("deve ser finito", "must be finite"),
+        ));
+    }
+    Ok(data.iter().map(|&y| box_cox_value(y, lambda)).collect())
+}
+
+fn box_cox_value(y: f64, lambda: f64) -> f64 {
+    if lambda.abs() < 1e-12 {
+        y.ln()
+    } else {
+        (y.powf(lambda) - 1.0) / lambda
+    }
+}
+
+/// Inversa de [`box_cox`]: `(λ·z + 1)^(1/λ)`, ou `exp(z)` quando `λ = 0`
+///
+/// Permite levar previsões feitas na escala transformada de volta às unidades
+/// originais. Valores fora do domínio (`λ·z + 1 ≤ 0`) resultam em NaN.
+pub fn inverse_box_cox(data: &[f64], lambda: f64) -> Vec<f64> {
+    data.iter()
+        .map(|&z| {
+            if lambda.abs() < 1e-12 {
+                z.exp()
+            } else {
+                let base = lambda * z + 1.0;
+                if base > 0.0 { base.powf(1.0 / lambda) } else { f64::NAN }
+            }
+        })
+        .collect()
+}
+
+/// Escolhe em `grid` o λ de Box-Cox que maximiza a log-verossimilhança perfilada
+///
+/// `ℓ(λ) = -(n/2)·ln(σ²_λ) + (λ - 1)·Σ ln(y)`, onde `σ²_λ` é a variância (divisor n) da
+/// série transformada; é o critério clássico de Box e Cox, que supõe a série
+/// transformada aproximadamente normal. Valores de λ cuja série transformada não é finita
+/// são ignorados; o erro só ocorre se nenhum valor da grade for utilizável.
+pub fn box_cox_optimal_lambda(data: &[f64], grid: &[f64]) -> Result<f64, TimeSeriesError> {
+    if grid.is_empty() {
+        return Err(TimeSeriesError::invalid_parameter(
+            "grid",
+            LocalizedText::new("informe ao menos um valor de lambda", "give at least one lambda value"),
+        ));
+    }
+    if data.len() < 2 {
+        return Err(TimeSeriesError::insufficient_data(2, data.len()));
+    }
+    validate_positive(data)?;
+    validate_finite(grid)?;
+
+    let n = data.len() as f64;
+    let log_sum: f64 = data.iter().map(|v| v.ln()).sum();
+    // `None` quando a série transformada transborda (por exemplo 2^2000): o λ é ignorado
+    let log_likelihood = |lambda: f64| {
+        let summary = descriptive_stats_iter(data.iter().map(|&y| box_cox_value(y, lambda))).ok()?;
+        let llf = -n / 2.0 * summary.variance.max(MIN_RSS).ln() + (lambda - 1.0) * log_sum;
+        llf.is_finite().then_some(llf)
+    };
+
+    let mut best: Option<(f64, f64)> = None;
+    for &lambda in grid {
+        if let Some(llf) = log_likelihood(lambda) {
+            if best.is_none_or(|(_, best_llf)| llf > best_llf) {
+                best = Some((lambda, llf));
+            }
+        }
+    }
+    best.map(|(lambda, _)| lambda).ok_or_else(|| {
+        TimeSeriesError::invalid_parameter(
+            "grid",
+            LocalizedText::new(
+                "nenhum lambda produz uma série transformada finita",
+                "no lambda yields a finite transformed series",
+            ),
+        )
+    })
+}
+
+/// Etapa de um [`TransformPipeline`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Transform {
+    /// Logaritmo natural ([`log_transform`]); exige valores positivos
+    Log,
+    /// Box-Cox com o `lambda` dado ([`box_cox`]); exige valores positivos
+    BoxCox(f64),
+    /// Diferença de primeira ordem aplicada `order` vezes; a série perde `order` pontos
+    Difference(usize),
+    /// `y[t] - y[t - period]`; a série perde `period` pontos
+    SeasonalDifference(usize),
+    /// Z-score com média e desvio da série ([`standardize`])
+    Standardize,
+}
+
+impl fmt::Display for Transform {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Transform::Log => write!(f, "log"),
+            Transform::BoxCox(lambda) => write!(f, "Box-Cox (lambda {})", lambda),
+            Transform::Difference(order) => write!(f, "diferença de ordem {}", order),
+            Transform::SeasonalDifference(period) => write!(f, "diferença sazonal de período {}", period),
+            Transform::Standardize => write!(f, "padronização"),
+        }
+    }
+}
+
+/// Estado de uma etapa aplicada, necessário para desfazê-la
+#[derive(Debug, Clone, PartialEq)]
+enum TransformState {
+    Stateless,
+    /// Primeiro e último valor de cada nível de diferenciação (0 é a entrada da etapa)
+    Difference { heads: Vec<f64>, tails: Vec<f64> },
+    /// Primeiros e últimos `period` valores da entrada da etapa
+    SeasonalDifference { head: Vec<f64>, tail: Vec<f64> },
+    Standardize { mean: f64, std_dev: f64 },
+}
+
+/// Sequência de transformações inversíveis, para ajustar e prever numa escala transformada
+///
+/// [`apply`](TransformPipeline::apply) aplica as etapas em ordem e guarda o que é
+/// preciso para invertê-las (valores iniciais e finais das diferenças, média e desvio);
+/// [`invert_forecast`](TransformPipeline::invert_forecast) leva previsões feitas sobre o
+/// resultado de volta à escala original, desfazendo as etapas na ordem inversa.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransformPipeline {
+    steps: Vec<Transform>,
+    states: Vec<TransformState>,
+}
+
+impl TransformPipeline {
+    pub fn new(steps: Vec<Transform>) -> Self {
+        TransformPipeline {
+            steps,
+            states: Vec::new(),
+        }
+    }
+
+    pub fn steps(&self) -> &[Transform] {
+        &self.steps
+    }
+
+    /// Aplica as etapas a `data`, substituindo o estado de uma aplicação anterior
+    ///
+    /// Um erro em qualquer etapa (por exemplo, log depois de uma diferença que gerou
+    /// valores não positivos) indica o índice e o nome da etapa; nesse caso o estado
+    /// anterior é descartado.
+    pub fn apply(&mut self, data: &[f64]) -> Result<Vec<f64>, TimeSeriesError> {
+        self.states.clear();
+        validate_finite(data)?;
+        let mut values = data.to_vec();
+        let mut states = Vec::with_capacity(self.steps.len());
+        for (index, step) in self.steps.iter().enumerate() {
+            let (next, state) = Self::apply_step(*step, &values).map_err(|err| {
+                TimeSeriesError::invalid_parameter(
+                    "steps",
+                    LocalizedText::new(
+                        format!("etapa {} ({}): {}", index, step, err.to_localized_string(Language::Pt)),
+                        format!("step {} ({}): {}", index, step, err.to_localized_string(Language::En)),
+                    ),
+                )
+            })?;
+            values = next;
+            states.push(state);
+        }
+        self.states = states;
+        Ok(values)
+    }
+
+    fn apply_step(step: Transform, data: &[f64]) -> Result<(Vec<f64>, TransformState), TimeSeriesError> {
+        match step {
+            Transform::Log => Ok((log_transform(data)?, TransformState::Stateless)),
+            Transform::BoxCox(lambda) => Ok((box_cox(data, lambda)?, TransformState::Stateless)),
+            Transform::Difference(order) => {
+                if order == 0 {
+                    return Err(TimeSeriesError::invalid_parameter(
+                        "order",
+                        LocalizedText::new("deve ser maior que zero", "must be greater than zero"),
+                    ));
+                }
+                if data.len() <= order {
+                    return Err(TimeSeriesError::insufficient_data(order + 1, data.len()));
+                }
+                let mut values = data.to_vec();
+                let (mut heads, mut tails) = (Vec::with_capacity(order), Vec::with_capacity(order));
+                for _ in 0..order {
+                    heads.push(values[0]);
+                    tails.push(values[values.len() - 1]);
+                    values = values.windows(2).map(|pair| pair[1] - pair[0]).collect();
+                }
+                Ok((values, TransformState::Difference { heads, tails }))
+            }
+            Transform::SeasonalDifference(period) => {
+                if period == 0 {
+                    return Err(TimeSeriesError::invalid_parameter(
+                        "period",
+                        LocalizedText::new("deve ser maior que zero", "must be greater than zero"),
+                    ));
+                }
+                if data.len() <= period {
+                    return Err(TimeSeriesError::insufficient_data(period + 1, data.len()));
+                }
+                let values = data[period..].iter().zip(data).map(|(y, lagged)| y - lagged).collect();
+                let state = TransformState::SeasonalDifference {
+                    head: data[..period].to_vec(),
+                    tail: data[data.len() - period..].to_vec(),
+                };
+                Ok((values, state))
+            }
+            Transform::Standardize => {
+                let (values, mean, std_dev) = standardize(data)?;
+                Ok((values, TransformState::Standardize { mean, std_dev }))
+            }
+        }
+    }
+
+    fn applied_states(&self) -> Result<&[TransformState], TimeSeriesError> {
+        if self.states.len() != self.steps.len() {
+            return Err(TimeSeriesError::new(
+                LocalizedText::new(
+                    "Pipeline ainda não aplicado: chame apply antes de inverter",
+                    "Pipeline not applied yet: call apply before inverting",
+                ),
+            ));
+        }
+        Ok(&self.states)
+    }
+
+    /// Desfaz as etapas sobre a própria série transformada, recuperando a série original
+    pub fn invert(&self, transformed: &[f64]) -> Result<Vec<f64>, TimeSeriesError> {
+        let states = self.applied_states()?;
+        let mut values = transformed.to_vec();
+        for (step, state) in self.steps.iter().zip(states).rev() {
+            values = match (step, state) {
+                (Transform::Difference(_), TransformState::Difference { heads, .. }) => {
+                    heads.iter().rev().fold(values, |diffs, &head| {
+                        core::iter::once(head)
+                            .chain(diffs.iter().scan(head, |level, d| {
+                                *level += d;
+                                Some(*level)
+                            }))
+                            .collect()
+                    })
+                }
+                (Transform::SeasonalDifference(_), TransformState::SeasonalDifference { head, .. }) => {
+                    let mut original = head.clone();
+                    for (t, d) in values.iter().enumerate() {
+                        original.push(d + original[t]);
+                    }
+                    original
+                }
+                _ => Self::invert_pointwise(*step, state, &values),
+            };
+        }
+        Ok(values)
+    }
+
+    /// Leva previsões feitas sobre a série transformada de volta à escala original
+    ///
+    /// `forecast[h]` é a previsão para o período `h` após o fim da série transformada; as
+    /// diferenças são desfeitas a partir dos últimos valores guardados por `apply`.
+    pub fn invert_forecast(&self, forecast: &[f64]) -> Result<Vec<f64>, TimeSeriesError> {
+        let states = self.applied_states()?;
+        validate_finite(forecast)?;
+        let mut values = forecast.to_vec();
+        for (step, state) in self.steps.iter().zip(states).rev() {
+            values = match state {
+                TransformState::Difference { tails, .. } => tails.iter().rev().fold(values, |diffs, &last| {
+                    diffs
+                        .iter()
+                        .scan(last, |level, d| {
+                            *level += d;
+                            Some(*level)
+                        })
+                        .collect()
+                }),
+                TransformState::SeasonalDifference { tail, .. } => {
+                    let period = tail.len();
+                    let mut extended = tail.clone();
+                    for (h, d) in values.iter().enumerate() {
+                        extended.push(d + extended[h]);
+                    }
+                    extended.split_off(period)
+                }
+                _ => Self::invert_pointwise(*step, state, &values),
+            };
+        }
+        Ok(values)
+    }
+
+    /// Inversa das etapas que atuam valor a valor (log, Box-Cox e padronização)
+    fn invert_pointwise(step: Transform, state: &TransformState, values: &[f64]) -> Vec<f64> {
+        match (step, state) {
+            (Transform::Log, _) => inverse_log_transform(values),
+            (Transform::BoxCox(lambda), _) => inverse_box_cox(values, lambda),
+            (_, TransformState::Standardize { mean, std_dev }) => destandardize(values, *mean, *std_dev),
+            _ => values.to_vec(),
+        }
+    }
+}
+
+fn validate_periods(len: usize, periods: usize) -> Result<(), TimeSeriesError> {
+    if periods == 0 {
+        return Err(TimeSeriesError::invalid_parameter(
+            "periods",
+            LocalizedText::new("deve ser maior que zero", "must be greater than zero"),
+        ));
+    }
+    if periods >= len {
+        return Err(TimeSeriesError::insufficient_data(periods + 1, len));
+    }
+    Ok(())
+}
+
+/// Variação percentual: `(x[i] - x[i-periods]) / x[i-periods] · 100` para `i ≥ periods`
+///
+/// O resultado tem `len - periods` valores. Um valor base zero gera erro com o índice
+/// correspondente em vez de infinito.
+pub fn percent_change(data: &[f64], periods: usize) -> Result<Vec<f64>, TimeSeriesError> {
+    validate_periods(data.len(), periods)?;
+    validate_finite(data)?;
+    if let Some(index) = data[..data.len() - periods].iter().position(|&v| v == 0.0) {
+        return Err(TimeSeriesError::new(
+            LocalizedText::new(
+                format!("Divisão por zero: valor base nulo no índice {}", index),
+                format!("Division by zero: zero base value at index {}", index),
+            ),
+        ));
+    }
+
+    Ok(data
+        .windows(periods + 1)
+        .map(|w| (w[periods] - w[0]) / w[0] * 100.0)
+        .collect())
+}
+
+/// Retornos logarítmicos: `ln(x[i] / x[i-periods])` para `i ≥ periods`
+///
+/// O resultado tem `len - periods` valores; exige valores estritamente positivos.
+pub fn log_returns(data: &[f64], periods: usize) -> Result<Vec<f64>, TimeSeriesError> {
+    validate_periods(data.len(), periods)?;
+    validate_positive(data)?;
+    Ok(data.windows(periods + 1).map(|w| (w[periods] / w[0]).ln()).collect())
+}
+
+#[cfg(test)]
+mod testes {
+    use super::*;
+    use crate::testes::assert_approx_eq;
+
+    #[test]
+    fn test_standardize_ida_e_volta() {
+        let data = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        let (z, mean, std_dev) = standardize(&data).unwrap();
+        assert_approx_eq(mean, 5.0, 1e-12);
+        assert_approx_eq(std_dev, 2.0, 1e-12);
+        assert_approx_eq(z[0], -1.5, 1e-12);
+        for (original, restored) in data.iter().zip(destandardize(&z, mean, std_dev)) {
+            assert_approx_eq(*original, restored, 1e-12);
+        }
+        assert!(standardize(&[3.0, 3.0, 3.0]).is_err());
+    }
+
+    #[test]
+    fn test_min_max_ida_e_volta() {
+        let data = [10.0, 15.0, 20.0, 12.5];
+        let (normalized, min, max) = min_max_normalize(&data, -1.0, 1.0).unwrap();
+        assert_eq!((min, max), (10.0, 20.0));
+        assert_approx_eq(normalized[0], -1.0, 1e-12);
+        assert_approx_eq(normalized[1], 0.0, 1e-12);
+        assert_approx_eq(normalized[2], 1.0, 1e-12);
+        for (original, restored) in data.iter().zip(min_max_denormalize(&normalized, -1.0, 1.0, min, max)) {
+            assert_approx_eq(*original, restored, 1e-12);
+        }
+        assert!(min_max_normalize(&[4.0, 4.0], 0.0, 1.0).is_err());
+        assert!(min_max_normalize(&data, 1.0, 1.0).is_err());
+    }
+
+    #[test]
+    fn test_box_cox_ida_e_volta() {
+        let data = [1.5, 2.0, 4.0, 8.0, 20.0];
+        for lambda in [0.0, 0.5, -0.5, 2.0] {
+            let transformed = box_cox(&data, lambda).unwrap();
+            for (original, restored) in data.iter().zip(inverse_box_cox(&transformed, lambda)) {
+                assert_approx_eq(*original, restored, 1e-9);
+            }
+        }
+        let logs = log_transform(&data).unwrap();
+        assert_eq!(box_cox(&data, 0.0).unwrap(), logs);
+        assert_approx_eq(inverse_log_transform(&logs)[4], 20.0, 1e-12);
+
+        assert!(matches!(log_transform(&[1.0, 0.0]), Err(TimeSeriesError::InvalidParameter { name: "data", .. })));
+        assert!(box_cox(&[-1.0, 2.0], 0.5).is_err());
+    }
+
+    #[test]
+    fn test_box_cox_lambda_otimo_log_normal() {
+        // ln(y) simétrico em torno de 2: a log-verossimilhança é simétrica em λ e máxima em 0
+        let data: Vec<f64> = (0..50).map(|i| (2.0 + (i as f64 - 24.5) / 10.0).exp()).collect();
+        let grid: Vec<f64> = (-20..=20).map(|i| i as f64 / 10.0).collect();
+        let lambda = box_cox_optimal_lambda(&data, &grid).unwrap();
+        assert!(lambda.abs() < 0.15, "lambda = {}", lambda);
+
+        // Dados já aproximadamente normais preferem λ perto de 1
+        let linear: Vec<f64> = (0..50).map(|i| 100.0 + i as f64).collect();
+        assert!(box_cox_optimal_lambda(&linear, &grid).unwrap() > 0.5);
+        assert!(box_cox_optimal_lambda(&linear, &[]).is_err());
+    }
+
+    #[test]
+    fn test_box_cox_lambda_otimo_ignora_transbordamento() {
+        // 2^2000 = inf: o λ = 2000 é descartado em vez de interromper a busca
+        assert_eq!(box_cox_optimal_lambda(&[2.0, 3.0, 4.0], &[1.0, 2000.0]).unwrap(), 1.0);
+        assert!(matches!(
+            box_cox_optimal_lambda(&[2.0, 3.0, 4.0], &[2000.0]),
+            Err(TimeSeriesError::InvalidParameter { name: "grid", .. })
+        ));
+    }
+
+    #[test]
+    fn test_percent_change_e_log_returns() {
+        let data = [100.0, 110.0, 99.0, 120.0];
+        let change = percent_change(&data, 1).unwrap();
+        assert_eq!(change.len(), 3);
+        assert_approx_eq(change[0], 10.0, 1e-12);
+        assert_approx_eq(change[1], -10.0, 1e-12);
+        assert_approx_eq(percent_change(&data, 3).unwrap()[0], 20.0, 1e-12);
+
+        let returns = log_returns(&data, 2).unwrap();
+        assert_eq!(returns.len(), 2);
+        assert_approx_eq(returns[0], (0.99f64).ln(), 1e-12);
+
+        let err = percent_change(&[5.0, 0.0, 3.0], 1).unwrap_err();
+        assert!(err.to_string().contains("índice 1"), "{}", err);
+        assert!(log_returns(&[5.0, -1.0, 3.0], 1).is_err());
+        assert_eq!(percent_change(&data, 4), Err(TimeSeriesError::InsufficientData { required: 5, got: 4 }));
+        assert!(percent_change(&data, 0).is_err());
+    }
+
+    #[test]
+    fn test_pipeline_de_transformacoes_ida_e_volta() {
+        let data = generators::seasonal_series(48, 4, 3.0, 0.8, 0.3, 9).unwrap();
+        let data: Vec<f64> = data.iter().map(|v| v + 20.0).collect();
+        let mut pipeline = TransformPipeline::new(vec![
+            Transform::Log,
+            Transform::Difference(2),
+            Transform::SeasonalDifference(4),
+            Transform::Standardize,
+        ]);
+        assert!(pipeline.invert(&[0.0]).is_err());
+
+        let transformed = pipeline.apply(&data).unwrap();
+        assert_eq!(transformed.len(), data.len() - 2 - 4);
+        let restored = pipeline.invert(&transformed).unwrap();
+        assert_eq!(restored.len(), data.len());
+        for (r, d) in restored.iter().zip(&data) {
+            assert_approx_eq(*r, *d, 1e-9);
+        }
+
+        // Log depois de uma diferença com valores negativos falha na etapa 1
+        let mut invalid = TransformPipeline::new(vec![Transform::Difference(1), Transform::Log]);
+        let message = invalid.apply(&[5.0, 3.0, 4.0]).unwrap_err().to_string();
+        assert!(message.contains("etapa 1 (log)"), "{}", message);
+    }
+
+    #[test]
+    fn test_pipeline_previsao_na_escala_original() {
+        // Crescimento de 5% por período: log + diferença dá uma série constante
+        let data: Vec<f64> = (0..20).map(|t| 100.0 * 1.05f64.powi(t)).collect();
+        let mut pipeline = TransformPipeline::new(vec![Transform::Log, Transform::Difference(1)]);
+        let transformed = pipeline.apply(&data).unwrap();
+        let result = linear_regression(&transformed).unwrap();
+        let forecast = pipeline.invert_forecast(&predict_future(&result, 3)).unwrap();
+        for (h, value) in forecast.iter().enumerate() {
+            assert_approx_eq(*value, 100.0 * 1.05f64.powi(20 + h as i32), 1e-6);
+        }
+
+        // Diferença sazonal: o padrão do último ciclo se repete
+        let cycle = [1.0, 5.0, 3.0, 8.0, 2.0, 6.0, 4.0, 9.0];
+        let mut seasonal = TransformPipeline::new(vec![Transform::SeasonalDifference(4)]);
+        seasonal.apply(&cycle).unwrap();
+        assert_eq!(seasonal.invert_forecast(&[1.0, 1.0, 1.0, 1.0, 1.0]).unwrap(), vec![3.0, 7.0, 5.0, 10.0, 4.0]);
+    }
+}
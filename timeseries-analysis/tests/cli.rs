@@ -0,0 +1,213 @@
+#![cfg(feature = "std")]
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+fn run_cli(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_timeseries-analysis"))
+        .args(args)
+        .current_dir(Path::new(env!("CARGO_MANIFEST_DIR")))
+        .output()
+        .expect("falha ao executar o binário")
+}
+
+#[test]
+fn test_cli_analisa_csv() {
+    let output = run_cli(&["tests/fixtures/vendas.csv", "--column", "1", "--forecast", "2", "--no-plot"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(stdout.contains("Observacoes: 5"));
+    assert!(stdout.contains("y = 102.0000 + 14.5000·x"));
+    assert!(stdout.contains("Previsoes para os proximos 2 periodos:"));
+    assert!(stdout.contains("Periodo 7: 189.00"));
+    assert!(!stdout.contains("Legenda:"));
+}
+
+#[test]
+fn test_cli_erros() {
+    let output = run_cli(&["tests/fixtures/invalido.txt"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Linha 3"));
+
+    let output = run_cli(&["tests/fixtures/inexistente.csv"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Erro ao ler"));
+
+    let output = run_cli(&[]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Uso:"));
+}
+
+/// Valor JSON mínimo, suficiente para inspecionar a saída do binário
+/// (strings só aparecem como chaves e booleanos não são emitidos)
+#[derive(Debug)]
+enum Json {
+    Null,
+    Number(f64),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    fn get(&self, key: &str) -> &Json {
+        match self {
+            Json::Object(fields) => &fields.iter().find(|(k, _)| k == key).expect(key).1,
+            _ => panic!("não é um objeto: {:?}", self),
+        }
+    }
+
+    fn as_f64(&self) -> f64 {
+        match self {
+            Json::Number(n) => *n,
+            _ => panic!("não é um número: {:?}", self),
+        }
+    }
+}
+
+fn parse_json(text: &str) -> Json {
+    fn skip_ws(s: &[u8], pos: &mut usize) {
+        while *pos < s.len() && s[*pos].is_ascii_whitespace() {
+            *pos += 1;
+        }
+    }
+
+    fn parse_string(s: &[u8], pos: &mut usize) -> String {
+        assert_eq!(s[*pos], b'"');
+        *pos += 1;
+        let mut out = String::new();
+        while s[*pos] != b'"' {
+            if s[*pos] == b'\\' {
+                *pos += 1;
+                out.push(match s[*pos] {
+                    b'n' => '\n',
+                    b't' => '\t',
+                    b'r' => '\r',
+                    c => c as char,
+                });
+            } else {
+                out.push(s[*pos] as char);
+            }
+            *pos += 1;
+        }
+        *pos += 1;
+        out
+    }
+
+    fn parse_value(s: &[u8], pos: &mut usize) -> Json {
+        skip_ws(s, pos);
+        match s[*pos] {
+            b'{' => {
+                *pos += 1;
+                let mut fields = Vec::new();
+                loop {
+                    skip_ws(s, pos);
+                    if s[*pos] == b'}' {
+                        *pos += 1;
+                        return Json::Object(fields);
+                    }
+                    let key = parse_string(s, pos);
+                    skip_ws(s, pos);
+                    assert_eq!(s[*pos], b':');
+                    *pos += 1;
+                    fields.push((key, parse_value(s, pos)));
+                    skip_ws(s, pos);
+                    if s[*pos] == b',' {
+                        *pos += 1;
+                    }
+                }
+            }
+            b'[' => {
+                *pos += 1;
+                let mut items = Vec::new();
+                loop {
+                    skip_ws(s, pos);
+                    if s[*pos] == b']' {
+                        *pos += 1;
+                        return Json::Array(items);
+                    }
+                    items.push(parse_value(s, pos));
+                    skip_ws(s, pos);
+                    if s[*pos] == b',' {
+                        *pos += 1;
+                    }
+                }
+            }
+            b'n' => {
+                *pos += 4;
+                Json::Null
+            }
+            b'-' | b'0'..=b'9' => {
+                let start = *pos;
+                while *pos < s.len() && matches!(s[*pos], b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9') {
+                    *pos += 1;
+                }
+                let number = std::str::from_utf8(&s[start..*pos]).unwrap();
+                Json::Number(number.parse().expect("número JSON inválido"))
+            }
+            c => panic!("valor JSON não suportado começando com '{}'", c as char),
+        }
+    }
+
+    let bytes = text.as_bytes();
+    let mut pos = 0;
+    let value = parse_value(bytes, &mut pos);
+    skip_ws(bytes, &mut pos);
+    assert_eq!(pos, bytes.len(), "conteúdo após o documento JSON");
+    value
+}
+
+#[test]
+fn test_cli_saida_json() {
+    let output = run_cli(&["tests/fixtures/vendas.csv", "--column", "1", "--output", "json"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let document = parse_json(&String::from_utf8_lossy(&output.stdout));
+
+    let data = timeseries_analysis::load_series("tests/fixtures/vendas.csv", 1).unwrap();
+    let expected = timeseries_analysis::linear_regression(&data).unwrap();
+    let regression = document.get("regression");
+    assert!((regression.get("slope").as_f64() - expected.slope).abs() < 1e-12);
+    assert!((regression.get("intercept").as_f64() - expected.intercept).abs() < 1e-12);
+    assert_eq!(document.get("stats").get("count").as_f64(), 5.0);
+    assert!(matches!(document.get("metrics").get("rmse"), Json::Number(_)));
+    match document.get("forecasts") {
+        Json::Array(items) => assert_eq!(items.len(), 3),
+        other => panic!("forecasts não é um array: {:?}", other),
+    }
+}
+
+#[test]
+fn test_cli_saida_csv() {
+    let output = run_cli(&["tests/fixtures/vendas.csv", "--column", "1", "--forecast", "1", "--output", "csv"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 7);
+    assert_eq!(lines[0], "period,actual,fitted,forecast");
+    assert!(lines[1].starts_with("1,100,"));
+    assert_eq!(lines[6], "6,,,174.5");
+    assert!(!stdout.contains("Legenda:"));
+
+    let output = run_cli(&["tests/fixtures/vendas.csv", "--output", "xml"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_cli_decimals() {
+    let output = run_cli(&["tests/fixtures/vendas.csv", "--column", "1", "--forecast", "1", "--decimals", "1", "--no-plot"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("y = 102.0 + 14.5·x"));
+    assert!(stdout.contains("Periodo 6: 174.5\n"));
+
+    let output = run_cli(&["tests/fixtures/vendas.csv", "--column", "1", "--forecast", "1", "--decimals", "0", "--output", "csv"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().nth(1), Some("1,100,102,"));
+
+    let output = run_cli(&["tests/fixtures/vendas.csv", "--column", "1", "--decimals", "3", "--output", "json"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("\"slope\":14.500"));
+    assert_eq!(parse_json(&stdout).get("stats").get("mean").as_f64(), 131.0);
+
+    let output = run_cli(&["tests/fixtures/vendas.csv", "--decimals", "x"]);
+    assert_eq!(output.status.code(), Some(2));
+}
@@ -0,0 +1,20 @@
+//! O idioma global do `Display` fica num binário de teste próprio: mudá-lo afetaria os
+//! testes que comparam mensagens em português
+
+use timeseries_analysis::*;
+
+#[test]
+fn test_idioma_global_das_mensagens() {
+    let err = linear_regression(&[1.0, f64::NAN]).unwrap_err();
+    assert_eq!(message_language(), Language::Pt);
+    assert_eq!(err.to_string(), "TimeSeriesError: Valor não finito no índice 1");
+
+    set_message_language(Language::En);
+    assert_eq!(message_language(), Language::En);
+    assert_eq!(err.to_string(), "TimeSeriesError: Non-finite value at index 1");
+    let invalid = generators::random_walk(0, 1.0, 1).unwrap_err();
+    assert_eq!(invalid.to_string(), "TimeSeriesError: Invalid parameter 'n': must be at least 1");
+
+    set_message_language(Language::Pt);
+    assert_eq!(invalid.to_string(), "TimeSeriesError: Parâmetro inválido 'n': deve ser ao menos 1");
+}
@@ -0,0 +1,22 @@
+use std::path::Path;
+use std::process::Command;
+
+#[test]
+fn test_compila_sem_std() {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let consumer = manifest_dir.join("tests/no_std_consumer/Cargo.toml");
+    let target_dir = manifest_dir.join("target/no_std_consumer");
+
+    let output = Command::new(env!("CARGO"))
+        .args(["build", "--offline", "--quiet", "--manifest-path"])
+        .arg(&consumer)
+        .env("CARGO_TARGET_DIR", &target_dir)
+        .output()
+        .expect("falha ao executar cargo");
+
+    assert!(
+        output.status.success(),
+        "consumidor no_std não compilou:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
@@ -0,0 +1,23 @@
+//! Consumidor mínimo `no_std` usado por `tests/no_std.rs` para garantir que a
+//! biblioteca compila sem a `std`
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use timeseries_analysis::{
+    calculate_descriptive_stats, evaluate_all, linear_regression, predict_future, TimeSeriesError,
+};
+
+pub fn forecast_next(data: &[f64], periods: usize) -> Result<Vec<f64>, TimeSeriesError> {
+    let result = linear_regression(data)?;
+    evaluate_all(data, &result.predictions)?;
+    calculate_descriptive_stats(data)?;
+    Ok(predict_future(&result, periods))
+}
+
+pub fn forecast_next_f32(data: &[f32], periods: usize) -> Result<Vec<f32>, TimeSeriesError> {
+    let result = linear_regression(data)?;
+    Ok(predict_future(&result, periods))
+}
@@ -0,0 +1,130 @@
+#![cfg(feature = "std")]
+
+mod snapshot;
+
+use snapshot::assert_snapshot;
+use timeseries_analysis::*;
+
+const VENDAS: [f64; 5] = [100.0, 120.0, 130.0, 145.0, 160.0];
+
+fn render(component: &dyn Renderable) -> String {
+    component.render().expect("falha ao renderizar")
+}
+
+#[test]
+fn test_snapshot_ascii_plot() {
+    assert_snapshot("ascii_plot", &render(&LinePlot::new(&[1.0, 3.0, 2.0], &[1.0, 2.0, 3.0], "Teste")));
+
+    let long: Vec<f64> = (0..40).map(|i| (i as f64 * 0.4).sin() * 10.0).collect();
+    let grouped = LinePlot {
+        config: PlotConfig { width: 20, height: 6, ..PlotConfig::default() },
+        ..LinePlot::new(&long, &long, "Agrupado")
+    };
+    assert_snapshot("ascii_plot_agrupado", &render(&grouped));
+
+    let english = LinePlot {
+        config: PlotConfig { language: Language::En, y_label_precision: 0, ..PlotConfig::default() },
+        ..LinePlot::new(&VENDAS, &[102.0, 116.5, 131.0, 145.5, 160.0], "Sales")
+    };
+    assert_snapshot("ascii_plot_ingles", &render(&english));
+}
+
+#[test]
+fn test_snapshot_ascii_plot_previsao_e_rotulos() {
+    let result = linear_regression(&VENDAS).unwrap();
+    let forecasts = predict_future(&result, 3);
+    assert_snapshot("ascii_plot_previsao", &render(&ForecastPlot::new(&VENDAS, &result, &forecasts, "Vendas")));
+
+    let configured = ForecastPlot {
+        config: PlotConfig { height: 5, y_label_precision: 3, language: Language::En, ..PlotConfig::default() },
+        ..ForecastPlot::new(&VENDAS, &result, &forecasts, "Sales")
+    };
+    assert_snapshot("ascii_plot_previsao_config", &render(&configured));
+
+    let labels: Vec<String> = ["jan", "fev", "mar", "abr", "mai"].iter().map(|s| s.to_string()).collect();
+    let labeled = LabeledPlot {
+        actual: &VENDAS,
+        predicted: &result.predictions,
+        labels: &labels,
+        title: "Meses",
+    };
+    assert_snapshot("ascii_plot_rotulos", &render(&labeled));
+}
+
+#[test]
+fn test_snapshot_grafico_com_faixa() {
+    let actual = [0.0, 2.0, 4.0, 6.0, 8.0, 10.0];
+    let point = |period: usize, value: f64, lower: f64, upper: f64| ForecastPoint {
+        period,
+        x: period as f64,
+        value,
+        lower,
+        upper,
+    };
+    // Faixa de 9 a 14 no primeiro período; largura zero no segundo
+    let forecast = [point(6, 12.0, 9.0, 14.0), point(7, 14.0, 14.0, 14.0)];
+    let config = PlotConfig { height: 7, ..PlotConfig::default() };
+    assert_snapshot("ascii_plot_faixa", &ascii_plot_with_band(&actual, &forecast, "Faixa", &config).unwrap());
+}
+
+#[test]
+fn test_snapshot_ascii_multi_plot() {
+    let raw = [1.0, 4.0, 2.0, 5.0];
+    let smooth = [1.0, 2.5, 3.0, 3.5];
+    let fit = [1.5, 2.5, 3.5, 4.5];
+    let multi = MultiPlot {
+        series: &[("Dados", &raw), ("Media movel", &smooth), ("Ajuste", &fit)],
+        title: "Varias",
+        config: PlotConfig { height: 4, ..PlotConfig::default() },
+    };
+    assert_snapshot("ascii_multi_plot", &render(&multi));
+}
+
+#[test]
+fn test_snapshot_histograma_sparkline_boxplot() {
+    let data = [0.0, 1.0, 1.5, 2.0, 2.5, 3.0, 4.0, 2.2, 1.8, 2.1];
+    assert_snapshot("histograma", &render(&Histogram { data: &data, bins: 4 }));
+
+    let sparklines = format!(
+        "{}\n{}\n",
+        render(&Sparkline::new(&data)),
+        render(&Sparkline { data: &data, style: SparklineStyle::Ascii })
+    );
+    assert_snapshot("sparkline", &sparklines);
+
+    let outlier = [1.0, 2.0, 2.5, 3.0, 3.5, 4.0, 15.0];
+    assert_snapshot("boxplot", &render(&BoxPlot { series: &[("dados", &data), ("outlier", &outlier)] }));
+}
+
+#[test]
+fn test_snapshot_relatorio() {
+    let report = Report {
+        data: &VENDAS,
+        options: ReportOptions { title: "VENDAS".to_string(), ..ReportOptions::default() },
+    };
+    assert_snapshot("relatorio", &render(&report));
+
+    let stats = calculate_descriptive_stats(&VENDAS).unwrap();
+    let result = linear_regression(&VENDAS).unwrap();
+    let metrics = evaluate_all(&VENDAS, &result.predictions).unwrap();
+    let sections = [render(&stats), render(&result), render(&metrics), render(&analyze(&VENDAS, 2).unwrap())];
+    assert_snapshot("secoes", &sections.join("\n\n"));
+}
+
+#[test]
+fn test_snapshot_relatorio_completo_e_resumo() {
+    let data = [10.0, 12.0, 11.0, 14.0, 13.0, 40.0, 16.0, 15.0, 18.0, 17.0];
+    let options = ReportOptions {
+        plot: false,
+        forecast_periods: 2,
+        anomaly_threshold: 2.5,
+        ..ReportOptions::default()
+    };
+    assert_snapshot("relatorio_completo", &generate_report(&data, &options).unwrap());
+
+    let data = [3.0, 5.0, 7.5, 8.5, 11.0];
+    assert_snapshot("resumo_regressao", &linear_regression(&data).unwrap().summary(&data));
+
+    let stats = calculate_descriptive_stats(&[1000.0, 2500.5, 4000.25]).unwrap();
+    assert_snapshot("estatisticas_6_casas", &stats.format_with(&FormatOptions::with_decimals(6)));
+}
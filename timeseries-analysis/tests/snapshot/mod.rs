@@ -0,0 +1,74 @@
+//! Testes de snapshot: compara a saída com arquivos `.snap` em `tests/snapshots/`
+//!
+//! Com `UPDATE_SNAPSHOTS=1`, os arquivos são (re)escritos com a saída atual em vez de
+//! comparados — revise o `git diff` antes de versioná-los.
+
+use std::path::PathBuf;
+
+fn snapshot_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots").join(format!("{}.snap", name))
+}
+
+fn update_mode() -> bool {
+    std::env::var("UPDATE_SNAPSHOTS").is_ok_and(|value| value == "1")
+}
+
+/// Compara `actual` com o snapshot `name`, falhando com um diff linha a linha
+pub fn assert_snapshot(name: &str, actual: &str) {
+    let path = snapshot_path(name);
+    if update_mode() {
+        std::fs::write(&path, actual).unwrap_or_else(|err| panic!("falha ao escrever {}: {}", path.display(), err));
+        return;
+    }
+    let expected = std::fs::read_to_string(&path).unwrap_or_else(|err| {
+        panic!(
+            "snapshot '{}' indisponível ({}): rode com UPDATE_SNAPSHOTS=1 para criá-lo",
+            name, err
+        )
+    });
+    if expected != actual {
+        panic!(
+            "snapshot '{}' diverge (- esperado, + atual); rode com UPDATE_SNAPSHOTS=1 se a mudança for intencional\n{}",
+            name,
+            line_diff(&expected, actual)
+        );
+    }
+}
+
+/// Diff por maior subsequência comum de linhas; `·` marca espaços no fim da linha
+fn line_diff(expected: &str, actual: &str) -> String {
+    let old: Vec<&str> = expected.split('\n').collect();
+    let new: Vec<&str> = actual.split('\n').collect();
+    let (n, m) = (old.len(), new.len());
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let show = |line: &str| {
+        let trimmed = line.trim_end_matches(' ');
+        format!("{}{}", trimmed, "·".repeat(line.len() - trimmed.len()))
+    };
+    let mut out = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && old[i] == new[j] {
+            out.push_str(&format!("  {}\n", show(old[i])));
+            i += 1;
+            j += 1;
+        } else if i < n && (j == m || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            out.push_str(&format!("- {}\n", show(old[i])));
+            i += 1;
+        } else {
+            out.push_str(&format!("+ {}\n", show(new[j])));
+            j += 1;
+        }
+    }
+    out
+}